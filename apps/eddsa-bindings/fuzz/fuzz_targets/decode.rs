@@ -0,0 +1,16 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use multi_party_eddsa_node::{try_decode_point, try_decode_scalar};
+
+// Run with `cargo +nightly fuzz run decode` from apps/eddsa-bindings.
+fuzz_target!(|data: &[u8]| {
+    if let Some(point) = try_decode_point(data.to_vec()) {
+        // the canonical encoding must decode back to itself
+        let again = try_decode_point(point.bytes.clone()).expect("canonical point rejected");
+        assert_eq!(again.bytes, point.bytes);
+    }
+    if let Some(scalar) = try_decode_scalar(data.to_vec()) {
+        assert_eq!(scalar.bytes.len(), 32);
+    }
+});
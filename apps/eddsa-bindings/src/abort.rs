@@ -0,0 +1,75 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use napi::Status;
+
+use crate::error::TssError;
+use crate::store::{lock, Store};
+
+pub(crate) fn abort_tokens_store() -> &'static Mutex<Store<Arc<AtomicBool>>> {
+    static STORE: OnceLock<Mutex<Store<Arc<AtomicBool>>>> = OnceLock::new();
+    STORE.get_or_init(|| Mutex::new(Store::new()))
+}
+
+/// Create a token that lets a coordinator cancel an operation it was passed to, e.g. on a
+/// ceremony timeout. Release it with `release_abort_token` once the operation is over.
+#[napi_derive::napi]
+pub fn create_abort_token() -> napi::Result<String> {
+    crate::guard::guard("create_abort_token", || {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let token = format!("abort_{}", COUNTER.fetch_add(1, Ordering::Relaxed));
+        lock(abort_tokens_store())
+            .insert(token.clone(), Arc::new(AtomicBool::new(false)), |_| false);
+        Ok(token)
+    })
+}
+
+/// Abort every operation running with `token`; each returns `Aborted` at its next per-party
+/// step, as do later operations passed the same token. False if the token is unknown.
+#[napi_derive::napi]
+pub fn abort(token: String) -> napi::Result<bool> {
+    crate::guard::guard("abort", || {
+        Ok(match lock(abort_tokens_store()).get(&token) {
+            Some(flag) => {
+                flag.store(true, Ordering::Relaxed);
+                true
+            }
+            None => false,
+        })
+    })
+}
+
+/// Forget a token. Operations already holding it can still be aborted through it.
+#[napi_derive::napi]
+pub fn release_abort_token(token: String) -> napi::Result<bool> {
+    crate::guard::guard("release_abort_token", || {
+        Ok(lock(abort_tokens_store()).remove(&token).is_some())
+    })
+}
+
+// The flag behind an optional token, resolved once so the per-party checks never touch the
+// store
+pub(crate) struct AbortSignal(Option<Arc<AtomicBool>>);
+
+impl AbortSignal {
+    pub(crate) fn from_token(token: Option<String>) -> napi::Result<Self> {
+        let Some(token) = token else {
+            return Ok(AbortSignal(None));
+        };
+        let flag = lock(abort_tokens_store())
+            .get(&token)
+            .cloned()
+            .ok_or_else(|| napi::Error::new(Status::InvalidArg, "Abort token not found"))?;
+        Ok(AbortSignal(Some(flag)))
+    }
+
+    pub(crate) fn check(&self, operation: &'static str) -> napi::Result<()> {
+        match &self.0 {
+            Some(flag) if flag.load(Ordering::Relaxed) => {
+                Err(TssError::Aborted { operation }.into())
+            }
+            _ => Ok(()),
+        }
+    }
+}
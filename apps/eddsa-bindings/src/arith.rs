@@ -0,0 +1,179 @@
+use curv::cryptographic_primitives::secret_sharing::feldman_vss::{
+    ShamirSecretSharing, VerifiableSS,
+};
+use curv::elliptic::curves::{Ed25519, Scalar};
+use curv::BigInt;
+use napi::Status;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    check_distinct_parties, check_non_empty, point_to_serializable, scalar_to_serializable,
+    serializable_to_point, serializable_to_scalar, SerializablePoint, SerializableScalar,
+};
+
+// Group arithmetic over the Ed25519 order for integrators writing their own
+// aggregation steps. Results are always reduced, canonical encodings.
+
+/// a + b mod l
+#[napi_derive::napi]
+pub fn scalar_add(
+    a: SerializableScalar,
+    b: SerializableScalar,
+) -> napi::Result<SerializableScalar> {
+    crate::guard::guard("scalar_add", || {
+        Ok(scalar_to_serializable(
+            &(serializable_to_scalar(&a)? + serializable_to_scalar(&b)?),
+        ))
+    })
+}
+
+/// a - b mod l
+#[napi_derive::napi]
+pub fn scalar_sub(
+    a: SerializableScalar,
+    b: SerializableScalar,
+) -> napi::Result<SerializableScalar> {
+    crate::guard::guard("scalar_sub", || {
+        Ok(scalar_to_serializable(
+            &(serializable_to_scalar(&a)? - serializable_to_scalar(&b)?),
+        ))
+    })
+}
+
+/// a * b mod l
+#[napi_derive::napi]
+pub fn scalar_mul(
+    a: SerializableScalar,
+    b: SerializableScalar,
+) -> napi::Result<SerializableScalar> {
+    crate::guard::guard("scalar_mul", || {
+        Ok(scalar_to_serializable(
+            &(serializable_to_scalar(&a)? * serializable_to_scalar(&b)?),
+        ))
+    })
+}
+
+/// a^-1 mod l; zero has no inverse and is rejected
+#[napi_derive::napi]
+pub fn scalar_invert(a: SerializableScalar) -> napi::Result<SerializableScalar> {
+    crate::guard::guard("scalar_invert", || {
+        serializable_to_scalar(&a)?
+            .invert()
+            .map(|inverse| scalar_to_serializable(&inverse))
+            .ok_or_else(|| napi::Error::new(Status::InvalidArg, "Zero has no inverse"))
+    })
+}
+
+/// Scalar with the given integer value. JS numbers are exact only up to 2^53,
+/// and negative values are rejected.
+#[napi_derive::napi]
+pub fn scalar_from_u64(value: i64) -> napi::Result<SerializableScalar> {
+    crate::guard::guard("scalar_from_u64", || {
+        let value = u64::try_from(value)
+            .map_err(|_| napi::Error::new(Status::InvalidArg, "Value must not be negative"))?;
+        Ok(scalar_to_serializable(&Scalar::<Ed25519>::from_bigint(
+            &BigInt::from(value),
+        )))
+    })
+}
+
+/// Scalar for a party index, so Lagrange coefficients can be computed over
+/// indices without building the little-endian 32-byte encoding by hand.
+#[napi_derive::napi]
+pub fn scalar_from_index(index: u16) -> SerializableScalar {
+    scalar_to_serializable(&Scalar::<Ed25519>::from(u64::from(index)))
+}
+
+/// Integer value of a scalar, read from its little-endian encoding; None if it
+/// exceeds the range of `scalar_from_u64`, i.e. does not fit in an i64.
+#[napi_derive::napi]
+pub fn scalar_to_u64(s: SerializableScalar) -> napi::Result<Option<i64>> {
+    crate::guard::guard("scalar_to_u64", || {
+        let bytes = serializable_to_scalar(&s)?.to_bytes();
+        if bytes[8..].iter().any(|&b| b != 0) {
+            return Ok(None);
+        }
+        let mut low = [0u8; 8];
+        low.copy_from_slice(&bytes[..8]);
+        Ok(i64::try_from(u64::from_le_bytes(low)).ok())
+    })
+}
+
+/// a + b
+#[napi_derive::napi]
+pub fn point_add(a: SerializablePoint, b: SerializablePoint) -> napi::Result<SerializablePoint> {
+    crate::guard::guard("point_add", || {
+        Ok(point_to_serializable(
+            &(serializable_to_point(&a)? + serializable_to_point(&b)?),
+        ))
+    })
+}
+
+/// s * p
+#[napi_derive::napi]
+pub fn point_mul_scalar(
+    p: SerializablePoint,
+    s: SerializableScalar,
+) -> napi::Result<SerializablePoint> {
+    crate::guard::guard("point_mul_scalar", || {
+        Ok(point_to_serializable(
+            &(serializable_to_point(&p)? * serializable_to_scalar(&s)?),
+        ))
+    })
+}
+
+/// One signer's term in the interpolation at zero, as `debug_lagrange_points` reports it
+#[napi_derive::napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableDebugPoint {
+    #[serde(rename = "party_index")]
+    #[napi(js_name = "partyIndex")]
+    pub party_index: u16,
+    #[serde(rename = "eval_point")]
+    #[napi(js_name = "evalPoint")]
+    pub eval_point: SerializableScalar,
+    pub coefficient: SerializableScalar,
+}
+
+/// DEBUGGING AID: the Lagrange terms `aggregate_signature` and `generate_signature` use to
+/// interpolate the local signatures of `parties_index` (0-based, as passed to them) at zero.
+/// Party i is evaluated at i + 1 and weighted by prod_{j != i} (j + 1) / (j - i), over the
+/// whole set given; aggregation interpolates over the first threshold + 1 signers only, so
+/// pass those to see its coefficients. Not needed for signing.
+#[napi_derive::napi]
+pub fn debug_lagrange_points(
+    parties_index: Vec<u16>,
+    share_count: u16,
+) -> napi::Result<Vec<SerializableDebugPoint>> {
+    crate::guard::guard("debug_lagrange_points", || {
+        check_non_empty("parties_index", parties_index.len())?;
+        check_distinct_parties(&parties_index)?;
+        if let Some(index) = parties_index.iter().find(|&&index| index >= share_count) {
+            return Err(napi::Error::new(
+                Status::InvalidArg,
+                format!(
+                    "Party index {} is out of range for {} shares",
+                    index, share_count
+                ),
+            ));
+        }
+        let params = ShamirSecretSharing {
+            threshold: (parties_index.len() - 1) as u16,
+            share_count,
+        };
+        Ok(parties_index
+            .iter()
+            .map(|&index| SerializableDebugPoint {
+                party_index: index,
+                eval_point: scalar_to_serializable(&Scalar::<Ed25519>::from(u64::from(index) + 1)),
+                coefficient: scalar_to_serializable(
+                    &VerifiableSS::<Ed25519>::map_share_to_new_params(
+                        &params,
+                        index,
+                        &parties_index,
+                    ),
+                ),
+            })
+            .collect())
+    })
+}
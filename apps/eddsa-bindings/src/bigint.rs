@@ -0,0 +1,100 @@
+use curv::arithmetic::Converter;
+use curv::BigInt;
+use napi::Status;
+
+use crate::{bigint_to_serializable, serializable_to_bigint, SerializableBigInt};
+
+// Conversions between the big-endian `SerializableBigInt` encoding the protocol
+// uses (commitments, blind factors) and explicit byte orders, for integrators
+// whose tooling assumes little-endian. All outputs are minimal: no leading
+// zeros in big-endian order, no trailing zeros in little-endian order, except
+// the fixed-length blind factor encoding below.
+
+/// Bytes of randomness in a commitment blind factor
+pub(crate) const BLIND_FACTOR_BYTES: usize = 32;
+
+/// Big-endian bytes of `value`, the order `SerializableBigInt` already uses
+#[napi_derive::napi]
+pub fn bigint_to_bytes_be(value: SerializableBigInt) -> napi::Result<Vec<u8>> {
+    crate::guard::guard("bigint_to_bytes_be", || {
+        Ok(serializable_to_bigint(&value).to_bytes())
+    })
+}
+
+/// Little-endian bytes of `value`
+#[napi_derive::napi]
+pub fn bigint_to_bytes_le(value: SerializableBigInt) -> napi::Result<Vec<u8>> {
+    crate::guard::guard("bigint_to_bytes_le", || {
+        let mut bytes = serializable_to_bigint(&value).to_bytes();
+        bytes.reverse();
+        Ok(bytes)
+    })
+}
+
+/// Decode big-endian bytes into the protocol encoding
+#[napi_derive::napi]
+pub fn bigint_from_bytes_be(bytes: Vec<u8>) -> napi::Result<SerializableBigInt> {
+    crate::guard::guard("bigint_from_bytes_be", || {
+        Ok(bigint_to_serializable(&BigInt::from_bytes(&bytes)))
+    })
+}
+
+/// Decode little-endian bytes into the protocol encoding
+#[napi_derive::napi]
+pub fn bigint_from_bytes_le(mut bytes: Vec<u8>) -> napi::Result<SerializableBigInt> {
+    crate::guard::guard("bigint_from_bytes_le", || {
+        bytes.reverse();
+        Ok(bigint_to_serializable(&BigInt::from_bytes(&bytes)))
+    })
+}
+
+/// Number of bytes in the fixed-length blind factor encoding
+#[napi_derive::napi]
+pub fn blind_factor_length() -> u32 {
+    BLIND_FACTOR_BYTES as u32
+}
+
+// Big-endian and zero-padded on the left, so the length does not depend on leading zeros.
+// Still a valid big-endian magnitude, so it can go in a `SerializableBigInt` as is.
+pub(crate) fn blind_factor_fixed_bytes(blind_factor: &BigInt) -> napi::Result<Vec<u8>> {
+    let bytes = blind_factor.to_bytes();
+    if bytes.len() > BLIND_FACTOR_BYTES {
+        return Err(napi::Error::new(
+            Status::InvalidArg,
+            format!(
+                "Blind factor of {} bytes exceeds {} bytes",
+                bytes.len(),
+                BLIND_FACTOR_BYTES
+            ),
+        ));
+    }
+    let mut fixed = vec![0u8; BLIND_FACTOR_BYTES - bytes.len()];
+    fixed.extend(bytes);
+    Ok(fixed)
+}
+
+/// Encode a blind factor as exactly `blind_factor_length()` big-endian bytes, zero-padded
+#[napi_derive::napi]
+pub fn blind_factor_to_fixed_bytes(blind_factor: SerializableBigInt) -> napi::Result<Vec<u8>> {
+    crate::guard::guard("blind_factor_to_fixed_bytes", || {
+        blind_factor_fixed_bytes(&serializable_to_bigint(&blind_factor))
+    })
+}
+
+/// Decode a blind factor from exactly `blind_factor_length()` big-endian bytes
+#[napi_derive::napi]
+pub fn blind_factor_from_fixed_bytes(bytes: Vec<u8>) -> napi::Result<SerializableBigInt> {
+    crate::guard::guard("blind_factor_from_fixed_bytes", || {
+        if bytes.len() != BLIND_FACTOR_BYTES {
+            return Err(napi::Error::new(
+                Status::InvalidArg,
+                format!(
+                    "Blind factor must be {} bytes, got {}",
+                    BLIND_FACTOR_BYTES,
+                    bytes.len()
+                ),
+            ));
+        }
+        Ok(bigint_to_serializable(&BigInt::from_bytes(&bytes)))
+    })
+}
@@ -0,0 +1,35 @@
+use cryptoxide::blake2b;
+use cryptoxide::digest::Digest as _;
+use sha2::digest::consts::U64;
+use sha2::digest::{FixedOutputDirty, Output, Reset, Update};
+
+/// BLAKE2b-512 behind the `digest` 0.9 traits the core hashes with, so it can
+/// stand in for SHA-512 in the challenge and nonce derivation.
+#[derive(Clone)]
+pub(crate) struct Blake2b512(blake2b::Blake2b);
+
+impl Default for Blake2b512 {
+    fn default() -> Self {
+        Blake2b512(blake2b::Blake2b::new(64))
+    }
+}
+
+impl Update for Blake2b512 {
+    fn update(&mut self, data: impl AsRef<[u8]>) {
+        self.0.input(data.as_ref());
+    }
+}
+
+impl FixedOutputDirty for Blake2b512 {
+    type OutputSize = U64;
+
+    fn finalize_into_dirty(&mut self, out: &mut Output<Self>) {
+        self.0.result(out);
+    }
+}
+
+impl Reset for Blake2b512 {
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex, OnceLock};
+
+use napi::Status;
+
+use crate::error::TssError;
+use crate::store::{lock, Store};
+use crate::threshold_sig;
+use crate::threshold_sig::ChallengeHash;
+use crate::{
+    SerializableLocalSig, SerializablePoint, SerializableScalar, SerializableSignature,
+    SerializableVerifiableSS,
+};
+
+// Aggregation of local signatures as they arrive, for a coordinator that
+// already knows R and the VSS schemes. Each signature is checked on its own
+// against its signer's commitments, so a bad one is rejected when it arrives
+// rather than failing the whole batch in `verify_local_sigs`. The challenge k
+// is fixed from the message when the collector is created, so a signature for
+// another message cannot decide which ones are accepted.
+#[derive(Clone)]
+pub(crate) struct SigCollector {
+    R: SerializablePoint,
+    k: SerializableScalar,
+    vss_private_keys: Vec<SerializableVerifiableSS>,
+    vss_ephemeral_keys: Vec<SerializableVerifiableSS>,
+    // accepted signatures by 0-based party index
+    local_sigs: BTreeMap<u16, SerializableLocalSig>,
+}
+
+pub(crate) fn sig_collectors_store() -> &'static Mutex<Store<SigCollector>> {
+    static STORE: OnceLock<Mutex<Store<SigCollector>>> = OnceLock::new();
+    STORE.get_or_init(|| Mutex::new(Store::new()))
+}
+
+fn not_found() -> napi::Error {
+    napi::Error::new(Status::InvalidArg, "Signature collector not found")
+}
+
+/// Start collecting local signatures on `message` under `session_id` for the aggregate `R`.
+/// The ephemeral VSS schemes must sum to `R`, so a collector is never set up for the wrong
+/// round. `hash` selects the challenge hash the signers use, as in `compute_local_sig_bytes`.
+#[napi_derive::napi]
+pub fn sig_collector_new(
+    session_id: String,
+    R: SerializablePoint,
+    message: Vec<u8>,
+    vss_private_keys: Vec<SerializableVerifiableSS>,
+    vss_ephemeral_keys: Vec<SerializableVerifiableSS>,
+    hash: Option<ChallengeHash>,
+) -> napi::Result<()> {
+    crate::guard::guard("sig_collector_new", || {
+        threshold_sig::check_message_size(&message)?;
+        let y = threshold_sig::sum_zeroth_commitments("vss_private_keys", &vss_private_keys)?;
+        threshold_sig::derive_aggregate_R_from_vss(vss_ephemeral_keys.clone(), Some(R.clone()))?;
+        let k = threshold_sig::challenge_with_hash(
+            &crate::serializable_to_point(&R)?,
+            &y,
+            &message,
+            hash,
+        );
+        let mut collectors = lock(sig_collectors_store());
+        if collectors.get(&session_id).is_some() {
+            return Err(napi::Error::new(
+                Status::InvalidArg,
+                "Signature collector already exists",
+            ));
+        }
+        let collector = SigCollector {
+            R,
+            k: crate::scalar_to_serializable(&k),
+            vss_private_keys,
+            vss_ephemeral_keys,
+            local_sigs: BTreeMap::new(),
+        };
+        collectors.insert(session_id, collector, |_| false);
+        Ok(())
+    })
+}
+
+/// Check the local signature of the signer at 0-based `party_index` against its commitments
+/// and keep it if it binds; false rejects it and leaves the collector unchanged. A signature
+/// whose challenge k is not the one of the collector's message and R is rejected too.
+#[napi_derive::napi]
+pub fn sig_collector_add(
+    session_id: String,
+    party_index: u16,
+    local_sig: SerializableLocalSig,
+) -> napi::Result<bool> {
+    crate::guard::guard("sig_collector_add", || {
+        let collector = lock(sig_collectors_store())
+            .get(&session_id)
+            .cloned()
+            .ok_or_else(not_found)?;
+        if collector.local_sigs.contains_key(&party_index) {
+            return Err(TssError::DuplicatePartyIndex { index: party_index }.into());
+        }
+        if crate::serializable_to_scalar(&local_sig.k)?
+            != crate::serializable_to_scalar(&collector.k)?
+        {
+            return Ok(false);
+        }
+        if !threshold_sig::local_sig_binds_party(
+            local_sig.clone(),
+            party_index,
+            collector.vss_private_keys,
+            collector.vss_ephemeral_keys,
+        )? {
+            return Ok(false);
+        }
+        lock(sig_collectors_store())
+            .get_mut(&session_id)
+            .ok_or_else(not_found)?
+            .local_sigs
+            .insert(party_index, local_sig);
+        Ok(true)
+    })
+}
+
+/// Aggregate the accepted local signatures into the signature under R and close the
+/// collector; errors, leaving it open, while fewer than threshold + 1 have been accepted
+#[napi_derive::napi]
+pub fn sig_collector_finalize(session_id: String) -> napi::Result<SerializableSignature> {
+    crate::guard::guard("sig_collector_finalize", || {
+        let collector = lock(sig_collectors_store())
+            .get(&session_id)
+            .cloned()
+            .ok_or_else(not_found)?;
+        let (parties_index, local_sigs): (Vec<u16>, Vec<SerializableLocalSig>) =
+            collector.local_sigs.into_iter().unzip();
+        let vss_sum = threshold_sig::verify_local_sigs(
+            local_sigs.clone(),
+            parties_index.clone(),
+            collector.vss_private_keys,
+            collector.vss_ephemeral_keys,
+        )?;
+        let signature = threshold_sig::generate_signature(
+            vss_sum,
+            local_sigs,
+            parties_index,
+            collector.R,
+            None,
+            None,
+            None,
+            None,
+        )?;
+        lock(sig_collectors_store()).remove(&session_id);
+        Ok(signature)
+    })
+}
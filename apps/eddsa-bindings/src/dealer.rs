@@ -0,0 +1,232 @@
+use curv::arithmetic::Converter;
+use curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use curv::elliptic::curves::{Ed25519, Point, Scalar};
+use curv::BigInt;
+use multi_party_eddsa::protocols::thresholdsig::{Keys, Parameters, SharedKeys};
+use napi::Status;
+use napi_derive::napi;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::error::TssError;
+use crate::rng::rng_source;
+use crate::store::lock;
+use crate::threshold_sig;
+use crate::{
+    check_sharing_params, point_to_serializable, scalar_to_serializable, vss_to_serializable,
+    SerializablePoint, SerializableSharedKeys, SerializableVerifiableSS,
+};
+
+// Trusted-dealer sharing of an existing key. Unlike keygen, where no party
+// ever holds the whole secret, the dealer starts from it: whoever runs this
+// can sign alone until the original secret and this process's memory are
+// destroyed, and every party must trust the dealer to have done so.
+//
+// Weighted sharing maps onto the same equal-weight scheme: a party of weight w
+// holds w consecutive shares of a (threshold_weight - 1)-of-sum(weights)
+// sharing and signs as w parties, one per share index. Any set of parties
+// whose weights add up to threshold_weight then holds threshold_weight shares,
+// the t + 1 the plain scheme needs, and aggregation is unchanged.
+
+/// Output of `deal_secret`: one share per party 1..=n, each also as an `export_shared_keys`
+/// backup the party loads with `import_and_verify_shared_keys`
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableDealResult {
+    pub threshold: u16,
+    #[serde(rename = "share_count")]
+    #[napi(js_name = "shareCount")]
+    pub share_count: u16,
+    /// The Ed25519 public key of the dealt secret, unchanged
+    pub y: SerializablePoint,
+    pub vss: SerializableVerifiableSS,
+    #[serde(rename = "shared_keys")]
+    #[napi(js_name = "sharedKeys")]
+    pub shared_keys: Vec<SerializableSharedKeys>,
+    pub backups: Vec<Vec<u8>>,
+}
+
+/// One party's shares from `deal_secret_weighted`: `share_indices[k]` is the party index
+/// `shared_keys[k]` and `backups[k]` sign under
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableWeightedShares {
+    pub weight: u16,
+    #[serde(rename = "share_indices")]
+    #[napi(js_name = "shareIndices")]
+    pub share_indices: Vec<u16>,
+    #[serde(rename = "shared_keys")]
+    #[napi(js_name = "sharedKeys")]
+    pub shared_keys: Vec<SerializableSharedKeys>,
+    pub backups: Vec<Vec<u8>>,
+}
+
+/// Output of `deal_secret_weighted`: `parties[j]` holds the shares of the party with
+/// weight `weights[j]`; `threshold` and `share_count` describe the underlying sharing
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableWeightedDealResult {
+    #[serde(rename = "threshold_weight")]
+    #[napi(js_name = "thresholdWeight")]
+    pub threshold_weight: u16,
+    pub threshold: u16,
+    #[serde(rename = "share_count")]
+    #[napi(js_name = "shareCount")]
+    pub share_count: u16,
+    /// The Ed25519 public key of the dealt secret, unchanged
+    pub y: SerializablePoint,
+    pub vss: SerializableVerifiableSS,
+    pub parties: Vec<SerializableWeightedShares>,
+}
+
+// Share `secret` among parties 1..=share_count, each with a fresh nonce prefix
+fn deal(
+    secret: Vec<u8>,
+    threshold: u16,
+    share_count: u16,
+) -> napi::Result<(Point<Ed25519>, VerifiableSS<Ed25519>, Vec<SharedKeys>)> {
+    threshold_sig::check_share_count(usize::from(share_count))?;
+    let parties: Vec<u16> = (1..=share_count).collect();
+    check_sharing_params(threshold, share_count, &parties)?;
+    let secret = Zeroizing::new(secret);
+    let secret: Zeroizing<[u8; 32]> = Zeroizing::new(
+        secret
+            .as_slice()
+            .try_into()
+            .map_err(|_| napi::Error::new(Status::InvalidArg, "Secret must be 32 bytes"))?,
+    );
+    let keys = Keys::phase1_create_from_private_key(0, *secret);
+    let params = Parameters {
+        threshold,
+        share_count,
+    };
+    let mut rng = lock(rng_source());
+    let (vss, secret_shares) = keys.phase2_distribute_rng(&params, &parties, &mut *rng);
+
+    let shared_keys = secret_shares
+        .into_iter()
+        .map(|x_i| {
+            let mut prefix = Zeroizing::new([0u8; 64]);
+            rng.fill_bytes(&mut *prefix);
+            SharedKeys {
+                y: keys.keypair.public_key.clone(),
+                x_i,
+                prefix: Scalar::from_bigint(&BigInt::from_bytes(&*prefix)),
+            }
+        })
+        .collect();
+    Ok((keys.keypair.public_key.clone(), vss, shared_keys))
+}
+
+fn shared_keys_to_serializable(shared_keys: &SharedKeys) -> SerializableSharedKeys {
+    SerializableSharedKeys {
+        y: point_to_serializable(&shared_keys.y),
+        x_i: scalar_to_serializable(&shared_keys.x_i),
+        prefix: scalar_to_serializable(&shared_keys.prefix),
+    }
+}
+
+/// TRUSTED DEALER: split an existing 32-byte Ed25519 secret into t-of-n shares for parties
+/// 1..=n, keeping its public key, so t + 1 of them sign as the original key did. The dealer
+/// sees the whole secret and can sign alone for as long as it keeps it; use keygen instead
+/// unless the key already exists. Every party gets a fresh nonce prefix, and the shares are
+/// secret: send each party only its own, over an authenticated encrypted channel.
+#[napi]
+pub fn deal_secret(
+    secret: Vec<u8>,
+    threshold: u16,
+    share_count: u16,
+) -> napi::Result<SerializableDealResult> {
+    crate::guard::guard("deal_secret", || {
+        let (y, vss, shared_keys) = deal(secret, threshold, share_count)?;
+        Ok(SerializableDealResult {
+            threshold,
+            share_count,
+            y: point_to_serializable(&y),
+            vss: vss_to_serializable(&vss),
+            backups: (1..=share_count)
+                .zip(&shared_keys)
+                .map(|(party, shared_keys)| threshold_sig::backup_bytes(party, shared_keys))
+                .collect(),
+            shared_keys: shared_keys
+                .iter()
+                .map(shared_keys_to_serializable)
+                .collect(),
+        })
+    })
+}
+
+/// TRUSTED DEALER, as `deal_secret`, with signing power split by weight: any set of parties
+/// whose `weights` add up to `threshold_weight` can sign. The party of weight w gets w shares
+/// of a (threshold_weight - 1)-of-sum(weights) sharing, at consecutive party indices, and
+/// signs by running one signer per share (each share imported as its own key) in the usual
+/// signing round; local signatures from all of them are aggregated as any others.
+#[napi]
+pub fn deal_secret_weighted(
+    secret: Vec<u8>,
+    weights: Vec<u16>,
+    threshold_weight: u16,
+) -> napi::Result<SerializableWeightedDealResult> {
+    crate::guard::guard("deal_secret_weighted", || {
+        if weights.is_empty() {
+            return Err(TssError::EmptyInput { name: "weights" }.into());
+        }
+        if weights.contains(&0) {
+            return Err(napi::Error::new(
+                Status::InvalidArg,
+                "Weights must be at least 1",
+            ));
+        }
+        let total: usize = weights.iter().map(|&weight| usize::from(weight)).sum();
+        threshold_sig::check_share_count(total)?;
+        let share_count = u16::try_from(total).map_err(|_| {
+            napi::Error::new(
+                Status::InvalidArg,
+                format!("Total weight {} exceeds {}", total, u16::MAX),
+            )
+        })?;
+        if threshold_weight == 0 || threshold_weight > share_count {
+            return Err(napi::Error::new(
+                Status::InvalidArg,
+                format!(
+                    "threshold_weight {} must be between 1 and the total weight {}",
+                    threshold_weight, share_count
+                ),
+            ));
+        }
+        let threshold = threshold_weight - 1;
+        let (y, vss, shared_keys) = deal(secret, threshold, share_count)?;
+
+        let mut next_index = 1u16;
+        let parties = weights
+            .iter()
+            .map(|&weight| {
+                let share_indices: Vec<u16> = (next_index..next_index + weight).collect();
+                next_index += weight;
+                let shares =
+                    &shared_keys[usize::from(share_indices[0] - 1)..][..usize::from(weight)];
+                SerializableWeightedShares {
+                    weight,
+                    backups: share_indices
+                        .iter()
+                        .zip(shares)
+                        .map(|(&index, shared_keys)| {
+                            threshold_sig::backup_bytes(index, shared_keys)
+                        })
+                        .collect(),
+                    share_indices,
+                    shared_keys: shares.iter().map(shared_keys_to_serializable).collect(),
+                }
+            })
+            .collect();
+        Ok(SerializableWeightedDealResult {
+            threshold_weight,
+            threshold,
+            share_count,
+            y: point_to_serializable(&y),
+            vss: vss_to_serializable(&vss),
+            parties,
+        })
+    })
+}
@@ -0,0 +1,26 @@
+use crate::{
+    point_to_serializable, scalar_to_serializable, serializable_to_point, serializable_to_scalar,
+    SerializablePoint, SerializableScalar,
+};
+
+// Total decoders for untrusted input, built as fuzz targets (see fuzz/). They
+// take buffers of any length and report failure as None. Unlike the other
+// exports they are not wrapped in `guard`, so a panic still reaches the fuzzer
+// instead of being turned into an error.
+
+/// Canonical encoding of the point in `bytes`, or None if it is not a valid
+/// 32-byte (or prefixed 33-byte) Ed25519 point.
+#[napi_derive::napi]
+pub fn try_decode_point(bytes: Vec<u8>) -> Option<SerializablePoint> {
+    serializable_to_point(&SerializablePoint { bytes })
+        .ok()
+        .map(|p| point_to_serializable(&p))
+}
+
+/// The scalar in `bytes`, or None if it is not a 32-byte little-endian scalar.
+#[napi_derive::napi]
+pub fn try_decode_scalar(bytes: Vec<u8>) -> Option<SerializableScalar> {
+    serializable_to_scalar(&SerializableScalar { bytes })
+        .ok()
+        .map(|s| scalar_to_serializable(&s))
+}
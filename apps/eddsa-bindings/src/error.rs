@@ -0,0 +1,239 @@
+use napi::Status;
+use std::fmt;
+
+use crate::threshold_sig::{IdKind, KeyPhase};
+
+/// Protocol errors surfaced across the napi boundary. The variant name is the
+/// error code and prefixes the napi error reason, e.g.
+/// `InsufficientParticipants: 1 parties provided, 2 required`.
+#[derive(Debug)]
+pub(crate) enum TssError {
+    InsufficientParticipants {
+        provided: usize,
+        required: usize,
+    },
+    RInconsistent {
+        what: &'static str,
+    },
+    KeyNotReady {
+        operation: &'static str,
+        key_id: String,
+        required: KeyPhase,
+        actual: KeyPhase,
+    },
+    InvalidPoint {
+        name: &'static str,
+    },
+    NonCanonicalScalar {
+        name: &'static str,
+    },
+    SignatureMismatch,
+    InvalidDecommitment {
+        party: usize,
+    },
+    InvalidShare {
+        party: usize,
+    },
+    MessageTooLarge {
+        size: usize,
+        limit: usize,
+    },
+    EmptyInput {
+        name: &'static str,
+    },
+    Panic {
+        operation: &'static str,
+    },
+    MalformedVss {
+        expected: usize,
+        actual: usize,
+    },
+    NotInSubgroup {
+        name: &'static str,
+    },
+    ShareCountTooLarge {
+        share_count: usize,
+        limit: usize,
+    },
+    DuplicatePartyIndex {
+        index: u16,
+    },
+    Aborted {
+        operation: &'static str,
+    },
+    InvalidPrefix {
+        reason: &'static str,
+    },
+    DuplicatePublicKey {
+        first: usize,
+        second: usize,
+    },
+    UnsupportedEncodingVersion {
+        version: u8,
+        supported: u8,
+    },
+    WrongIdType {
+        id: String,
+        expected: IdKind,
+        actual: IdKind,
+    },
+    SerializationFailed {
+        what: &'static str,
+        reason: String,
+    },
+}
+
+impl TssError {
+    pub(crate) fn code(&self) -> &'static str {
+        match self {
+            TssError::InsufficientParticipants { .. } => "InsufficientParticipants",
+            TssError::RInconsistent { .. } => "RInconsistent",
+            TssError::KeyNotReady { .. } => "KeyNotReady",
+            TssError::InvalidPoint { .. } => "InvalidPoint",
+            TssError::NonCanonicalScalar { .. } => "NonCanonicalScalar",
+            TssError::SignatureMismatch => "SignatureMismatch",
+            TssError::InvalidDecommitment { .. } => "InvalidDecommitment",
+            TssError::InvalidShare { .. } => "InvalidShare",
+            TssError::MessageTooLarge { .. } => "MessageTooLarge",
+            TssError::EmptyInput { .. } => "EmptyInput",
+            TssError::Panic { .. } => "Panic",
+            TssError::MalformedVss { .. } => "MalformedVss",
+            TssError::NotInSubgroup { .. } => "NotInSubgroup",
+            TssError::ShareCountTooLarge { .. } => "ShareCountTooLarge",
+            TssError::DuplicatePartyIndex { .. } => "DuplicatePartyIndex",
+            TssError::Aborted { .. } => "Aborted",
+            TssError::InvalidPrefix { .. } => "InvalidPrefix",
+            TssError::DuplicatePublicKey { .. } => "DuplicatePublicKey",
+            TssError::UnsupportedEncodingVersion { .. } => "UnsupportedEncodingVersion",
+            TssError::WrongIdType { .. } => "WrongIdType",
+            TssError::SerializationFailed { .. } => "SerializationFailed",
+        }
+    }
+
+    fn status(&self) -> Status {
+        match self {
+            TssError::InsufficientParticipants { .. } => Status::InvalidArg,
+            TssError::RInconsistent { .. } => Status::InvalidArg,
+            TssError::KeyNotReady { .. } => Status::InvalidArg,
+            TssError::InvalidPoint { .. } => Status::InvalidArg,
+            TssError::NonCanonicalScalar { .. } => Status::InvalidArg,
+            TssError::SignatureMismatch => Status::GenericFailure,
+            TssError::InvalidDecommitment { .. } => Status::InvalidArg,
+            TssError::InvalidShare { .. } => Status::InvalidArg,
+            TssError::MessageTooLarge { .. } => Status::InvalidArg,
+            TssError::EmptyInput { .. } => Status::InvalidArg,
+            TssError::Panic { .. } => Status::GenericFailure,
+            TssError::MalformedVss { .. } => Status::InvalidArg,
+            TssError::NotInSubgroup { .. } => Status::InvalidArg,
+            TssError::ShareCountTooLarge { .. } => Status::InvalidArg,
+            TssError::DuplicatePartyIndex { .. } => Status::InvalidArg,
+            TssError::Aborted { .. } => Status::Cancelled,
+            TssError::InvalidPrefix { .. } => Status::InvalidArg,
+            TssError::DuplicatePublicKey { .. } => Status::InvalidArg,
+            TssError::UnsupportedEncodingVersion { .. } => Status::InvalidArg,
+            TssError::WrongIdType { .. } => Status::InvalidArg,
+            TssError::SerializationFailed { .. } => Status::GenericFailure,
+        }
+    }
+}
+
+impl fmt::Display for TssError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: ", self.code())?;
+        match self {
+            TssError::InsufficientParticipants { provided, required } => {
+                write!(f, "{} parties provided, {} required", provided, required)
+            }
+            TssError::RInconsistent { what } => {
+                write!(f, "{} does not match the provided R", what)
+            }
+            TssError::KeyNotReady {
+                operation,
+                key_id,
+                required,
+                actual,
+            } => write!(
+                f,
+                "{} requires key {} to have reached phase {:?}, but it is in phase {:?}",
+                operation, key_id, required, actual
+            ),
+            TssError::InvalidPoint { name } => {
+                write!(f, "{} is not a valid Ed25519 point encoding", name)
+            }
+            TssError::NonCanonicalScalar { name } => {
+                write!(f, "{} is not a canonical 32-byte scalar", name)
+            }
+            TssError::SignatureMismatch => write!(f, "s*G does not equal R + k*A"),
+            TssError::InvalidDecommitment { party } => {
+                write!(
+                    f,
+                    "decommitment of party {} does not open its commitment",
+                    party
+                )
+            }
+            TssError::InvalidShare { party } => {
+                write!(
+                    f,
+                    "secret share from party {} does not match its VSS commitments",
+                    party
+                )
+            }
+            TssError::MessageTooLarge { size, limit } => {
+                write!(
+                    f,
+                    "message of {} bytes exceeds the {} byte limit",
+                    size, limit
+                )
+            }
+            TssError::EmptyInput { name } => write!(f, "{} must not be empty", name),
+            TssError::Panic { operation } => write!(f, "internal panic in {}", operation),
+            TssError::MalformedVss { expected, actual } => write!(
+                f,
+                "VSS has {} commitments, its threshold requires {}",
+                actual, expected
+            ),
+            TssError::NotInSubgroup { name } => {
+                write!(f, "{} is not in the prime-order subgroup", name)
+            }
+            TssError::ShareCountTooLarge { share_count, limit } => {
+                write!(f, "{} parties exceed the limit of {}", share_count, limit)
+            }
+            TssError::DuplicatePartyIndex { index } => {
+                write!(f, "party index {} appears more than once", index)
+            }
+            TssError::Aborted { operation } => write!(f, "{} was aborted", operation),
+            TssError::InvalidPrefix { reason } => {
+                write!(f, "shared keys prefix is corrupted: {}", reason)
+            }
+            TssError::DuplicatePublicKey { first, second } => {
+                write!(f, "public keys {} and {} are the same point", first, second)
+            }
+            TssError::UnsupportedEncodingVersion { version, supported } => write!(
+                f,
+                "encoding version {} is not supported, this build reads versions up to {}",
+                version, supported
+            ),
+            TssError::WrongIdType {
+                id,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{} names {}, expected the id of {}",
+                id,
+                actual.described(),
+                expected.described()
+            ),
+            TssError::SerializationFailed { what, reason } => {
+                write!(f, "{} could not be serialized: {}", what, reason)
+            }
+        }
+    }
+}
+
+impl From<TssError> for napi::Error {
+    fn from(e: TssError) -> Self {
+        crate::metrics::record_error(e.code());
+        napi::Error::new(e.status(), e.to_string())
+    }
+}
@@ -0,0 +1,208 @@
+use napi::Status;
+use napi_derive::napi;
+
+use crate::{
+    check_encoding_version, point_to_serializable, scalar_to_serializable, serializable_to_point,
+    serializable_to_scalar, serializable_to_vss, vss_to_serializable, SerializableBigInt,
+    SerializableKeygenMessage1, SerializableKeygenMessage2, SerializablePoint, SerializableScalar,
+    SerializableVerifiableSS, ENCODING_VERSION,
+};
+
+// One binary frame per keygen message, for transports that carry bytes rather
+// than JSON:
+//
+//   magic "TSKG" | version (u8) | round (u8) | body length (u32 LE) | body
+//
+// Integers in the body are little-endian, points and scalars 32 bytes, and big
+// integers are prefixed with their u16 length. The version is the crate's
+// `encoding_version`. Decoding checks every field, so a frame that decodes
+// holds valid points and scalars and a well-formed VSS.
+
+const MAGIC: &[u8; 4] = b"TSKG";
+const HEADER_LEN: usize = 4 + 1 + 1 + 4;
+const ROUND_1: u8 = 1;
+const ROUND_2: u8 = 2;
+
+fn invalid(reason: impl Into<String>) -> napi::Error {
+    napi::Error::new(Status::InvalidArg, reason.into())
+}
+
+fn frame(round: u8, body: Vec<u8>) -> napi::Result<Vec<u8>> {
+    let len = u32::try_from(body.len()).map_err(|_| invalid("Frame body is too large"))?;
+    let mut frame = Vec::with_capacity(HEADER_LEN + body.len());
+    frame.extend_from_slice(MAGIC);
+    frame.push(ENCODING_VERSION);
+    frame.push(round);
+    frame.extend_from_slice(&len.to_le_bytes());
+    frame.extend(body);
+    Ok(frame)
+}
+
+// The body of a frame for `round`, after checking the header and the length
+fn unframe(frame: &[u8], round: u8) -> napi::Result<Body<'_>> {
+    if frame.len() < HEADER_LEN {
+        return Err(invalid("Frame is truncated"));
+    }
+    let (header, body) = frame.split_at(HEADER_LEN);
+    if &header[..4] != MAGIC {
+        return Err(invalid("Not a keygen frame"));
+    }
+    check_encoding_version(header[4])?;
+    if header[5] != round {
+        return Err(invalid(format!(
+            "Expected a round {} frame, got round {}",
+            round, header[5]
+        )));
+    }
+    let len = u32::from_le_bytes([header[6], header[7], header[8], header[9]]) as usize;
+    if body.len() != len {
+        return Err(invalid(format!(
+            "Frame body is {} bytes, its header says {}",
+            body.len(),
+            len
+        )));
+    }
+    Ok(Body(body))
+}
+
+struct Body<'a>(&'a [u8]);
+
+impl<'a> Body<'a> {
+    fn take(&mut self, n: usize) -> napi::Result<&'a [u8]> {
+        if self.0.len() < n {
+            return Err(invalid("Frame body is truncated"));
+        }
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn u16(&mut self) -> napi::Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn point(&mut self) -> napi::Result<SerializablePoint> {
+        let point = serializable_to_point(&SerializablePoint {
+            bytes: self.take(32)?.to_vec(),
+        })?;
+        Ok(point_to_serializable(&point))
+    }
+
+    fn scalar(&mut self) -> napi::Result<SerializableScalar> {
+        let scalar = serializable_to_scalar(&SerializableScalar {
+            bytes: self.take(32)?.to_vec(),
+        })?;
+        Ok(scalar_to_serializable(&scalar))
+    }
+
+    fn bigint(&mut self) -> napi::Result<SerializableBigInt> {
+        let len = usize::from(self.u16()?);
+        Ok(SerializableBigInt {
+            bytes: self.take(len)?.to_vec(),
+        })
+    }
+
+    fn finish(self) -> napi::Result<()> {
+        if !self.0.is_empty() {
+            return Err(invalid("Frame body has trailing bytes"));
+        }
+        Ok(())
+    }
+}
+
+fn put_bigint(body: &mut Vec<u8>, name: &str, value: &SerializableBigInt) -> napi::Result<()> {
+    let len = u16::try_from(value.bytes.len())
+        .map_err(|_| invalid(format!("{} is too long for a frame", name)))?;
+    body.extend_from_slice(&len.to_le_bytes());
+    body.extend_from_slice(&value.bytes);
+    Ok(())
+}
+
+// Canonical 32-byte encodings, so a frame never carries a prefixed or invalid point
+fn put_point(body: &mut Vec<u8>, point: &SerializablePoint) -> napi::Result<()> {
+    body.extend_from_slice(&point_to_serializable(&serializable_to_point(point)?).bytes);
+    Ok(())
+}
+
+fn put_scalar(body: &mut Vec<u8>, scalar: &SerializableScalar) -> napi::Result<()> {
+    body.extend_from_slice(&scalar_to_serializable(&serializable_to_scalar(scalar)?).bytes);
+    Ok(())
+}
+
+/// Pack a `phase1_broadcast_message` into one keygen frame
+#[napi]
+pub fn encode_keygen_round1(message: SerializableKeygenMessage1) -> napi::Result<Vec<u8>> {
+    crate::guard::guard("encode_keygen_round1", || {
+        let mut body = message.party_index.to_le_bytes().to_vec();
+        put_point(&mut body, &message.public_key)?;
+        put_bigint(&mut body, "commitment", &message.commitment)?;
+        put_bigint(&mut body, "blind_factor", &message.blind_factor)?;
+        frame(ROUND_1, body)
+    })
+}
+
+/// Unpack a frame from `encode_keygen_round1`, rejecting a truncated frame, trailing bytes,
+/// another round's frame or an unsupported version
+#[napi]
+pub fn decode_keygen_round1(frame: Vec<u8>) -> napi::Result<SerializableKeygenMessage1> {
+    crate::guard::guard("decode_keygen_round1", || {
+        let mut body = unframe(&frame, ROUND_1)?;
+        let message = SerializableKeygenMessage1 {
+            party_index: body.u16()?,
+            public_key: body.point()?,
+            commitment: body.bigint()?,
+            blind_factor: body.bigint()?,
+        };
+        body.finish()?;
+        Ok(message)
+    })
+}
+
+/// Pack a keygen phase 2 message into one frame. It holds a secret share, so send it to its
+/// recipient only, over an encrypted channel.
+#[napi]
+pub fn encode_keygen_round2(message: SerializableKeygenMessage2) -> napi::Result<Vec<u8>> {
+    crate::guard::guard("encode_keygen_round2", || {
+        let vss = serializable_to_vss(&message.vss)?;
+        let mut body = message.party_index.to_le_bytes().to_vec();
+        body.extend_from_slice(&message.recipient_index.to_le_bytes());
+        body.extend_from_slice(&vss.parameters.threshold.to_le_bytes());
+        body.extend_from_slice(&vss.parameters.share_count.to_le_bytes());
+        for commitment in &vss.commitments {
+            body.extend_from_slice(&commitment.to_bytes(true));
+        }
+        put_scalar(&mut body, &message.share)?;
+        frame(ROUND_2, body)
+    })
+}
+
+/// Unpack a frame from `encode_keygen_round2`, rejecting a truncated frame, trailing bytes,
+/// another round's frame, an unsupported version or an invalid VSS
+#[napi]
+pub fn decode_keygen_round2(frame: Vec<u8>) -> napi::Result<SerializableKeygenMessage2> {
+    crate::guard::guard("decode_keygen_round2", || {
+        let mut body = unframe(&frame, ROUND_2)?;
+        let party_index = body.u16()?;
+        let recipient_index = body.u16()?;
+        let threshold = body.u16()?;
+        let share_count = body.u16()?;
+        // a degree-t polynomial has t + 1 commitments
+        let commitments = (0..=usize::from(threshold))
+            .map(|_| body.point())
+            .collect::<napi::Result<Vec<_>>>()?;
+        let vss = SerializableVerifiableSS {
+            threshold,
+            share_count,
+            commitments,
+        };
+        let message = SerializableKeygenMessage2 {
+            party_index,
+            recipient_index,
+            vss: vss_to_serializable(&serializable_to_vss(&vss)?),
+            share: body.scalar()?,
+        };
+        body.finish()?;
+        Ok(message)
+    })
+}
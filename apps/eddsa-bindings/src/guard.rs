@@ -0,0 +1,64 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::error::TssError;
+
+#[cfg(test)]
+thread_local! {
+    // operation that panics on its next guarded call on this thread, for the boundary tests
+    pub(crate) static PANIC_HOOK: std::cell::Cell<Option<&'static str>> = const { std::cell::Cell::new(None) };
+}
+
+#[cfg(feature = "persistent-store")]
+thread_local! {
+    // guarded calls in progress on this thread; exported functions call each other
+    static DEPTH: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+// Counts a guarded call for as long as it runs, unwinding included
+#[cfg(feature = "persistent-store")]
+struct Depth;
+
+#[cfg(feature = "persistent-store")]
+impl Depth {
+    // true for the outermost call
+    fn enter() -> (Depth, bool) {
+        let outermost = DEPTH.with(|depth| depth.replace(depth.get() + 1)) == 0;
+        (Depth, outermost)
+    }
+}
+
+#[cfg(feature = "persistent-store")]
+impl Drop for Depth {
+    fn drop(&mut self) {
+        DEPTH.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+/// Run the body of an exported function, turning a panic into a `Panic` error
+/// instead of unwinding into Node. The panic payload is dropped rather than
+/// forwarded, since it may have been formatted from secret material.
+///
+/// With a persistent store, the outermost call writes the store changes of a
+/// successful body through before returning.
+pub(crate) fn guard<T>(
+    operation: &'static str,
+    body: impl FnOnce() -> napi::Result<T>,
+) -> napi::Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        #[cfg(feature = "persistent-store")]
+        let (_depth, outermost) = Depth::enter();
+        #[cfg(test)]
+        if PANIC_HOOK.with(|hook| hook.get()) == Some(operation) {
+            PANIC_HOOK.with(|hook| hook.set(None));
+            panic!("panic hook fired in {}", operation);
+        }
+        let result = body();
+        #[cfg(feature = "persistent-store")]
+        let result = match result {
+            Ok(value) if outermost => crate::persist::flush().map(|()| value),
+            result => result,
+        };
+        result
+    }))
+    .unwrap_or_else(|_| Err(TssError::Panic { operation }.into()))
+}
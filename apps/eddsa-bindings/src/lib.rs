@@ -6,9 +6,41 @@ use multi_party_eddsa::protocols::thresholdsig::{
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 use curv::BigInt;
 use curv::arithmetic::Converter;
+use curv::cryptographic_primitives::secret_sharing::feldman_vss::{ShamirSecretSharing, VerifiableSS};
+use curv::elliptic::curves::{Ed25519, Point};
+
+mod abort;
+mod arith;
+mod bigint;
+mod blake2b;
+mod collector;
+mod dealer;
+mod decode;
+mod error;
+mod frame;
+mod guard;
+mod metrics;
+mod parallel;
+#[cfg(feature = "persistent-store")]
+mod persist;
+mod point_cache;
+mod rng;
+mod rpc;
+mod self_test;
+mod signature_format;
+mod signer;
+mod simulate;
+mod store;
+mod test_vectors;
+#[cfg(feature = "testing")]
+mod testing;
+mod verify_key;
+
+pub use decode::{try_decode_point, try_decode_scalar};
+
+use error::TssError;
 
 // Serializable wrapper types for NAPI
 #[napi(object)]
@@ -23,6 +55,9 @@ pub struct SerializableScalar {
     pub bytes: Vec<u8>,
 }
 
+/// Big-endian magnitude, as curv's `BigInt::to_bytes` emits it. Commitments are checked
+/// against the blind factor decoded in this order, so other orders must go through
+/// `bigint_from_bytes_le` and friends first.
 #[napi(object)]
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SerializableBigInt {
@@ -34,6 +69,8 @@ pub struct SerializableBigInt {
 pub struct SerializableSharedKeys {
     pub y: SerializablePoint,
     pub x_i: SerializableScalar,
+    /// Second half of the party's expanded Ed25519 secret, keying the deterministic part of
+    /// its nonces. Independent of `x_i`; a zero or duplicated value is rejected as corruption.
     pub prefix: SerializableScalar,
 }
 
@@ -81,6 +118,178 @@ pub struct SerializableLocalSig {
     pub k: SerializableScalar,
 }
 
+/// A local signature paired with the 0-based index of the signer that produced it
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableIndexedLocalSig {
+    #[serde(rename = "party_index")]
+    #[napi(js_name = "partyIndex")]
+    pub party_index: u16,
+    #[serde(rename = "local_sig")]
+    #[napi(js_name = "localSig")]
+    pub local_sig: SerializableLocalSig,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableKeyMetadata {
+    #[serde(rename = "public_key")]
+    #[napi(js_name = "publicKey")]
+    pub public_key: SerializablePoint,
+    #[serde(rename = "party_index")]
+    #[napi(js_name = "partyIndex")]
+    pub party_index: u16,
+}
+
+/// Everything a party broadcasts in keygen phase 1, routable as one network message. The
+/// blind factor is empty under the `Deterministic` commitment scheme.
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableKeygenMessage1 {
+    #[serde(rename = "party_index")]
+    #[napi(js_name = "partyIndex")]
+    pub party_index: u16,
+    #[serde(rename = "public_key")]
+    #[napi(js_name = "publicKey")]
+    pub public_key: SerializablePoint,
+    pub commitment: SerializableBigInt,
+    #[serde(rename = "blind_factor")]
+    #[napi(js_name = "blindFactor")]
+    pub blind_factor: SerializableBigInt,
+}
+
+/// What a party sends one recipient in keygen phase 2: its VSS commitments, which every
+/// party receives, and the recipient's secret share, which no one else may see.
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableKeygenMessage2 {
+    #[serde(rename = "party_index")]
+    #[napi(js_name = "partyIndex")]
+    pub party_index: u16,
+    #[serde(rename = "recipient_index")]
+    #[napi(js_name = "recipientIndex")]
+    pub recipient_index: u16,
+    pub vss: SerializableVerifiableSS,
+    pub share: SerializableScalar,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableVerifyResult {
+    pub valid: bool,
+    pub reason: Option<String>,
+}
+
+/// Output of `verify_exclusive`
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableExclusiveResult {
+    /// Indices of the messages the signature verifies for, in input order
+    #[serde(rename = "valid_indices")]
+    #[napi(js_name = "validIndices")]
+    pub valid_indices: Vec<u32>,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableSecretShareEnvelope {
+    #[serde(rename = "recipient_index")]
+    #[napi(js_name = "recipientIndex")]
+    pub recipient_index: u16,
+    pub share: SerializableScalar,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializablePartyValidation {
+    #[serde(rename = "party_index")]
+    #[napi(js_name = "partyIndex")]
+    pub party_index: u16,
+    pub valid: bool,
+    pub reason: Option<String>,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableValidationReport {
+    pub valid: bool,
+    /// Problems with the inputs as a whole, e.g. mismatched lengths
+    pub errors: Vec<String>,
+    pub parties: Vec<SerializablePartyValidation>,
+}
+
+/// A share that does not lie on the polynomial its dealer committed to
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableShareMismatch {
+    pub dealer: u16,
+    pub recipient: u16,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableConsistencyReport {
+    pub consistent: bool,
+    /// Problems with the inputs as a whole, e.g. mismatched lengths or an undecodable VSS
+    pub errors: Vec<String>,
+    /// Dealers with at least one share off their commitments, in party order
+    #[serde(rename = "equivocating_dealers")]
+    #[napi(js_name = "equivocatingDealers")]
+    pub equivocating_dealers: Vec<u16>,
+    pub mismatches: Vec<SerializableShareMismatch>,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableMetrics {
+    pub keygens: u32,
+    pub signatures: u32,
+    pub verifications: u32,
+    #[serde(rename = "errors_by_code")]
+    #[napi(js_name = "errorsByCode")]
+    pub errors_by_code: std::collections::HashMap<String, u32>,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableSigningReceipt {
+    pub signature: SerializableSignature,
+    pub R: SerializablePoint,
+    #[serde(rename = "parties_index")]
+    #[napi(js_name = "partiesIndex")]
+    pub parties_index: Vec<u16>,
+    /// The challenge k = H(R || A || M) mod l the signature commits to, not the raw message
+    #[serde(rename = "message_hash")]
+    #[napi(js_name = "messageHash")]
+    pub message_hash: SerializableScalar,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableSignedBy {
+    pub signature: SerializableSignature,
+    /// The 0-based parties whose local signatures were aggregated, in ascending order
+    pub participants: Vec<u16>,
+    /// One bit per party of the full set, party i at bit i % 8 of byte i / 8; only when `share_count` is given
+    pub bitmap: Option<Vec<u8>>,
+}
+
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableImportResult {
+    #[serde(rename = "shared_key_id")]
+    #[napi(js_name = "sharedKeyId")]
+    pub shared_key_id: String,
+    #[serde(rename = "public_key")]
+    #[napi(js_name = "publicKey")]
+    pub public_key: SerializablePoint,
+    /// Whether the share signed and verified the test message; absent for a share of a
+    /// larger key, which cannot sign alone
+    #[serde(rename = "self_test_passed")]
+    #[napi(js_name = "selfTestPassed")]
+    pub self_test_passed: Option<bool>,
+}
+
 // Helper functions to convert between types
 fn point_to_serializable(p: &curv::elliptic::curves::Point<curv::elliptic::curves::Ed25519>) -> SerializablePoint {
     SerializablePoint {
@@ -94,17 +303,63 @@ fn scalar_to_serializable(s: &curv::elliptic::curves::Scalar<curv::elliptic::cur
     }
 }
 
+// Version of the byte layout of stored key material (backups and persisted records), bumped
+// whenever a point or scalar encoding changes so older blobs can be told apart and migrated
+const ENCODING_VERSION: u8 = 1;
+
+fn check_encoding_version(version: u8) -> napi::Result<()> {
+    if version == 0 || version > ENCODING_VERSION {
+        return Err(TssError::UnsupportedEncodingVersion { version, supported: ENCODING_VERSION }.into());
+    }
+    Ok(())
+}
+
 fn bigint_to_serializable(b: &BigInt) -> SerializableBigInt {
     SerializableBigInt {
         bytes: b.to_bytes(),
     }
 }
 
+// OpenPGP and some Ed25519 libraries mark the native point encoding with this leading byte
+const POINT_PREFIX: u8 = 0x40;
+
+// The bare 32-byte RFC 8032 encoding is canonical and is what every function returns;
+// a 33-byte encoding is also accepted on input when it carries POINT_PREFIX.
+fn point_encoding(sp: &SerializablePoint) -> napi::Result<[u8; 32]> {
+    let encoding = match sp.bytes.as_slice() {
+        [POINT_PREFIX, rest @ ..] if rest.len() == 32 => rest,
+        [_, rest @ ..] if rest.len() == 32 => {
+            return Err(napi::Error::new(Status::InvalidArg, format!("Unknown point prefix byte, expected 0x{:02x}", POINT_PREFIX)));
+        }
+        bytes => bytes,
+    };
+    encoding.try_into()
+        .map_err(|_| napi::Error::new(Status::InvalidArg, "Invalid point bytes length"))
+}
+
 fn serializable_to_point(sp: &SerializablePoint) -> napi::Result<curv::elliptic::curves::Point<curv::elliptic::curves::Ed25519>> {
-    let bytes: [u8; 32] = sp.bytes.as_slice().try_into()
-        .map_err(|_| napi::Error::new(Status::InvalidArg, "Invalid point bytes length"))?;
-    curv::elliptic::curves::Point::from_bytes(&bytes)
-        .map_err(|_| napi::Error::new(Status::InvalidArg, "Invalid point"))
+    point_cache::decode_point(&point_encoding(sp)?)
+        .ok_or_else(|| napi::Error::new(Status::InvalidArg, "Invalid point"))
+}
+
+// Whether [l]P is the identity, i.e. P has no small-order component. Decoded with
+// curve25519-dalek, since curv refuses such points outright.
+fn point_in_prime_order_subgroup(sp: &SerializablePoint, name: &'static str) -> napi::Result<bool> {
+    curve25519_dalek::edwards::CompressedEdwardsY(point_encoding(sp)?)
+        .decompress()
+        .map(|point| point.is_torsion_free())
+        .ok_or_else(|| TssError::InvalidPoint { name }.into())
+}
+
+// Strict mode: name an on-curve point outside the prime-order subgroup as such, instead
+// of letting it fail decoding as a generic invalid point
+fn check_subgroup(name: &'static str, points: &[SerializablePoint]) -> napi::Result<()> {
+    for point in points {
+        if !point_in_prime_order_subgroup(point, name)? {
+            return Err(TssError::NotInSubgroup { name }.into());
+        }
+    }
+    Ok(())
 }
 
 fn serializable_to_scalar(ss: &SerializableScalar) -> napi::Result<curv::elliptic::curves::Scalar<curv::elliptic::curves::Ed25519>> {
@@ -114,6 +369,185 @@ fn serializable_to_scalar(ss: &SerializableScalar) -> napi::Result<curv::ellipti
         .map_err(|_| napi::Error::new(Status::InvalidArg, "Invalid scalar"))
 }
 
+// The prefix is drawn independently of x_i, so it cannot be checked against the share; only
+// the corruptions that leave a recognisable trace (zeroed, or a copy of x_i) are caught
+fn check_prefix(prefix: &curv::elliptic::curves::Scalar<Ed25519>, x_i: &curv::elliptic::curves::Scalar<Ed25519>) -> napi::Result<()> {
+    if prefix.is_zero() {
+        return Err(TssError::InvalidPrefix { reason: "it is zero" }.into());
+    }
+    if prefix == x_i {
+        return Err(TssError::InvalidPrefix { reason: "it equals x_i" }.into());
+    }
+    Ok(())
+}
+
+fn serializable_to_shared_keys(shared_keys: &SerializableSharedKeys) -> napi::Result<SharedKeys> {
+    let x_i = serializable_to_scalar(&shared_keys.x_i)?;
+    let prefix = serializable_to_scalar(&shared_keys.prefix)?;
+    check_prefix(&prefix, &x_i)?;
+    Ok(SharedKeys {
+        y: serializable_to_point(&shared_keys.y)?,
+        x_i,
+        prefix,
+    })
+}
+
+// Affine (x, y) of an Ed25519 point, recovered from its compressed encoding as in
+// RFC 8032 5.1.3; None if the encoded y has no x on the curve
+fn affine_coordinates(p: &Point<Ed25519>) -> Option<(BigInt, BigInt)> {
+    use curv::arithmetic::{BasicOps, BitManipulation, Modulo, One, Zero};
+
+    let mut bytes = p.to_bytes(true).to_vec();
+    let x_is_odd = bytes[31] & 0x80 != 0;
+    bytes[31] &= 0x7f;
+    bytes.reverse();
+    let y = BigInt::from_bytes(&bytes);
+
+    let q = BigInt::from(2).pow(255) - BigInt::from(19);
+    let d = BigInt::mod_mul(&(&q - BigInt::from(121665)), &BigInt::mod_inv(&BigInt::from(121666), &q)?, &q);
+    let y2 = BigInt::mod_mul(&y, &y, &q);
+    let u = BigInt::mod_sub(&y2, &BigInt::one(), &q);
+    let v = BigInt::mod_add(&BigInt::mod_mul(&d, &y2, &q), &BigInt::one(), &q);
+    let x2 = BigInt::mod_mul(&u, &BigInt::mod_inv(&v, &q)?, &q);
+
+    let mut x = BigInt::mod_pow(&x2, &((&q + BigInt::from(3)) / BigInt::from(8)), &q);
+    if BigInt::mod_mul(&x, &x, &q) != x2 {
+        let sqrt_m1 = BigInt::mod_pow(&BigInt::from(2), &((&q - BigInt::one()) / BigInt::from(4)), &q);
+        x = BigInt::mod_mul(&x, &sqrt_m1, &q);
+    }
+    if BigInt::mod_mul(&x, &x, &q) != x2 {
+        return None;
+    }
+    if x.test_bit(0) != x_is_odd {
+        if x.is_zero() {
+            return None;
+        }
+        x = &q - &x;
+    }
+    Some((x, y))
+}
+
+// 32-byte little-endian field element, the byte order RFC 8032 uses for coordinates
+fn field_element_to_bytes(n: &BigInt) -> Vec<u8> {
+    let mut bytes = n.to_bytes();
+    bytes.reverse();
+    bytes.resize(32, 0);
+    bytes
+}
+
+// Aggregation needs threshold + 1 contributions to interpolate the signature
+// A repeated index would count one party's contribution twice
+fn check_distinct_parties(parties: &[u16]) -> napi::Result<()> {
+    let mut seen = std::collections::BTreeSet::new();
+    match parties.iter().find(|&&index| !seen.insert(index)) {
+        Some(&index) => Err(TssError::DuplicatePartyIndex { index }.into()),
+        None => Ok(()),
+    }
+}
+
+// Two keygen parties with the same public key share a secret, typically two nodes
+// provisioned from one seed. Compared after decoding, so two encodings of one point
+// still collide; positions are 0-based into `public_keys`.
+fn check_distinct_public_keys(public_keys: &[Point<Ed25519>]) -> napi::Result<()> {
+    let mut seen = std::collections::BTreeMap::new();
+    for (second, point) in public_keys.iter().enumerate() {
+        if let Some(&first) = seen.get(&point.to_bytes(true).to_vec()) {
+            return Err(TssError::DuplicatePublicKey { first, second }.into());
+        }
+        seen.insert(point.to_bytes(true).to_vec(), second);
+    }
+    Ok(())
+}
+
+fn check_participants(parties_index: &[u16], threshold: u16) -> napi::Result<()> {
+    check_distinct_parties(parties_index)?;
+    // a 0-based index i needs a sharing of at least i + 1 parties
+    if let Some(&max) = parties_index.iter().max() {
+        threshold_sig::check_share_count(usize::from(max) + 1)?;
+    }
+    let required = usize::from(threshold) + 1;
+    if parties_index.len() < required {
+        return Err(TssError::InsufficientParticipants { provided: parties_index.len(), required }.into());
+    }
+    Ok(())
+}
+
+// A t-of-n sharing needs n >= 1 and t < n, and one share goes to each of the n parties.
+// The smallest valid case is the degenerate 1-of-1 (t = 0, n = 1), whose only share is
+// the secret itself.
+fn check_sharing_params(threshold: u16, share_count: u16, parties: &[u16]) -> napi::Result<()> {
+    threshold_sig::check_share_count(usize::from(share_count))?;
+    if threshold >= share_count {
+        return Err(napi::Error::new(Status::InvalidArg, format!("threshold {} must be below share_count {}", threshold, share_count)));
+    }
+    if parties.len() != usize::from(share_count) {
+        return Err(napi::Error::new(Status::InvalidArg, format!("Expected {} parties, got {}", share_count, parties.len())));
+    }
+    if parties.contains(&0) {
+        return Err(napi::Error::new(Status::InvalidArg, "Party indices start at 1"));
+    }
+    check_distinct_parties(parties)
+}
+
+// Checked before anything indexes the first element of an input vector
+fn check_non_empty(name: &'static str, len: usize) -> napi::Result<()> {
+    if len == 0 {
+        return Err(TssError::EmptyInput { name }.into());
+    }
+    Ok(())
+}
+
+// Catches a wrong R before it turns into a signature that silently fails to verify
+fn check_R_consistent(what: &'static str, actual: &Point<Ed25519>, R: &Point<Ed25519>) -> napi::Result<()> {
+    if actual != R {
+        return Err(TssError::RInconsistent { what }.into());
+    }
+    Ok(())
+}
+
+// JSON results are built through here rather than json!, which panics on a value that
+// fails to serialize
+fn to_json<T: Serialize>(what: &'static str, value: &T) -> napi::Result<serde_json::Value> {
+    serde_json::to_value(value).map_err(|e| TssError::SerializationFailed { what, reason: e.to_string() }.into())
+}
+
+// Scalars travel little-endian; canonical encodings are strictly below the group order
+fn scalar_bytes_are_canonical(bytes: &[u8]) -> bool {
+    let mut be = bytes.to_vec();
+    be.reverse();
+    &BigInt::from_bytes(&be) < curv::elliptic::curves::Scalar::<curv::elliptic::curves::Ed25519>::group_order()
+}
+
+fn vss_to_serializable(vss: &VerifiableSS<Ed25519>) -> SerializableVerifiableSS {
+    SerializableVerifiableSS {
+        threshold: vss.parameters.threshold,
+        share_count: vss.parameters.share_count,
+        commitments: vss.commitments.iter().map(point_to_serializable).collect(),
+    }
+}
+
+// A degree-t polynomial has t + 1 coefficients, each committed to once
+fn expected_commitments(threshold: u16) -> usize {
+    usize::from(threshold) + 1
+}
+
+fn serializable_to_vss(vss: &SerializableVerifiableSS) -> napi::Result<VerifiableSS<Ed25519>> {
+    let expected = expected_commitments(vss.threshold);
+    if vss.commitments.len() != expected {
+        return Err(TssError::MalformedVss { expected, actual: vss.commitments.len() }.into());
+    }
+    let commitments: Vec<Point<Ed25519>> = vss.commitments.iter()
+        .map(serializable_to_point)
+        .collect::<napi::Result<Vec<_>>>()?;
+    Ok(VerifiableSS {
+        parameters: ShamirSecretSharing {
+            threshold: vss.threshold,
+            share_count: vss.share_count,
+        },
+        commitments,
+    })
+}
+
 fn serializable_to_bigint(sb: &SerializableBigInt) -> BigInt {
     BigInt::from_bytes(&sb.bytes)
 }
@@ -122,511 +556,2895 @@ fn serializable_to_bigint(sb: &SerializableBigInt) -> BigInt {
 #[napi]
 pub mod threshold_sig {
     use super::*;
-    use curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
-    use curv::elliptic::curves::{Ed25519, Point, Scalar};
+    use curv::elliptic::curves::Scalar;
     use curv::BigInt;
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
     use std::sync::{Mutex, OnceLock};
+    use crate::parallel::with_verification_pool;
+    use crate::rng::rng_source;
+    use crate::store::{lock, Store};
+    use rand::RngCore;
+    use multi_party_eddsa::protocols::Signature;
+    use sha2::{Digest, Sha512};
+    use zeroize::{Zeroize, Zeroizing};
+
+    /// Keygen progress of a stored key; each phase function requires the previous one
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+    pub(crate) enum KeyPhase {
+        Created,
+        Broadcast,
+        Distributed,
+        KeypairConstructed,
+    }
 
-    // Store Keys instances (in a real implementation, you'd want better state management)
-    fn keys_store() -> &'static Mutex<HashMap<String, Keys>> {
-        static STORE: OnceLock<Mutex<HashMap<String, Keys>>> = OnceLock::new();
-        STORE.get_or_init(|| Mutex::new(HashMap::new()))
+    /// How `ephemeral_key_create` derives a key's nonces. `Deterministic` (the default)
+    /// hashes the key prefix, domain, signer set and message together with fresh randomness;
+    /// `Random` samples the nonce from randomness alone, for a party that suspects its prefix
+    /// or domain leaked. All signers of a session must use the same mode.
+    #[napi(string_enum)]
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    // napi derives Clone and Copy for string enums, but not in the `noop` build
+    #[cfg_attr(feature = "noop", derive(Clone, Copy))]
+    pub enum EphemeralMode {
+        #[default]
+        Deterministic,
+        Random,
     }
-    
-    fn ephemeral_keys_store() -> &'static Mutex<HashMap<String, EphemeralKey>> {
-        static STORE: OnceLock<Mutex<HashMap<String, EphemeralKey>>> = OnceLock::new();
-        STORE.get_or_init(|| Mutex::new(HashMap::new()))
+
+    // The Ed25519 seed a key was created from; the expanded keypair alone cannot give it back
+    #[derive(Serialize, Deserialize)]
+    pub(crate) struct Seed([u8; 32]);
+
+    impl Drop for Seed {
+        fn drop(&mut self) {
+            self.0.zeroize();
+        }
     }
 
-    #[napi]
-    /// Create keys for a party (Phase 1)
-    pub fn phase1_create(party_index: u16) -> Result<String> {
-        let keys = Keys::phase1_create(party_index);
-        let key_id = format!("keys_{}", party_index);
-        keys_store().lock().unwrap().insert(key_id.clone(), keys);
-        Ok(key_id)
+    #[derive(Serialize, Deserialize)]
+    pub(crate) struct StoredKey {
+        pub(crate) keys: Keys,
+        pub(crate) phase: KeyPhase,
+        // set once the keypair is constructed
+        pub(crate) shared_keys: Option<SharedKeys>,
+        #[serde(default)]
+        pub(crate) ephemeral_mode: EphemeralMode,
+        // only for 1-of-1 keys imported with `import_single_party_key`
+        #[serde(default)]
+        pub(crate) seed: Option<Seed>,
     }
 
-    #[napi]
-    /// Create keys from a private key (Phase 1)
-    pub fn phase1_create_from_private_key(party_index: u16, secret: Vec<u8>) -> Result<String> {
-        let secret_array: [u8; 32] = secret.as_slice().try_into()
-            .map_err(|_| napi::Error::new(Status::InvalidArg, "Secret must be 32 bytes"))?;
-        let keys = Keys::phase1_create_from_private_key(party_index, secret_array);
-        let key_id = format!("keys_{}", party_index);
-        keys_store().lock().unwrap().insert(key_id.clone(), keys);
-        Ok(key_id)
+    impl StoredKey {
+        fn new(keys: Keys) -> Self {
+            Self::constructed(keys, KeyPhase::Created, None)
+        }
+
+        fn constructed(keys: Keys, phase: KeyPhase, shared_keys: Option<SharedKeys>) -> Self {
+            StoredKey { keys, phase, shared_keys, ephemeral_mode: EphemeralMode::default(), seed: None }
+        }
+
+        fn with_seed(mut self, secret: [u8; 32]) -> Self {
+            self.seed = Some(Seed(secret));
+            self
+        }
+
+        pub(crate) fn require_phase(&self, key_id: &str, required: KeyPhase, operation: &'static str) -> Result<()> {
+            if self.phase < required {
+                return Err(TssError::KeyNotReady {
+                    operation,
+                    key_id: key_id.to_string(),
+                    required,
+                    actual: self.phase,
+                }.into());
+            }
+            Ok(())
+        }
+
+        fn advance(&mut self, phase: KeyPhase) {
+            self.phase = self.phase.max(phase);
+        }
     }
 
-    #[napi]
-    /// Get public key for a keys instance
-    pub fn get_public_key(key_id: String) -> Result<SerializablePoint> {
-        let keys = keys_store().lock().unwrap();
-        let key = keys.get(&key_id)
-            .ok_or_else(|| napi::Error::new(Status::InvalidArg, "Key not found"))?;
-        Ok(point_to_serializable(&key.keypair.public_key))
+    // Store Keys instances (in a real implementation, you'd want better state management)
+    pub(crate) fn keys_store() -> &'static Mutex<Store<StoredKey>> {
+        static STORE: OnceLock<Mutex<Store<StoredKey>>> = OnceLock::new();
+        STORE.get_or_init(|| Mutex::new(Store::new()))
+    }
+    
+    pub(crate) fn ephemeral_keys_store() -> &'static Mutex<Store<EphemeralKey>> {
+        static STORE: OnceLock<Mutex<Store<EphemeralKey>>> = OnceLock::new();
+        STORE.get_or_init(|| Mutex::new(Store::new()))
     }
 
-    #[napi]
-    /// Phase 1 broadcast - returns commitment and blind factor
-    pub fn phase1_broadcast(key_id: String) -> Result<serde_json::Value> {
-        let mut keys_store = keys_store().lock().unwrap();
-        let key = keys_store.get_mut(&key_id)
-            .ok_or_else(|| napi::Error::new(Status::InvalidArg, "Key not found"))?;
-        
-        let (bcm1, blind_factor) = key.phase1_broadcast();
-        
-        let result = serde_json::json!({
-            "commitment": bigint_to_serializable(&bcm1.com),
-            "blind_factor": bigint_to_serializable(&blind_factor)
-        });
-        Ok(result)
+    // Streaming challenge hasher: absorbs the encodings R || A up front, then message chunks
+    pub(crate) struct SigHasher {
+        R: [u8; 32],
+        y: [u8; 32],
+        // verify under [8]sB = [8]R + [8]kA, with R and A decoded by curve25519-dalek
+        cofactored: bool,
+        hasher: ChallengeHasher,
     }
 
-    #[napi]
-    /// Phase 1 verify commitments and Phase 2 distribute shares
-    pub fn phase1_verify_com_phase2_distribute(
-        key_id: String,
-        threshold: u16,
-        share_count: u16,
-        blind_factors: Vec<SerializableBigInt>,
-        public_keys: Vec<SerializablePoint>,
-        commitments: Vec<SerializableBigInt>,
-        parties: Vec<u16>,
-    ) -> Result<serde_json::Value> {
-        let mut keys_store = keys_store().lock().unwrap();
-        let key = keys_store.get_mut(&key_id)
-            .ok_or_else(|| napi::Error::new(Status::InvalidArg, "Key not found"))?;
+    pub(crate) fn sig_hashers_store() -> &'static Mutex<Store<SigHasher>> {
+        static STORE: OnceLock<Mutex<Store<SigHasher>>> = OnceLock::new();
+        STORE.get_or_init(|| Mutex::new(Store::new()))
+    }
 
-        let params = Parameters { threshold, share_count };
-        
-        let blind_vec: Vec<BigInt> = blind_factors.iter().map(serializable_to_bigint).collect();
-        let y_vec: Vec<Point<Ed25519>> = public_keys.iter()
-            .map(|pk| serializable_to_point(pk))
-            .collect::<Result<Vec<_>>>()?;
-        
-        let bc1_vec: Vec<thresholdsig::KeyGenBroadcastMessage1> = commitments.iter()
-            .map(|com| thresholdsig::KeyGenBroadcastMessage1 { com: serializable_to_bigint(com) })
-            .collect();
-
-        let (vss, secret_shares) = key.phase1_verify_com_phase2_distribute(
-            &params,
-            &blind_vec,
-            &y_vec,
-            &bc1_vec,
-            &parties,
-        ).map_err(|e| napi::Error::new(Status::GenericFailure, format!("{:?}", e)))?;
-
-        // Serialize VSS
-        let vss_serializable = SerializableVerifiableSS {
-            threshold: vss.parameters.threshold,
-            share_count: vss.parameters.share_count,
-            commitments: vss.commitments.iter().map(point_to_serializable).collect(),
-        };
+    // Phase 1 broadcasts buffered by a coordinator, keyed by sending party so
+    // verification always sees them in party order, along with the parties' keygen
+    // VSS schemes once they are known, for the audit transcript
+    pub(crate) struct Phase1Session {
+        broadcasts: BTreeMap<u16, Phase1Broadcast>,
+        vss_schemes: BTreeMap<u16, SerializableVerifiableSS>,
+    }
 
-        // Access shares using indexing (SecretShares implements Index)
-        let secret_shares_serializable: Vec<SerializableScalar> = (0..share_count as usize)
-            .map(|i| scalar_to_serializable(&secret_shares[i]))
-            .collect();
+    struct Phase1Broadcast {
+        commitment: SerializableBigInt,
+        blind_factor: SerializableBigInt,
+        public_key: SerializablePoint,
+    }
 
-        let result = serde_json::json!({
-            "vss": vss_serializable,
-            "secret_shares": secret_shares_serializable
-        });
-        Ok(result)
+    pub(crate) fn phase1_sessions_store() -> &'static Mutex<Store<Phase1Session>> {
+        static STORE: OnceLock<Mutex<Store<Phase1Session>>> = OnceLock::new();
+        STORE.get_or_init(|| Mutex::new(Store::new()))
     }
 
-    #[napi]
-    /// Phase 2 verify VSS and construct keypair
-    pub fn phase2_verify_vss_construct_keypair(
+    // Secret shares a party has received so far in phase 2, each checked against its
+    // dealer's VSS on arrival, until every dealer's share is in
+    pub(crate) struct Phase2Session {
         key_id: String,
         threshold: u16,
         share_count: u16,
         public_keys: Vec<SerializablePoint>,
-        secret_shares: Vec<SerializableScalar>,
-        vss_schemes: Vec<SerializableVerifiableSS>,
-        index: u16,
-    ) -> Result<SerializableSharedKeys> {
-        let mut keys_store = keys_store().lock().unwrap();
-        let key = keys_store.get_mut(&key_id)
-            .ok_or_else(|| napi::Error::new(Status::InvalidArg, "Key not found"))?;
-
-        let params = Parameters { threshold, share_count };
-        
-        let y_vec: Vec<Point<Ed25519>> = public_keys.iter()
-            .map(|pk| serializable_to_point(pk))
-            .collect::<Result<Vec<_>>>()?;
-        
-        let secret_shares_vec: Vec<Scalar<Ed25519>> = secret_shares.iter()
-            .map(|ss| serializable_to_scalar(ss))
-            .collect::<Result<Vec<_>>>()?;
+        party_index: u16,
+        shares: BTreeMap<u16, (SerializableScalar, SerializableVerifiableSS)>,
+    }
 
-        // Reconstruct VSS schemes - need to use the actual parties array [1, 2, ..., share_count]
-        let parties: Vec<u16> = (1..=share_count).collect();
-        let vss_scheme_vec: Vec<VerifiableSS<Ed25519>> = vss_schemes.iter()
-            .map(|vss| {
-                let commitments: Vec<Point<Ed25519>> = vss.commitments.iter()
-                    .map(|c| serializable_to_point(c))
-                    .collect::<Result<Vec<_>>>()?;
-                // Create a temporary VerifiableSS to get the parameters structure
-                // Use the actual parties array to match the original VSS structure
-                let (temp_vss, _) = VerifiableSS::share_at_indices(
-                    vss.threshold,
-                    vss.share_count,
-                    &Scalar::<Ed25519>::zero(), // dummy scalar, we only need the structure
-                    &parties,
-                );
-                Ok(VerifiableSS {
-                    parameters: temp_vss.parameters,
-                    commitments,
-                })
-            })
-            .collect::<Result<Vec<_>>>()?;
+    pub(crate) fn phase2_sessions_store() -> &'static Mutex<Store<Phase2Session>> {
+        static STORE: OnceLock<Mutex<Store<Phase2Session>>> = OnceLock::new();
+        STORE.get_or_init(|| Mutex::new(Store::new()))
+    }
 
-        let shared_keys = key.phase2_verify_vss_construct_keypair(
-            &params,
-            &y_vec,
-            &secret_shares_vec,
-            &vss_scheme_vec,
-            index,
-        ).map_err(|e| napi::Error::new(Status::GenericFailure, format!("{:?}", e)))?;
+    // SHA-512 of the message and nonce inputs each ephemeral key was made for by
+    // `ephemeral_key_get_or_create`, by ephemeral key id. Entries outliving their ephemeral key are ignored.
+    pub(crate) fn ephemeral_messages_store() -> &'static Mutex<Store<Vec<u8>>> {
+        static STORE: OnceLock<Mutex<Store<Vec<u8>>>> = OnceLock::new();
+        STORE.get_or_init(|| Mutex::new(Store::new()))
+    }
 
-        // prefix is a public field, so we can access it directly
-        Ok(SerializableSharedKeys {
-            y: point_to_serializable(&shared_keys.y),
-            x_i: scalar_to_serializable(&shared_keys.x_i),
-            prefix: scalar_to_serializable(&shared_keys.prefix),
-        })
+    /// What a store id names, read off the prefix this module mints it with. Key ids
+    /// carry several prefixes (`keys_`, `single_`, `restored_`, ...), so anything
+    /// without another kind's prefix is taken to be one. Phase 1 session ids are chosen
+    /// by the caller and carry no prefix, so an id is never inferred to be one.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub(crate) enum IdKind {
+        Key,
+        EphemeralKey,
+        Hasher,
+        Phase1Session,
+        Phase2Session,
+        Signer,
+        SignerRound,
     }
 
-    #[napi]
-    /// Create ephemeral key from deterministic secret
-    pub fn ephemeral_key_create(key_id: String, message: Vec<u8>, index: u16) -> Result<String> {
-        let keys_store = keys_store().lock().unwrap();
-        let key = keys_store.get(&key_id)
-            .ok_or_else(|| napi::Error::new(Status::InvalidArg, "Key not found"))?;
+    impl IdKind {
+        fn of(id: &str) -> IdKind {
+            if id.starts_with("eph_") {
+                IdKind::EphemeralKey
+            } else if id.starts_with("hasher_") {
+                IdKind::Hasher
+            } else if id.starts_with("phase2_") {
+                IdKind::Phase2Session
+            } else if id.starts_with("signer_") {
+                IdKind::Signer
+            } else if id.starts_with("round_") {
+                IdKind::SignerRound
+            } else {
+                IdKind::Key
+            }
+        }
+
+        pub(crate) fn described(self) -> &'static str {
+            match self {
+                IdKind::Key => "a key",
+                IdKind::EphemeralKey => "an ephemeral key",
+                IdKind::Hasher => "a signature hasher",
+                IdKind::Phase1Session => "a phase 1 session",
+                IdKind::Phase2Session => "a phase 2 session",
+                IdKind::Signer => "a signer",
+                IdKind::SignerRound => "a signer round",
+            }
+        }
 
-        let ephemeral_key = EphemeralKey::ephermeral_key_create_from_deterministic_secret(
-            key,
-            &message,
-            index,
-        );
+        // Error for an id missing from this kind's store: WrongIdType when the id
+        // names something else, so a mixed-up id is not reported as merely absent.
+        // Any string can be a phase 1 session id, so a missing one is just missing.
+        pub(crate) fn not_found(self, id: &str) -> napi::Error {
+            let actual = IdKind::of(id);
+            if self != IdKind::Phase1Session && actual != self {
+                return TssError::WrongIdType { id: id.to_string(), expected: self, actual }.into();
+            }
+            let what = match self {
+                IdKind::Key => "Key",
+                IdKind::EphemeralKey => "Ephemeral key",
+                IdKind::Hasher => "Hasher",
+                IdKind::Phase1Session | IdKind::Phase2Session => "Session",
+                IdKind::Signer => "Signer",
+                IdKind::SignerRound => "Round",
+            };
+            napi::Error::new(Status::InvalidArg, format!("{} not found", what))
+        }
+    }
 
-        let eph_key_id = format!("eph_{}_{}", key_id, index);
-        ephemeral_keys_store().lock().unwrap().insert(eph_key_id.clone(), ephemeral_key);
-        Ok(eph_key_id)
+    fn next_hasher_id() -> String {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        format!("hasher_{}", COUNTER.fetch_add(1, Ordering::Relaxed))
     }
 
     #[napi]
-    /// Get ephemeral R point
-    pub fn get_ephemeral_R(eph_key_id: String) -> Result<SerializablePoint> {
-        let eph_keys_store = ephemeral_keys_store().lock().unwrap();
-        let eph_key = eph_keys_store.get(&eph_key_id)
-            .ok_or_else(|| napi::Error::new(Status::InvalidArg, "Ephemeral key not found"))?;
-        Ok(point_to_serializable(&eph_key.R_i))
+    /// Store id under which `phase1_create` and `phase1_create_from_private_key` keep a party's keys
+    pub fn key_id_for_party(party_index: u16) -> String {
+        format!("keys_{}", party_index)
     }
 
     #[napi]
-    /// Ephemeral Phase 1 broadcast
-    pub fn ephemeral_phase1_broadcast(eph_key_id: String) -> Result<serde_json::Value> {
-        let mut eph_keys_store = ephemeral_keys_store().lock().unwrap();
-        let eph_key = eph_keys_store.get_mut(&eph_key_id)
-            .ok_or_else(|| napi::Error::new(Status::InvalidArg, "Ephemeral key not found"))?;
+    /// Store id under which `ephemeral_key_create` keeps the ephemeral key of `key_id` at `index`
+    pub fn ephemeral_key_id_for(key_id: String, index: u16) -> String {
+        format!("{}{}", ephemeral_key_id_prefix(&key_id), index)
+    }
 
-        let (bcm1, blind_factor) = eph_key.phase1_broadcast();
-        
-        let result = serde_json::json!({
-            "commitment": bigint_to_serializable(&bcm1.com),
-            "blind_factor": bigint_to_serializable(&blind_factor)
-        });
-        Ok(result)
+    fn ephemeral_key_id_prefix(key_id: &str) -> String {
+        format!("eph_{}_", key_id)
     }
 
-    #[napi]
-    /// Ephemeral Phase 1 verify and Phase 2 distribute
-    pub fn ephemeral_phase1_verify_com_phase2_distribute(
-        eph_key_id: String,
-        threshold: u16,
-        share_count: u16,
-        blind_factors: Vec<SerializableBigInt>,
-        R_points: Vec<SerializablePoint>,
-        commitments: Vec<SerializableBigInt>,
-        parties: Vec<u16>,
-    ) -> Result<serde_json::Value> {
-        let mut eph_keys_store = ephemeral_keys_store().lock().unwrap();
-        let eph_key = eph_keys_store.get_mut(&eph_key_id)
-            .ok_or_else(|| napi::Error::new(Status::InvalidArg, "Ephemeral key not found"))?;
+    // A long-lived key is still referenced while ephemeral keys derived from it
+    // exist; evicting it would strand an in-flight signing round.
+    fn key_has_ephemeral_keys(key_id: &str) -> bool {
+        let prefix = ephemeral_key_id_prefix(key_id);
+        lock(ephemeral_keys_store()).ids().any(|id| id.starts_with(&prefix))
+    }
 
-        let params = Parameters { threshold, share_count };
-        
-        let blind_vec: Vec<BigInt> = blind_factors.iter().map(serializable_to_bigint).collect();
-        let R_vec: Vec<Point<Ed25519>> = R_points.iter()
-            .map(|r| serializable_to_point(r))
-            .collect::<Result<Vec<_>>>()?;
-        
-        let bc1_vec: Vec<thresholdsig::KeyGenBroadcastMessage1> = commitments.iter()
-            .map(|com| thresholdsig::KeyGenBroadcastMessage1 { com: serializable_to_bigint(com) })
-            .collect();
-
-        let (vss, secret_shares) = eph_key.phase1_verify_com_phase2_distribute(
-            &params,
-            &blind_vec,
-            &R_vec,
-            &bc1_vec,
-            &parties,
-        ).map_err(|e| napi::Error::new(Status::GenericFailure, format!("{:?}", e)))?;
-
-        let vss_serializable = SerializableVerifiableSS {
-            threshold: vss.parameters.threshold,
-            share_count: vss.parameters.share_count,
-            commitments: vss.commitments.iter().map(point_to_serializable).collect(),
-        };
+    #[napi]
+    /// Ids of the ephemeral keys currently stored for `key_id`, sorted
+    pub fn list_ephemeral_keys_for(key_id: String) -> Result<Vec<String>> {
+        crate::guard::guard("list_ephemeral_keys_for", || {
+            let prefix = ephemeral_key_id_prefix(&key_id);
+            let mut ids: Vec<String> = lock(ephemeral_keys_store()).ids()
+                .filter(|id| id.starts_with(&prefix))
+                .cloned()
+                .collect();
+            ids.sort();
+            Ok(ids)
+        })
+    }
 
-        // Access shares using indexing (SecretShares implements Index)
-        let secret_shares_serializable: Vec<SerializableScalar> = (0..share_count as usize)
-            .map(|i| scalar_to_serializable(&secret_shares[i]))
-            .collect();
+    // Move a stored key to a fresh `batch_` id, for drivers that run several keygens over
+    // the same party indices, whose `keys_{i}` ids would otherwise overwrite each other
+    pub(crate) fn move_key_to_batch_id(key_id: &str) -> Result<String> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let mut keys = lock(keys_store());
+        let key = keys.remove(key_id)
+            .ok_or_else(|| IdKind::Key.not_found(key_id))?;
+        let batch_id = format!("batch_{}", COUNTER.fetch_add(1, Ordering::Relaxed));
+        keys.insert(batch_id.clone(), key, key_has_ephemeral_keys);
+        Ok(batch_id)
+    }
 
-        let result = serde_json::json!({
-            "vss": vss_serializable,
-            "secret_shares": secret_shares_serializable
-        });
-        Ok(result)
+    fn check_canonical_shares(secret_shares: &[SerializableScalar]) -> Result<()> {
+        match secret_shares.iter().position(|share| !scalar_bytes_are_canonical(&share.bytes)) {
+            Some(i) => Err(napi::Error::new(Status::InvalidArg, format!("Secret share {} is not a canonical scalar", i))),
+            None => Ok(()),
+        }
     }
 
     #[napi]
-    /// Ephemeral Phase 2 verify and construct keypair
-    pub fn ephemeral_phase2_verify_vss_construct_keypair(
-        eph_key_id: String,
-        threshold: u16,
-        share_count: u16,
-        R_points: Vec<SerializablePoint>,
-        secret_shares: Vec<SerializableScalar>,
-        vss_schemes: Vec<SerializableVerifiableSS>,
-        index: u16,
-    ) -> Result<SerializableEphemeralSharedKeys> {
-        let mut eph_keys_store = ephemeral_keys_store().lock().unwrap();
-        let eph_key = eph_keys_store.get_mut(&eph_key_id)
-            .ok_or_else(|| napi::Error::new(Status::InvalidArg, "Ephemeral key not found"))?;
+    /// Bound the key stores, evicting least-recently-used entries beyond capacity (0 = unbounded)
+    pub fn set_store_capacity(max_keys: u32, max_ephemeral: u32) -> Result<()> {
+        crate::guard::guard("set_store_capacity", || {
+            lock(keys_store()).set_capacity(max_keys as usize, key_has_ephemeral_keys);
+            lock(ephemeral_keys_store()).set_capacity(max_ephemeral as usize, |_| false);
+            Ok(())
+        })
+    }
 
-        let params = Parameters { threshold, share_count };
-        
-        let R_vec: Vec<Point<Ed25519>> = R_points.iter()
-            .map(|r| serializable_to_point(r))
-            .collect::<Result<Vec<_>>>()?;
-        
-        let secret_shares_vec: Vec<Scalar<Ed25519>> = secret_shares.iter()
-            .map(|ss| serializable_to_scalar(ss))
-            .collect::<Result<Vec<_>>>()?;
+    const DEFAULT_MAX_MESSAGE_SIZE: u32 = 16 * 1024 * 1024;
+    static MAX_MESSAGE_SIZE: AtomicU32 = AtomicU32::new(DEFAULT_MAX_MESSAGE_SIZE);
 
-        // Reconstruct VSS schemes - need to use the actual parties array
-        let parties: Vec<u16> = (1..=share_count).collect();
-        let vss_scheme_vec: Vec<VerifiableSS<Ed25519>> = vss_schemes.iter()
-            .map(|vss| {
-                let commitments: Vec<Point<Ed25519>> = vss.commitments.iter()
-                    .map(|c| serializable_to_point(c))
-                    .collect::<Result<Vec<_>>>()?;
-                // Create a temporary VerifiableSS to get the parameters structure
-                // Use the actual parties array to match the original VSS structure
-                let (temp_vss, _) = VerifiableSS::share_at_indices(
-                    vss.threshold,
-                    vss.share_count,
-                    &Scalar::<Ed25519>::zero(), // dummy scalar, we only need the structure
-                    &parties,
-                );
-                Ok(VerifiableSS {
-                    parameters: temp_vss.parameters,
-                    commitments,
-                })
-            })
-            .collect::<Result<Vec<_>>>()?;
+    #[napi]
+    /// Cap the message size accepted by `ephemeral_key_create`, `compute_local_sig` and
+    /// `verify_signature` (default 16 MiB). Streamed messages are not checked against it.
+    pub fn set_max_message_size(bytes: u32) -> Result<()> {
+        crate::guard::guard("set_max_message_size", || {
+            MAX_MESSAGE_SIZE.store(bytes, Ordering::Relaxed);
+            Ok(())
+        })
+    }
+
+    pub(crate) fn check_message_size(message: &[u8]) -> Result<()> {
+        let limit = MAX_MESSAGE_SIZE.load(Ordering::Relaxed) as usize;
+        if message.len() > limit {
+            return Err(TssError::MessageTooLarge { size: message.len(), limit }.into());
+        }
+        Ok(())
+    }
 
-        let ephemeral_shared_keys = eph_key.phase2_verify_vss_construct_keypair(
-            &params,
-            &R_vec,
-            &secret_shares_vec,
-            &vss_scheme_vec,
-            index,
-        ).map_err(|e| napi::Error::new(Status::GenericFailure, format!("{:?}", e)))?;
+    const DEFAULT_MAX_SHARE_COUNT: u32 = 1024;
+    static MAX_SHARE_COUNT: AtomicU32 = AtomicU32::new(DEFAULT_MAX_SHARE_COUNT);
 
-        Ok(SerializableEphemeralSharedKeys {
-            R: point_to_serializable(&ephemeral_shared_keys.R),
-            r_i: scalar_to_serializable(&ephemeral_shared_keys.r_i),
+    #[napi]
+    /// Cap the number of parties a sharing may have (default 1024), so absurd share counts and
+    /// party indices are refused before anything is allocated or indexed for them
+    pub fn set_max_share_count(count: u32) -> Result<()> {
+        crate::guard::guard("set_max_share_count", || {
+            MAX_SHARE_COUNT.store(count, Ordering::Relaxed);
+            Ok(())
         })
     }
 
+    pub(crate) fn check_share_count(share_count: usize) -> Result<()> {
+        let limit = MAX_SHARE_COUNT.load(Ordering::Relaxed) as usize;
+        if share_count > limit {
+            return Err(TssError::ShareCountTooLarge { share_count, limit }.into());
+        }
+        Ok(())
+    }
+
     #[napi]
-    /// Compute local signature
+    /// Create keys for a party (Phase 1)
+    pub fn phase1_create(party_index: u16) -> Result<String> {
+        crate::guard::guard("phase1_create", || {
+            let mut secret = [0u8; 32];
+            lock(rng_source()).fill_bytes(&mut secret);
+            let keys = Keys::phase1_create_from_private_key(party_index, secret);
+            let key_id = key_id_for_party(party_index);
+            lock(keys_store()).insert(key_id.clone(), StoredKey::new(keys), key_has_ephemeral_keys);
+            Ok(key_id)
+        })
+    }
+
+    #[napi]
+    /// Create keys from a private key (Phase 1)
+    pub fn phase1_create_from_private_key(party_index: u16, secret: Vec<u8>) -> Result<String> {
+        crate::guard::guard("phase1_create_from_private_key", || {
+            let secret = Zeroizing::new(secret);
+            let secret_array: Zeroizing<[u8; 32]> = Zeroizing::new(secret.as_slice().try_into()
+                .map_err(|_| napi::Error::new(Status::InvalidArg, "Secret must be 32 bytes"))?);
+            let keys = Keys::phase1_create_from_private_key(party_index, *secret_array);
+            // the seed is not kept: this key may become a share of a multi-party key, and
+            // only `import_single_party_key` keys are ever exported
+            let key_id = key_id_for_party(party_index);
+            lock(keys_store()).insert(key_id.clone(), StoredKey::new(keys), key_has_ephemeral_keys);
+            Ok(key_id)
+        })
+    }
+
+    #[napi]
+    /// Get the party's individual public key for a keys instance. This is the key broadcast in
+    /// keygen, NOT the group key signatures verify under; prefer `get_individual_public_key`
+    /// or `get_group_public_key`, which say which one they return.
+    pub fn get_public_key(key_id: String) -> Result<SerializablePoint> {
+        crate::guard::guard("get_public_key", || {
+            let mut keys = lock(keys_store());
+            let key = keys.get(&key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+            Ok(point_to_serializable(&key.keys.keypair.public_key))
+        })
+    }
+
+    #[napi]
+    /// Get the party's individual public key, the one broadcast in keygen. Never an address:
+    /// signatures verify under `get_group_public_key`.
+    pub fn get_individual_public_key(key_id: String) -> Result<SerializablePoint> {
+        crate::guard::guard("get_individual_public_key", || {
+            let mut keys = lock(keys_store());
+            let key = keys.get(&key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+            Ok(point_to_serializable(&key.keys.keypair.public_key))
+        })
+    }
+
+    #[napi]
+    /// Get the aggregated group key y of a constructed key, the key threshold signatures
+    /// verify under
+    pub fn get_group_public_key(shared_key_id: String) -> Result<SerializablePoint> {
+        crate::guard::guard("get_group_public_key", || {
+            Ok(point_to_serializable(&group_key(&shared_key_id, "get_group_public_key")?))
+        })
+    }
+
+    #[napi]
+    /// Compute the group key y from the keygen VSS schemes alone, as the sum of their zeroth
+    /// commitments. Available once every party has distributed, before anyone completes
+    /// phase 2, so a coordinator can show the address early and parties can cross-check y.
+    pub fn compute_group_public_key(vss_schemes: Vec<SerializableVerifiableSS>) -> Result<SerializablePoint> {
+        crate::guard::guard("compute_group_public_key", || {
+            Ok(point_to_serializable(&sum_zeroth_commitments("vss_schemes", &vss_schemes)?))
+        })
+    }
+
+    // y of a key whose keypair has been constructed
+    fn group_key(shared_key_id: &str, op: &'static str) -> Result<Point<Ed25519>> {
+        let mut keys = lock(keys_store());
+        let key = keys.get(shared_key_id)
+            .ok_or_else(|| IdKind::Key.not_found(shared_key_id))?;
+        key.require_phase(shared_key_id, KeyPhase::KeypairConstructed, op)?;
+        Ok(key.shared_keys.as_ref()
+            .ok_or_else(|| napi::Error::new(Status::GenericFailure, "Shared keys missing"))?
+            .y
+            .clone())
+    }
+
+    #[napi]
+    /// Get the public key as 64 bytes x || y, each affine coordinate 32 bytes little-endian.
+    /// The coordinates are checked to lie on the curve.
+    pub fn get_public_key_uncompressed(key_id: String) -> Result<Vec<u8>> {
+        crate::guard::guard("get_public_key_uncompressed", || {
+            let mut keys = lock(keys_store());
+            let key = keys.get(&key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+            let (x, y) = affine_coordinates(&key.keys.keypair.public_key)
+                .ok_or_else(|| napi::Error::from(TssError::InvalidPoint { name: "public key" }))?;
+            let mut bytes = field_element_to_bytes(&x);
+            bytes.extend(field_element_to_bytes(&y));
+            Ok(bytes)
+        })
+    }
+
+    #[napi]
+    /// Get the public key together with the party index the key was created with
+    pub fn get_key_metadata(key_id: String) -> Result<SerializableKeyMetadata> {
+        crate::guard::guard("get_key_metadata", || {
+            let mut keys = lock(keys_store());
+            let key = keys.get(&key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+            Ok(SerializableKeyMetadata {
+                public_key: point_to_serializable(&key.keys.keypair.public_key),
+                party_index: key.keys.party_index,
+            })
+        })
+    }
+
+    #[napi]
+    /// Get the keygen phase a key has completed ("Created", "Broadcast", "Distributed" or "KeypairConstructed")
+    pub fn get_key_phase(key_id: String) -> Result<String> {
+        crate::guard::guard("get_key_phase", || {
+            let mut keys = lock(keys_store());
+            let key = keys.get(&key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+            Ok(format!("{:?}", key.phase))
+        })
+    }
+
+    /// Phase 1 commitment to a party's public key share.
+    ///
+    /// `HashCommitment` (the default) is the protocol's blinded hash commitment: it hides
+    /// the point and needs the blind factor sent alongside it. `Deterministic` hashes the
+    /// point alone under a domain tag, so nothing extra is transmitted, but hiding rests
+    /// only on the entropy of the point and a reused key yields the same commitment in
+    /// every session, making sessions linkable. Binding is the same under both.
+    #[napi(string_enum)]
+    #[derive(Debug, PartialEq, Deserialize)]
+    // napi derives Clone and Copy for string enums, but not in the `noop` build
+    #[cfg_attr(feature = "noop", derive(Clone, Copy))]
+    pub enum CommitmentScheme {
+        HashCommitment,
+        Deterministic,
+    }
+
+    fn deterministic_commitment(y: &Point<Ed25519>) -> BigInt {
+        let digest = Sha512::new()
+            .chain(b"multi-party-eddsa deterministic commitment")
+            .chain(&*y.to_bytes(true))
+            .finalize();
+        BigInt::from_bytes(&digest)
+    }
+
+    // What `phase1_broadcast` and `ephemeral_phase1_broadcast` return
+    #[derive(Serialize)]
+    struct BroadcastResult {
+        commitment: SerializableBigInt,
+        blind_factor: Option<SerializableBigInt>,
+    }
+
+    // What the phase 2 distribute steps return
+    #[derive(Serialize)]
+    struct DistributeResult {
+        vss: SerializableVerifiableSS,
+        secret_shares: Vec<SerializableScalar>,
+        share_envelopes: Vec<SerializableSecretShareEnvelope>,
+    }
+
+    #[napi]
+    /// Phase 1 broadcast - returns commitment and blind factor (null under the `Deterministic` scheme)
+    pub fn phase1_broadcast(key_id: String, scheme: Option<CommitmentScheme>) -> Result<serde_json::Value> {
+        crate::guard::guard("phase1_broadcast", || {
+            let mut keys_store = lock(keys_store());
+            let key = keys_store.get_mut(&key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+
+            let broadcast = match scheme.unwrap_or(CommitmentScheme::HashCommitment) {
+                CommitmentScheme::HashCommitment => {
+                    let (bcm1, blind_factor) = key.keys.phase1_broadcast_rng(&mut *lock(rng_source()));
+                    BroadcastResult {
+                        commitment: bigint_to_serializable(&bcm1.com),
+                        blind_factor: Some(bigint_to_serializable(&blind_factor)),
+                    }
+                }
+                CommitmentScheme::Deterministic => BroadcastResult {
+                    commitment: bigint_to_serializable(&deterministic_commitment(&key.keys.keypair.public_key)),
+                    blind_factor: None,
+                },
+            };
+            let result = to_json("phase1_broadcast", &broadcast)?;
+            key.advance(KeyPhase::Broadcast);
+            Ok(result)
+        })
+    }
+
+    #[napi]
+    /// `phase1_broadcast` with the blind factor zero-padded to `blind_factor_length()` bytes,
+    /// for transports with fixed-size fields. It is accepted by the distribute step as is.
+    pub fn phase1_broadcast_fixed(key_id: String, scheme: Option<CommitmentScheme>) -> Result<serde_json::Value> {
+        crate::guard::guard("phase1_broadcast_fixed", || fix_blind_factor(phase1_broadcast(key_id, scheme)?))
+    }
+
+    // Replace the minimal blind factor of a broadcast with its fixed-length encoding; a null
+    // blind factor (`Deterministic` scheme) stays null
+    fn fix_blind_factor(mut broadcast: serde_json::Value) -> Result<serde_json::Value> {
+        if broadcast["blind_factor"].is_null() {
+            return Ok(broadcast);
+        }
+        let blind_factor: SerializableBigInt = serde_json::from_value(broadcast["blind_factor"].clone())
+            .map_err(|e| napi::Error::new(Status::GenericFailure, format!("Serialization error: {}", e)))?;
+        let fixed = SerializableBigInt {
+            bytes: crate::bigint::blind_factor_fixed_bytes(&serializable_to_bigint(&blind_factor))?,
+        };
+        broadcast["blind_factor"] = to_json("blind_factor", &fixed)?;
+        Ok(broadcast)
+    }
+
+    #[napi]
+    /// `phase1_broadcast` assembled with the party's index and public key into the single
+    /// message peers need, for `phase1_verify_com_phase2_distribute_messages`
+    pub fn phase1_broadcast_message(key_id: String, scheme: Option<CommitmentScheme>) -> Result<SerializableKeygenMessage1> {
+        crate::guard::guard("phase1_broadcast_message", || {
+            let broadcast = phase1_broadcast(key_id.clone(), scheme)?;
+            let part = |name: &str| -> Result<Option<SerializableBigInt>> {
+                serde_json::from_value(broadcast[name].clone())
+                    .map_err(|e| napi::Error::new(Status::GenericFailure, format!("Serialization error: {}", e)))
+            };
+            let commitment = part("commitment")?
+                .ok_or_else(|| napi::Error::new(Status::GenericFailure, "Broadcast has no commitment"))?;
+            let blind_factor = part("blind_factor")?.unwrap_or(SerializableBigInt { bytes: vec![] });
+            let mut keys = lock(keys_store());
+            let key = keys.get(&key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+            Ok(SerializableKeygenMessage1 {
+                party_index: key.keys.party_index,
+                public_key: point_to_serializable(&key.keys.keypair.public_key),
+                commitment,
+                blind_factor,
+            })
+        })
+    }
+
+    #[napi]
+    #[allow(clippy::too_many_arguments)]
+    /// Phase 1 verify commitments and Phase 2 distribute shares. `scheme` must match the one
+    /// used in `phase1_broadcast`; `blind_factors` is ignored under `Deterministic`.
+    /// `strict_subgroup` reports a peer key with a small-order component as `NotInSubgroup`.
+    pub fn phase1_verify_com_phase2_distribute(
+        key_id: String,
+        threshold: u16,
+        share_count: u16,
+        blind_factors: Vec<SerializableBigInt>,
+        public_keys: Vec<SerializablePoint>,
+        commitments: Vec<SerializableBigInt>,
+        parties: Vec<u16>,
+        scheme: Option<CommitmentScheme>,
+        strict_subgroup: Option<bool>,
+    ) -> Result<serde_json::Value> {
+        crate::guard::guard("phase1_verify_com_phase2_distribute", || {
+            let mut keys_store = lock(keys_store());
+            let key = keys_store.get_mut(&key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+            key.require_phase(&key_id, KeyPhase::Broadcast, "phase1_verify_com_phase2_distribute")?;
+            check_sharing_params(threshold, share_count, &parties)?;
+            // keygen shards for parties 1..=share_count, which the aggregation assumes;
+            // signing subsets are indexed independently in the ephemeral round
+            if let Some(party) = parties.iter().find(|&&p| p > share_count) {
+                return Err(napi::Error::new(Status::InvalidArg, format!("Party index {} out of range 1..={}", party, share_count)));
+            }
+            if strict_subgroup.unwrap_or(false) {
+                check_subgroup("public key", &public_keys)?;
+            }
+
+            let params = Parameters { threshold, share_count };
+        
+            let blind_vec: Vec<BigInt> = blind_factors.iter().map(serializable_to_bigint).collect();
+            let y_vec: Vec<Point<Ed25519>> = public_keys.iter()
+                .map(serializable_to_point)
+                .collect::<Result<Vec<_>>>()?;
+            check_distinct_public_keys(&y_vec)?;
+        
+            let bc1_vec: Vec<thresholdsig::KeyGenBroadcastMessage1> = commitments.iter()
+                .map(|com| thresholdsig::KeyGenBroadcastMessage1 { com: serializable_to_bigint(com) })
+                .collect();
+
+            let (vss, secret_shares) = match scheme.unwrap_or(CommitmentScheme::HashCommitment) {
+                CommitmentScheme::HashCommitment => with_verification_pool(|| key.keys.phase1_verify_com_phase2_distribute_rng(
+                    &params,
+                    &blind_vec,
+                    &y_vec,
+                    &bc1_vec,
+                    &parties,
+                    &mut *lock(rng_source()),
+                )).map_err(|e| match thresholdsig::find_invalid_decommitment(&blind_vec, &y_vec, &bc1_vec) {
+                    Some(i) => napi::Error::from(TssError::InvalidDecommitment { party: i + 1 }),
+                    None => napi::Error::new(Status::GenericFailure, format!("{:?}", e)),
+                })?,
+                CommitmentScheme::Deterministic => {
+                    if y_vec.len() != usize::from(share_count) || bc1_vec.len() != usize::from(share_count) {
+                        return Err(napi::Error::new(Status::InvalidArg, "Expected one public key and one commitment per party"));
+                    }
+                    if let Some(i) = y_vec.iter().zip(&bc1_vec).position(|(y, bc1)| deterministic_commitment(y) != bc1.com) {
+                        return Err(TssError::InvalidDecommitment { party: i + 1 }.into());
+                    }
+                    key.keys.phase2_distribute_rng(&params, &parties, &mut *lock(rng_source()))
+                }
+            };
+            key.advance(KeyPhase::Distributed);
+
+            // Serialize VSS
+            let vss_serializable = SerializableVerifiableSS {
+                threshold: vss.parameters.threshold,
+                share_count: vss.parameters.share_count,
+                commitments: vss.commitments.iter().map(point_to_serializable).collect(),
+            };
+
+            let secret_shares_serializable: Vec<SerializableScalar> = secret_shares.iter()
+                .map(scalar_to_serializable)
+                .collect();
+
+            // share i is for the party at parties[i]
+            let share_envelopes: Vec<SerializableSecretShareEnvelope> = parties.iter()
+                .zip(secret_shares_serializable.iter())
+                .map(|(&recipient_index, share)| SerializableSecretShareEnvelope {
+                    recipient_index,
+                    share: share.clone(),
+                })
+                .collect();
+
+            to_json("phase2_distribution", &DistributeResult {
+                vss: vss_serializable,
+                secret_shares: secret_shares_serializable,
+                share_envelopes,
+            })
+        })
+    }
+
+    #[napi]
+    /// `phase1_verify_com_phase2_distribute` over the messages of `phase1_broadcast_message`,
+    /// one per party in any order. They are sorted by party index, so the parties and their
+    /// keys, commitments and blind factors cannot be paired up wrongly.
+    pub fn phase1_verify_com_phase2_distribute_messages(
+        key_id: String,
+        threshold: u16,
+        share_count: u16,
+        mut messages: Vec<SerializableKeygenMessage1>,
+        scheme: Option<CommitmentScheme>,
+        strict_subgroup: Option<bool>,
+    ) -> Result<serde_json::Value> {
+        crate::guard::guard("phase1_verify_com_phase2_distribute_messages", || {
+            messages.sort_by_key(|message| message.party_index);
+            let parties: Vec<u16> = messages.iter().map(|message| message.party_index).collect();
+            check_distinct_parties(&parties)?;
+            let mut blind_factors = Vec::new();
+            let mut public_keys = Vec::new();
+            let mut commitments = Vec::new();
+            for message in messages {
+                blind_factors.push(message.blind_factor);
+                public_keys.push(message.public_key);
+                commitments.push(message.commitment);
+            }
+            phase1_verify_com_phase2_distribute(key_id, threshold, share_count, blind_factors, public_keys, commitments, parties, scheme, strict_subgroup)
+        })
+    }
+
+    #[napi]
+    /// Buffer one party's phase 1 broadcast in a coordinator session, creating the session on
+    /// first use. Pass empty blind factor bytes under the `Deterministic` scheme. Resending the
+    /// same broadcast is a no-op; a different broadcast for an already collected party is rejected.
+    pub fn phase1_collect(
+        session_id: String,
+        party_index: u16,
+        commitment: SerializableBigInt,
+        blind_factor: SerializableBigInt,
+        public_key: SerializablePoint,
+    ) -> Result<()> {
+        crate::guard::guard("phase1_collect", || {
+            if party_index == 0 {
+                return Err(napi::Error::new(Status::InvalidArg, "Party indices start at 1"));
+            }
+            let mut sessions = lock(phase1_sessions_store());
+            if sessions.get(&session_id).is_none() {
+                sessions.insert(session_id.clone(), Phase1Session { broadcasts: BTreeMap::new(), vss_schemes: BTreeMap::new() }, |_| false);
+            }
+            let session = sessions.get_mut(&session_id)
+                .ok_or_else(|| napi::Error::new(Status::GenericFailure, "Session was evicted"))?;
+
+            let broadcast = Phase1Broadcast { commitment, blind_factor, public_key };
+            match session.broadcasts.get(&party_index) {
+                Some(existing) if existing.commitment.bytes != broadcast.commitment.bytes
+                    || existing.blind_factor.bytes != broadcast.blind_factor.bytes
+                    || existing.public_key.bytes != broadcast.public_key.bytes =>
+                {
+                    Err(napi::Error::new(Status::InvalidArg, format!("Party {} already sent a different broadcast", party_index)))
+                }
+                Some(_) => Ok(()),
+                None => {
+                    session.broadcasts.insert(party_index, broadcast);
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    #[napi]
+    /// Whether the session holds a broadcast from every party 1..=share_count
+    pub fn phase1_ready(session_id: String, share_count: u16) -> Result<bool> {
+        crate::guard::guard("phase1_ready", || {
+            check_share_count(usize::from(share_count))?;
+            let mut sessions = lock(phase1_sessions_store());
+            Ok(sessions.get(&session_id).is_some_and(|session| {
+                (1..=share_count).all(|party| session.broadcasts.contains_key(&party))
+            }))
+        })
+    }
+
+    #[napi]
+    /// `phase1_verify_com_phase2_distribute` over the broadcasts buffered by `phase1_collect`,
+    /// ordered by party index regardless of arrival order. The session is kept so every local
+    /// party can verify against it.
+    pub fn phase1_verify_collected(
+        session_id: String,
+        key_id: String,
+        threshold: u16,
+        share_count: u16,
+        scheme: Option<CommitmentScheme>,
+    ) -> Result<serde_json::Value> {
+        crate::guard::guard("phase1_verify_collected", || {
+            let (blind_factors, public_keys, commitments, parties) = {
+                let mut sessions = lock(phase1_sessions_store());
+                let session = sessions.get(&session_id)
+                    .ok_or_else(|| IdKind::Phase1Session.not_found(&session_id))?;
+                if let Some(missing) = (1..=share_count).find(|party| !session.broadcasts.contains_key(party)) {
+                    return Err(napi::Error::new(Status::InvalidArg, format!("No broadcast collected from party {}", missing)));
+                }
+                if session.broadcasts.len() != usize::from(share_count) {
+                    return Err(napi::Error::new(Status::InvalidArg, format!("Collected {} broadcasts, expected {}", session.broadcasts.len(), share_count)));
+                }
+                let mut blind_factors = Vec::new();
+                let mut public_keys = Vec::new();
+                let mut commitments = Vec::new();
+                for broadcast in session.broadcasts.values() {
+                    blind_factors.push(broadcast.blind_factor.clone());
+                    public_keys.push(broadcast.public_key.clone());
+                    commitments.push(broadcast.commitment.clone());
+                }
+                (blind_factors, public_keys, commitments, session.broadcasts.keys().copied().collect())
+            };
+            let party_index = lock(keys_store()).get(&key_id)
+                .map(|key| key.keys.party_index)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+            let distribute = phase1_verify_com_phase2_distribute(key_id, threshold, share_count, blind_factors, public_keys, commitments, parties, scheme, None)?;
+            let vss = serde_json::from_value(distribute["vss"].clone())
+                .map_err(|e| napi::Error::new(Status::GenericFailure, e.to_string()))?;
+            phase2_collect_vss(session_id, party_index, vss)?;
+            Ok(distribute)
+        })
+    }
+
+    #[napi]
+    /// Record a party's keygen VSS scheme in a coordinator session for `export_keygen_transcript`.
+    /// `phase1_verify_collected` records those of local parties itself. Resending the same
+    /// scheme is a no-op; a different scheme for an already recorded party is rejected.
+    pub fn phase2_collect_vss(session_id: String, party_index: u16, vss: SerializableVerifiableSS) -> Result<()> {
+        crate::guard::guard("phase2_collect_vss", || {
+            serializable_to_vss(&vss)?;
+            let mut sessions = lock(phase1_sessions_store());
+            let session = sessions.get_mut(&session_id)
+                .ok_or_else(|| IdKind::Phase1Session.not_found(&session_id))?;
+            match session.vss_schemes.get(&party_index) {
+                Some(existing) if vss_to_bytes(existing.clone())? != vss_to_bytes(vss.clone())? => {
+                    Err(napi::Error::new(Status::InvalidArg, format!("Party {} already sent a different VSS scheme", party_index)))
+                }
+                Some(_) => Ok(()),
+                None => {
+                    session.vss_schemes.insert(party_index, vss);
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    #[napi]
+    /// Start receiving phase 2 shares one at a time for a distributed key, as an alternative
+    /// to passing them all to `phase2_verify_vss_construct_keypair`. `public_keys` are the
+    /// phase 1 keys of parties 1..=share_count. Returns the session id.
+    pub fn phase2_open(key_id: String, threshold: u16, share_count: u16, public_keys: Vec<SerializablePoint>) -> Result<String> {
+        crate::guard::guard("phase2_open", || {
+            static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+            let parties: Vec<u16> = (1..=share_count).collect();
+            check_sharing_params(threshold, share_count, &parties)?;
+            if public_keys.len() != usize::from(share_count) {
+                return Err(napi::Error::new(Status::InvalidArg, format!("Expected {} public keys, got {}", share_count, public_keys.len())));
+            }
+            let party_index = {
+                let mut keys = lock(keys_store());
+                let key = keys.get(&key_id)
+                    .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+                key.require_phase(&key_id, KeyPhase::Distributed, "phase2_open")?;
+                key.keys.party_index
+            };
+            let session_id = format!("phase2_{}", COUNTER.fetch_add(1, Ordering::Relaxed));
+            let session = Phase2Session { key_id, threshold, share_count, public_keys, party_index, shares: BTreeMap::new() };
+            lock(phase2_sessions_store()).insert(session_id.clone(), session, |_| false);
+            Ok(session_id)
+        })
+    }
+
+    #[napi]
+    /// Check the share dealt by `from_party` against its VSS scheme as soon as it arrives,
+    /// returning false, and buffering nothing, if it does not match or the scheme does not
+    /// commit to the dealer's public key. Resending an accepted share is a no-op.
+    pub fn phase2_receive_share(session_id: String, from_party: u16, share: SerializableScalar, vss: SerializableVerifiableSS) -> Result<bool> {
+        crate::guard::guard("phase2_receive_share", || {
+            let mut sessions = lock(phase2_sessions_store());
+            let session = sessions.get_mut(&session_id)
+                .ok_or_else(|| IdKind::Phase2Session.not_found(&session_id))?;
+            if from_party == 0 || from_party > session.share_count {
+                return Err(napi::Error::new(Status::InvalidArg, format!("Party index {} out of range 1..={}", from_party, session.share_count)));
+            }
+            if let Some((existing, _)) = session.shares.get(&from_party) {
+                if existing.bytes != share.bytes {
+                    return Err(napi::Error::new(Status::InvalidArg, format!("Party {} already sent a different share", from_party)));
+                }
+                return Ok(true);
+            }
+
+            let vss_scheme = serializable_to_vss(&vss)?;
+            let secret_share = serializable_to_scalar(&share)?;
+            let dealer_key = serializable_to_point(&session.public_keys[usize::from(from_party - 1)])?;
+            let valid = vss.threshold == session.threshold
+                && vss.share_count == session.share_count
+                && thresholdsig::find_invalid_share(&[vss_scheme], &[secret_share], &[dealer_key], session.party_index).is_none();
+            if valid {
+                session.shares.insert(from_party, (share, vss));
+            }
+            Ok(valid)
+        })
+    }
+
+    #[napi]
+    /// Construct the keypair from the shares of every party received in the session, then
+    /// close the session
+    pub fn phase2_construct(session_id: String) -> Result<SerializableSharedKeys> {
+        crate::guard::guard("phase2_construct", || {
+            let session = lock(phase2_sessions_store()).remove(&session_id)
+                .ok_or_else(|| IdKind::Phase2Session.not_found(&session_id))?;
+            if let Some(missing) = (1..=session.share_count).find(|party| !session.shares.contains_key(party)) {
+                let error = napi::Error::new(Status::InvalidArg, format!("No share received from party {}", missing));
+                lock(phase2_sessions_store()).insert(session_id, session, |_| false);
+                return Err(error);
+            }
+            let (secret_shares, vss_schemes) = session.shares.values().cloned().unzip();
+            phase2_verify_vss_construct_keypair(
+                session.key_id,
+                session.threshold,
+                session.share_count,
+                session.public_keys,
+                secret_shares,
+                vss_schemes,
+                session.party_index,
+                None,
+            )
+        })
+    }
+
+    const TRANSCRIPT_TAG: &[u8] = b"multi-party-eddsa keygen transcript";
+
+    fn transcript_checksum(body: &[u8]) -> Vec<u8> {
+        Sha512::new().chain(TRANSCRIPT_TAG).chain(body).finalize()[..32].to_vec()
+    }
+
+    #[napi]
+    /// Non-secret record of a keygen collected in a coordinator session, for audit: threshold
+    /// and share_count (u16 LE) and the group key, then per party in index order its index
+    /// (u16 LE), public key, commitment (u16 LE length, big-endian bytes) and its threshold + 1
+    /// VSS commitments, closed by a 32-byte checksum of everything before it. Secret shares
+    /// and blind factors are never included.
+    pub fn export_keygen_transcript(session_id: String) -> Result<Vec<u8>> {
+        crate::guard::guard("export_keygen_transcript", || {
+            let mut sessions = lock(phase1_sessions_store());
+            let session = sessions.get(&session_id)
+                .ok_or_else(|| IdKind::Phase1Session.not_found(&session_id))?;
+            check_non_empty("session broadcasts", session.broadcasts.len())?;
+            let share_count = session.broadcasts.len() as u16;
+            if let Some(missing) = (1..=share_count).find(|party| !session.broadcasts.contains_key(party) || !session.vss_schemes.contains_key(party)) {
+                return Err(napi::Error::new(Status::InvalidArg, format!("Session is missing the broadcast or VSS scheme of party {}", missing)));
+            }
+            let threshold = session.vss_schemes[&1].threshold;
+            let group_key = session.broadcasts.values()
+                .map(|broadcast| serializable_to_point(&broadcast.public_key))
+                .try_fold(Point::<Ed25519>::zero(), |acc, y| y.map(|y| acc + y))?;
+
+            let mut bytes = threshold.to_le_bytes().to_vec();
+            bytes.extend_from_slice(&share_count.to_le_bytes());
+            bytes.extend_from_slice(&group_key.to_bytes(true));
+            for (party, broadcast) in &session.broadcasts {
+                let vss = &session.vss_schemes[party];
+                if vss.threshold != threshold || vss.share_count != share_count {
+                    return Err(napi::Error::new(Status::InvalidArg, format!("VSS scheme of party {} does not match the session parameters", party)));
+                }
+                bytes.extend_from_slice(&party.to_le_bytes());
+                bytes.extend_from_slice(&serializable_to_point(&broadcast.public_key)?.to_bytes(true));
+                bytes.extend_from_slice(&(broadcast.commitment.bytes.len() as u16).to_le_bytes());
+                bytes.extend_from_slice(&broadcast.commitment.bytes);
+                for commitment in &serializable_to_vss(vss)?.commitments {
+                    bytes.extend_from_slice(&commitment.to_bytes(true));
+                }
+            }
+            let checksum = transcript_checksum(&bytes);
+            bytes.extend(checksum);
+            Ok(bytes)
+        })
+    }
+
+    // Parse a transcript, returning None as soon as it is malformed or any check fails
+    fn check_keygen_transcript(bytes: &[u8]) -> Option<()> {
+        let (body, checksum) = bytes.split_at(bytes.len().checked_sub(32)?);
+        if transcript_checksum(body) != checksum {
+            return None;
+        }
+        let mut rest = body;
+        let mut take = |n: usize| -> Option<&[u8]> {
+            if rest.len() < n {
+                return None;
+            }
+            let (head, tail) = rest.split_at(n);
+            rest = tail;
+            Some(head)
+        };
+        let read_u16 = |bytes: &[u8]| u16::from_le_bytes([bytes[0], bytes[1]]);
+        let read_point = |bytes: &[u8]| Point::<Ed25519>::from_bytes(bytes).ok();
+
+        let threshold = read_u16(take(2)?);
+        let share_count = read_u16(take(2)?);
+        if threshold >= share_count {
+            return None;
+        }
+        let group_key = read_point(take(32)?)?;
+        let mut key_sum = Point::<Ed25519>::zero();
+        for expected_party in 1..=share_count {
+            if read_u16(take(2)?) != expected_party {
+                return None;
+            }
+            let public_key = read_point(take(32)?)?;
+            let commitment_len = usize::from(read_u16(take(2)?));
+            take(commitment_len)?;
+            let commitments = (0..expected_commitments(threshold))
+                .map(|_| read_point(take(32)?))
+                .collect::<Option<Vec<_>>>()?;
+            // the VSS shares the secret behind the committed key, so it must open to it at zero
+            if commitments[0] != public_key {
+                return None;
+            }
+            key_sum = key_sum + public_key;
+        }
+        if !rest.is_empty() || key_sum != group_key {
+            return None;
+        }
+        Some(())
+    }
+
+    #[napi]
+    /// Re-check a transcript from `export_keygen_transcript`: its checksum, that every party's
+    /// VSS commits at zero to the public key it broadcast, and that the group key is the sum
+    /// of those keys. The checksum guards against corruption, not against a forger, who can
+    /// recompute it; authenticate the transcript separately if that matters.
+    pub fn verify_keygen_transcript(bytes: Vec<u8>) -> Result<bool> {
+        crate::guard::guard("verify_keygen_transcript", || {
+            Ok(check_keygen_transcript(&bytes).is_some())
+        })
+    }
+
+    #[napi]
+    /// Dry run of the phase 1 checks in `phase1_verify_com_phase2_distribute`: reports per party
+    /// whether its public key decodes and opens its commitment, without touching any store
+    pub fn validate_keygen_inputs(
+        threshold: u16,
+        share_count: u16,
+        blind_factors: Vec<SerializableBigInt>,
+        public_keys: Vec<SerializablePoint>,
+        commitments: Vec<SerializableBigInt>,
+        parties: Vec<u16>,
+        scheme: Option<CommitmentScheme>,
+    ) -> Result<SerializableValidationReport> {
+        crate::guard::guard("validate_keygen_inputs", || {
+            let scheme = scheme.unwrap_or(CommitmentScheme::HashCommitment);
+            let mut errors = Vec::new();
+            if let Err(e) = check_share_count(usize::from(share_count)) {
+                errors.push(e.reason);
+            }
+            if threshold >= share_count {
+                errors.push(format!("threshold {} must be below share_count {}", threshold, share_count));
+            }
+            let mut lengths = vec![
+                ("public_keys", public_keys.len()),
+                ("commitments", commitments.len()),
+                ("parties", parties.len()),
+            ];
+            if let CommitmentScheme::HashCommitment = scheme {
+                lengths.push(("blind_factors", blind_factors.len()));
+            }
+            for (name, len) in lengths {
+                if len != usize::from(share_count) {
+                    errors.push(format!("expected {} {}, got {}", share_count, name, len));
+                }
+            }
+
+            let parties_report: Vec<SerializablePartyValidation> = (0..public_keys.len())
+                .map(|i| {
+                    let party_index = parties.get(i).copied().unwrap_or(i as u16 + 1);
+                    let check = || -> std::result::Result<(), TssError> {
+                        let y = serializable_to_point(&public_keys[i])
+                            .map_err(|_| TssError::InvalidPoint { name: "public key" })?;
+                        let com = commitments.get(i).map(serializable_to_bigint);
+                        let opens = match scheme {
+                            CommitmentScheme::HashCommitment => match (com, blind_factors.get(i)) {
+                                (Some(com), Some(blind)) => thresholdsig::find_invalid_decommitment(
+                                    &[serializable_to_bigint(blind)],
+                                    &[y],
+                                    &[thresholdsig::KeyGenBroadcastMessage1 { com }],
+                                ).is_none(),
+                                _ => false,
+                            },
+                            CommitmentScheme::Deterministic => com == Some(deterministic_commitment(&y)),
+                        };
+                        if !opens {
+                            return Err(TssError::InvalidDecommitment { party: usize::from(party_index) });
+                        }
+                        Ok(())
+                    };
+                    let result = check();
+                    SerializablePartyValidation {
+                        party_index,
+                        valid: result.is_ok(),
+                        reason: result.err().map(|e| e.to_string()),
+                    }
+                })
+                .collect();
+
+            Ok(SerializableValidationReport {
+                valid: errors.is_empty() && parties_report.iter().all(|p| p.valid),
+                errors,
+                parties: parties_report,
+            })
+        })
+    }
+
+    #[napi]
+    /// Cross-check every dealer's VSS against the share it dealt to every recipient, where
+    /// `shares_matrix[d][r]` is the share `parties[d]` dealt to `parties[r]`. A dealer that gave
+    /// some recipients shares off its committed polynomial is reported even when each recipient
+    /// alone cannot tell. Does not touch any store.
+    pub fn check_vss_consistency(
+        vss_schemes: Vec<SerializableVerifiableSS>,
+        shares_matrix: Vec<Vec<SerializableScalar>>,
+        parties: Vec<u16>,
+    ) -> Result<SerializableConsistencyReport> {
+        crate::guard::guard("check_vss_consistency", || {
+            check_share_count(parties.len())?;
+            check_distinct_parties(&parties)?;
+            let mut errors = Vec::new();
+            for (name, len) in [("vss_schemes", vss_schemes.len()), ("shares_matrix rows", shares_matrix.len())] {
+                if len != parties.len() {
+                    errors.push(format!("expected {} {}, got {}", parties.len(), name, len));
+                }
+            }
+
+            let mut equivocating_dealers = Vec::new();
+            let mut mismatches = Vec::new();
+            for ((&dealer, vss), row) in parties.iter().zip(&vss_schemes).zip(&shares_matrix) {
+                let vss = match serializable_to_vss(vss) {
+                    Ok(vss) => vss,
+                    Err(e) => {
+                        errors.push(format!("VSS of dealer {}: {}", dealer, e.reason));
+                        equivocating_dealers.push(dealer);
+                        continue;
+                    }
+                };
+                if row.len() != parties.len() {
+                    errors.push(format!("expected {} shares from dealer {}, got {}", parties.len(), dealer, row.len()));
+                }
+                let mut equivocated = false;
+                for (&recipient, share) in parties.iter().zip(row) {
+                    let on_polynomial = serializable_to_scalar(share)
+                        .is_ok_and(|share| vss.validate_share(&share, recipient).is_ok());
+                    if !on_polynomial {
+                        mismatches.push(SerializableShareMismatch { dealer, recipient });
+                        equivocated = true;
+                    }
+                }
+                if equivocated {
+                    equivocating_dealers.push(dealer);
+                }
+            }
+
+            Ok(SerializableConsistencyReport {
+                consistent: errors.is_empty() && equivocating_dealers.is_empty(),
+                errors,
+                equivocating_dealers,
+                mismatches,
+            })
+        })
+    }
+
+    #[napi]
+    #[allow(clippy::too_many_arguments)]
+    /// Phase 2 verify VSS and construct keypair; `strict_shares` rejects non-canonical secret shares
+    pub fn phase2_verify_vss_construct_keypair(
+        key_id: String,
+        threshold: u16,
+        share_count: u16,
+        public_keys: Vec<SerializablePoint>,
+        secret_shares: Vec<SerializableScalar>,
+        vss_schemes: Vec<SerializableVerifiableSS>,
+        index: u16,
+        strict_shares: Option<bool>,
+    ) -> Result<SerializableSharedKeys> {
+        crate::guard::guard("phase2_verify_vss_construct_keypair", || {
+            check_share_count(usize::from(share_count))?;
+            if strict_shares.unwrap_or(false) {
+                check_canonical_shares(&secret_shares)?;
+            }
+
+            let mut keys_store = lock(keys_store());
+            let key = keys_store.get_mut(&key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+            key.require_phase(&key_id, KeyPhase::Distributed, "phase2_verify_vss_construct_keypair")?;
+
+            let params = Parameters { threshold, share_count };
+        
+            let y_vec: Vec<Point<Ed25519>> = public_keys.iter()
+                .map(serializable_to_point)
+                .collect::<Result<Vec<_>>>()?;
+        
+            let secret_shares_vec: Vec<Scalar<Ed25519>> = secret_shares.iter()
+                .map(serializable_to_scalar)
+                .collect::<Result<Vec<_>>>()?;
+
+            let vss_scheme_vec: Vec<VerifiableSS<Ed25519>> = vss_schemes.iter()
+                .map(serializable_to_vss)
+                .collect::<Result<Vec<_>>>()?;
+
+            let shared_keys = with_verification_pool(|| key.keys.phase2_verify_vss_construct_keypair(
+                &params,
+                &y_vec,
+                &secret_shares_vec,
+                &vss_scheme_vec,
+                index,
+            )).map_err(|e| match thresholdsig::find_invalid_share(&vss_scheme_vec, &secret_shares_vec, &y_vec, index) {
+                Some(i) => TssError::InvalidShare { party: i + 1 }.into(),
+                None => napi::Error::new(Status::GenericFailure, format!("{:?}", e)),
+            })?;
+            key.advance(KeyPhase::KeypairConstructed);
+
+            // prefix is a public field, so we can access it directly
+            let result = SerializableSharedKeys {
+                y: point_to_serializable(&shared_keys.y),
+                x_i: scalar_to_serializable(&shared_keys.x_i),
+                prefix: scalar_to_serializable(&shared_keys.prefix),
+            };
+            key.shared_keys = Some(shared_keys);
+            metrics::record_keygen();
+            Ok(result)
+        })
+    }
+
+    #[napi]
+    /// Import an existing Ed25519 secret as a 1-of-1 key (threshold 0, share_count 1), keeping
+    /// the standard Ed25519 public key of the seed. The returned key id is ready for
+    /// `ephemeral_key_create` at index 1; its keygen VSS is threshold 0, share_count 1 with
+    /// the public key as the only commitment.
+    pub fn import_single_party_key(secret: Vec<u8>) -> Result<String> {
+        crate::guard::guard("import_single_party_key", || {
+            static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+            let secret = Zeroizing::new(secret);
+            let secret_array: Zeroizing<[u8; 32]> = Zeroizing::new(secret.as_slice().try_into()
+                .map_err(|_| napi::Error::new(Status::InvalidArg, "Secret must be 32 bytes"))?);
+            let keys = Keys::phase1_create_from_private_key(1, *secret_array);
+
+            // run the degenerate 1-of-1 keygen against ourselves; the only share is the whole secret
+            let params = Parameters { threshold: 0, share_count: 1 };
+            let y_vec = vec![keys.keypair.public_key.clone()];
+            let mut rng = lock(rng_source());
+            let (bcm1, blind_factor) = keys.phase1_broadcast_rng(&mut *rng);
+            let (vss, secret_shares) = keys.phase1_verify_com_phase2_distribute_rng(
+                &params,
+                &[blind_factor],
+                &y_vec,
+                &[bcm1],
+                &[1],
+                &mut *rng,
+            ).map_err(|e| napi::Error::new(Status::GenericFailure, format!("{:?}", e)))?;
+            drop(rng);
+            let shared_keys = keys.phase2_verify_vss_construct_keypair(
+                &params,
+                &y_vec,
+                &secret_shares,
+                &[vss],
+                1,
+            ).map_err(|e| napi::Error::new(Status::GenericFailure, format!("{:?}", e)))?;
+
+            let key_id = format!("single_{}", COUNTER.fetch_add(1, Ordering::Relaxed));
+            let stored = StoredKey::constructed(keys, KeyPhase::KeypairConstructed, Some(shared_keys))
+                .with_seed(*secret_array);
+            lock(keys_store()).insert(key_id.clone(), stored, key_has_ephemeral_keys);
+            metrics::record_keygen();
+            Ok(key_id)
+        })
+    }
+
+    #[napi]
+    /// DEBUG/MIGRATION ONLY: the 32-byte Ed25519 seed of a 1-of-1 key imported with
+    /// `import_single_party_key`. Refused unless `allow_export` is set, for any key whose
+    /// group key is not its own public key, i.e. a share of a real threshold key, and for
+    /// keys from `phase1_create_from_private_key`, which do not keep their seed.
+    pub fn export_single_party_secret(key_id: String, allow_export: bool) -> Result<Vec<u8>> {
+        crate::guard::guard("export_single_party_secret", || {
+            if !allow_export {
+                return Err(napi::Error::new(Status::InvalidArg, "Secret export requires allow_export"));
+            }
+            let mut keys = lock(keys_store());
+            let key = keys.get(&key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+            key.require_phase(&key_id, KeyPhase::KeypairConstructed, "export_single_party_secret")?;
+            let shared_keys = key.shared_keys.as_ref()
+                .ok_or_else(|| napi::Error::new(Status::GenericFailure, "Shared keys not set"))?;
+            if shared_keys.y != key.keys.keypair.public_key {
+                return Err(napi::Error::new(Status::InvalidArg, "Key is a share of a multi-party key"));
+            }
+            let seed = key.seed.as_ref()
+                .ok_or_else(|| napi::Error::new(Status::InvalidArg, "Key was not created from a private key"))?;
+            Ok(seed.0.to_vec())
+        })
+    }
+
+    #[napi]
+    /// Version of the byte layout of exported and persisted key material. Blobs carry it in
+    /// their first byte; older versions are migrated on load and newer ones refused.
+    pub fn encoding_version() -> u32 {
+        u32::from(ENCODING_VERSION)
+    }
+
+    const BACKUP_TAG: &[u8] = b"multi-party-eddsa shared keys backup";
+    // encoding version, party index (u16 LE), y, x_i and prefix, followed by a 32-byte
+    // checksum of them
+    const BACKUP_BODY_LEN: usize = 1 + 2 + 3 * 32;
+    // the unversioned layout written before ENCODING_VERSION, read as version 0
+    const LEGACY_BACKUP_BODY_LEN: usize = BACKUP_BODY_LEN - 1;
+
+    pub(crate) fn backup_checksum(body: &[u8]) -> Vec<u8> {
+        Sha512::new().chain(BACKUP_TAG).chain(body).finalize()[..32].to_vec()
+    }
+
+    #[napi]
+    /// Backup encoding of a constructed key for `import_and_verify_shared_keys`. The bytes
+    /// carry the secret share in the clear and must be encrypted at rest.
+    pub fn export_shared_keys(shared_key_id: String) -> Result<Vec<u8>> {
+        crate::guard::guard("export_shared_keys", || {
+            let mut keys = lock(keys_store());
+            let key = keys.get(&shared_key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&shared_key_id))?;
+            key.require_phase(&shared_key_id, KeyPhase::KeypairConstructed, "export_shared_keys")?;
+            let shared_keys = key.shared_keys.as_ref()
+                .ok_or_else(|| napi::Error::new(Status::GenericFailure, "Shared keys missing"))?;
+            Ok(backup_bytes(key.keys.party_index, shared_keys))
+        })
+    }
+
+    pub(crate) fn backup_bytes(party_index: u16, shared_keys: &SharedKeys) -> Vec<u8> {
+        let mut bytes = vec![ENCODING_VERSION];
+        bytes.extend_from_slice(&party_index.to_le_bytes());
+        bytes.extend_from_slice(&shared_keys.y.to_bytes(true));
+        bytes.extend_from_slice(&shared_keys.x_i.to_bytes());
+        bytes.extend_from_slice(&shared_keys.prefix.to_bytes());
+        let checksum = backup_checksum(&bytes);
+        bytes.extend(checksum);
+        bytes
+    }
+
+    #[napi]
+    /// Restore a backup from `export_shared_keys` under a new key id. A backup whose checksum,
+    /// points or scalars do not decode is rejected, as is one from a newer encoding version;
+    /// unversioned backups from before `encoding_version` are still read. When the share alone is the whole key
+    /// (a 1-of-1 or threshold 0 keygen) it also signs and verifies `test_message`, reported
+    /// in `self_test_passed`; a share of a larger key cannot sign alone, so for it no self
+    /// test runs and `self_test_passed` is absent.
+    pub fn import_and_verify_shared_keys(bytes: Vec<u8>, test_message: Vec<u8>) -> Result<SerializableImportResult> {
+        crate::guard::guard("import_and_verify_shared_keys", || {
+            static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+            check_message_size(&test_message)?;
+            let body_len = match bytes.len() {
+                len if len == BACKUP_BODY_LEN + 32 => {
+                    check_encoding_version(bytes[0])?;
+                    BACKUP_BODY_LEN
+                }
+                len if len == LEGACY_BACKUP_BODY_LEN + 32 => LEGACY_BACKUP_BODY_LEN,
+                len => return Err(napi::Error::new(Status::InvalidArg, format!("Backup must be {} bytes, got {}", BACKUP_BODY_LEN + 32, len))),
+            };
+            let (body, checksum) = bytes.split_at(body_len);
+            if backup_checksum(body) != checksum {
+                return Err(napi::Error::new(Status::InvalidArg, "Backup checksum mismatch"));
+            }
+            // version 0 and 1 differ only in the leading version byte
+            let fields = &body[body_len - LEGACY_BACKUP_BODY_LEN..];
+            let party_index = u16::from_le_bytes([fields[0], fields[1]]);
+            let field = |i: usize| fields[2 + 32 * i..2 + 32 * (i + 1)].to_vec();
+            let shared_keys = serializable_to_shared_keys(&SerializableSharedKeys {
+                y: SerializablePoint { bytes: field(0) },
+                x_i: SerializableScalar { bytes: field(1) },
+                prefix: SerializableScalar { bytes: field(2) },
+            })?;
+            let keys = Keys::phase1_create_from_shared_keys(party_index, &shared_keys);
+
+            let self_test_passed = (Point::generator() * &shared_keys.x_i == shared_keys.y).then(|| {
+                let eph_key = EphemeralKey::ephermeral_key_create_from_deterministic_secret_rng(
+                    &keys,
+                    &test_message,
+                    party_index,
+                    &mut *lock(rng_source()),
+                );
+                let eph_shared_keys = EphemeralSharedKeys { R: eph_key.R_i.clone(), r_i: eph_key.r_i };
+                let local_sig = LocalSig::compute(&test_message, &eph_shared_keys, &shared_keys);
+                Signature { R: eph_key.R_i, s: local_sig.gamma_i }
+                    .verify(&test_message, &shared_keys.y)
+                    .is_ok()
+            });
+
+            let shared_key_id = format!("restored_{}", COUNTER.fetch_add(1, Ordering::Relaxed));
+            let public_key = point_to_serializable(&shared_keys.y);
+            let stored = StoredKey::constructed(keys, KeyPhase::KeypairConstructed, Some(shared_keys));
+            lock(keys_store()).insert(shared_key_id.clone(), stored, key_has_ephemeral_keys);
+            Ok(SerializableImportResult {
+                shared_key_id,
+                public_key,
+                self_test_passed,
+            })
+        })
+    }
+
+    #[napi]
+    /// Get the shared keys of a key whose keypair has been constructed
+    pub fn get_shared_keys(key_id: String) -> Result<SerializableSharedKeys> {
+        crate::guard::guard("get_shared_keys", || {
+            let mut keys = lock(keys_store());
+            let key = keys.get(&key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+            key.require_phase(&key_id, KeyPhase::KeypairConstructed, "get_shared_keys")?;
+            let shared_keys = key.shared_keys.as_ref()
+                .ok_or_else(|| napi::Error::new(Status::GenericFailure, "Shared keys missing"))?;
+            Ok(SerializableSharedKeys {
+                y: point_to_serializable(&shared_keys.y),
+                x_i: scalar_to_serializable(&shared_keys.x_i),
+                prefix: scalar_to_serializable(&shared_keys.prefix),
+            })
+        })
+    }
+
+    #[napi]
+    /// Enrollment step 1, run by each of t + 1 helpers to add a party at `new_party_index`
+    /// without changing y: split this key's Lagrange-weighted share into one piece per helper,
+    /// the k-th for `helpers[k]`. Indices are keygen party indices; all helpers pass the same set.
+    pub fn enroll_party_contribution(key_id: String, helpers: Vec<u16>, new_party_index: u16) -> Result<Vec<SerializableScalar>> {
+        crate::guard::guard("enroll_party_contribution", || {
+            let mut keys = lock(keys_store());
+            let key = keys.get(&key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+            key.require_phase(&key_id, KeyPhase::KeypairConstructed, "enroll_party_contribution")?;
+            let shared_keys = key.shared_keys.as_ref()
+                .ok_or_else(|| napi::Error::new(Status::GenericFailure, "Shared keys missing"))?;
+            let pieces = shared_keys
+                .enrollment_pieces_rng(key.keys.party_index, &helpers, new_party_index, &mut *lock(rng_source()))
+                .map_err(|_| napi::Error::new(Status::InvalidArg, "Helpers must be distinct, include this party and exclude the new one"))?;
+            Ok(pieces.iter().map(scalar_to_serializable).collect())
+        })
+    }
+
+    #[napi]
+    /// Enrollment step 2, run by each helper: the sum of the pieces it received from every
+    /// helper, which is all it sends to the new party
+    pub fn enroll_party_forward(pieces: Vec<SerializableScalar>) -> Result<SerializableScalar> {
+        crate::guard::guard("enroll_party_forward", || {
+            let sum = pieces.iter()
+                .try_fold(Scalar::<Ed25519>::zero(), |acc, piece| Ok::<_, napi::Error>(acc + serializable_to_scalar(piece)?))?;
+            Ok(scalar_to_serializable(&sum))
+        })
+    }
+
+    #[napi]
+    /// Enrollment step 3, run by the new party: add up the helpers' forwarded sums into its
+    /// share of `y`, check it against the keygen `vss_schemes` and store it ready to sign
+    /// alongside the existing parties, with a fresh nonce prefix. Returns the new key id.
+    pub fn enroll_party(
+        new_party_index: u16,
+        forwarded_sums: Vec<SerializableScalar>,
+        vss_schemes: Vec<SerializableVerifiableSS>,
+        y: SerializablePoint,
+    ) -> Result<String> {
+        crate::guard::guard("enroll_party", || {
+            static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+            check_non_empty("forwarded_sums", forwarded_sums.len())?;
+            let forwarded_sums = forwarded_sums.iter()
+                .map(serializable_to_scalar)
+                .collect::<Result<Vec<_>>>()?;
+            let vss_vec = vss_schemes.iter()
+                .map(serializable_to_vss)
+                .collect::<Result<Vec<_>>>()?;
+            let y = serializable_to_point(&y)?;
+            if vss_vec.iter().fold(Point::zero(), |acc, vss| acc + &vss.commitments[0]) != y {
+                return Err(napi::Error::new(Status::InvalidArg, "VSS schemes do not commit to y"));
+            }
+            let x_i = thresholdsig::enroll_party_share(new_party_index, &forwarded_sums, &vss_vec)
+                .map_err(|_| napi::Error::new(Status::InvalidArg, "Enrolled share does not match the VSS commitments"))?;
+
+            let mut prefix = [0u8; 64];
+            lock(rng_source()).fill_bytes(&mut prefix);
+            let prefix = Scalar::from_bigint(&BigInt::from_bytes(&prefix));
+            let shared_keys = SharedKeys { y, x_i, prefix };
+            let keys = Keys::phase1_create_from_shared_keys(new_party_index, &shared_keys);
+            let key_id = format!("enrolled_{}", COUNTER.fetch_add(1, Ordering::Relaxed));
+            let stored = StoredKey::constructed(keys, KeyPhase::KeypairConstructed, Some(shared_keys));
+            lock(keys_store()).insert(key_id.clone(), stored, key_has_ephemeral_keys);
+            Ok(key_id)
+        })
+    }
+
+    #[napi]
+    /// Whether two constructed keys come from the same keygen, i.e. share the group key `y`.
+    /// The encodings are compared in constant time.
+    pub fn same_group(shared_key_id_a: String, shared_key_id_b: String) -> Result<bool> {
+        crate::guard::guard("same_group", || {
+            let mut keys = lock(keys_store());
+            let mut group_key = |key_id: &str| -> Result<Vec<u8>> {
+                let key = keys.get(key_id)
+                    .ok_or_else(|| IdKind::Key.not_found(key_id))?;
+                key.require_phase(key_id, KeyPhase::KeypairConstructed, "same_group")?;
+                let shared_keys = key.shared_keys.as_ref()
+                    .ok_or_else(|| napi::Error::new(Status::GenericFailure, "Shared keys missing"))?;
+                Ok(shared_keys.y.to_bytes(true).to_vec())
+            };
+            let y_a = group_key(&shared_key_id_a)?;
+            let y_b = group_key(&shared_key_id_b)?;
+            Ok(constant_time_eq(&y_a, &y_b))
+        })
+    }
+
+    // no early exit, so timing does not leak the length of the common prefix
+    fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+    }
+
+    /// Hash of the challenge H(R||A||M) and of the deterministic nonce. `Sha512` (the
+    /// default) is RFC 8032 Ed25519; the others are for chains running Ed25519 variants
+    /// with a different challenge hash, whose signatures standard verifiers reject. Every
+    /// signer and the verifier must use the same choice.
+    #[napi(string_enum)]
+    #[derive(Debug, PartialEq, Deserialize)]
+    // napi derives Clone and Copy for string enums, but not in the `noop` build
+    #[cfg_attr(feature = "noop", derive(Clone, Copy))]
+    pub enum ChallengeHash {
+        Sha512,
+        Sha256,
+        Keccak256,
+        Blake2b,
+    }
+
+    // evaluate `$body` with `$H` naming the hasher selected by an Option<ChallengeHash>
+    macro_rules! with_challenge_hash {
+        ($hash:expr, $H:ident => $body:expr) => {
+            match $hash.unwrap_or(ChallengeHash::Sha512) {
+                ChallengeHash::Sha512 => { type $H = Sha512; $body }
+                ChallengeHash::Sha256 => { type $H = sha2::Sha256; $body }
+                ChallengeHash::Keccak256 => { type $H = sha3::Keccak256; $body }
+                ChallengeHash::Blake2b => { type $H = crate::blake2b::Blake2b512; $body }
+            }
+        };
+    }
+
+    // the challenge k = H(R || y || M) mod l under the selected hash
+    pub(crate) fn challenge_with_hash(R: &Point<Ed25519>, y: &Point<Ed25519>, message: &[u8], hash: Option<ChallengeHash>) -> Scalar<Ed25519> {
+        with_challenge_hash!(hash, H => Signature::k_with_hash::<H>(R, y, message))
+    }
+
+    // The challenge hasher of a streamed message, of the hash selected by an Option<ChallengeHash>
+    pub(crate) enum ChallengeHasher {
+        Sha512(Sha512),
+        Sha256(sha2::Sha256),
+        Keccak256(sha3::Keccak256),
+        Blake2b(crate::blake2b::Blake2b512),
+    }
+
+    impl ChallengeHasher {
+        // absorbs the encodings R || y, ready for the message
+        fn new(R: &[u8], y: &[u8], hash: Option<ChallengeHash>) -> Self {
+            fn absorbed<H: Digest>(R: &[u8], y: &[u8]) -> H {
+                H::new().chain(R).chain(y)
+            }
+            match hash.unwrap_or(ChallengeHash::Sha512) {
+                ChallengeHash::Sha512 => ChallengeHasher::Sha512(absorbed(R, y)),
+                ChallengeHash::Sha256 => ChallengeHasher::Sha256(absorbed(R, y)),
+                ChallengeHash::Keccak256 => ChallengeHasher::Keccak256(absorbed(R, y)),
+                ChallengeHash::Blake2b => ChallengeHasher::Blake2b(absorbed(R, y)),
+            }
+        }
+
+        fn update(&mut self, chunk: &[u8]) {
+            match self {
+                ChallengeHasher::Sha512(hasher) => hasher.update(chunk),
+                ChallengeHasher::Sha256(hasher) => hasher.update(chunk),
+                ChallengeHasher::Keccak256(hasher) => hasher.update(chunk),
+                ChallengeHasher::Blake2b(hasher) => hasher.update(chunk),
+            }
+        }
+
+        fn digest(self) -> Vec<u8> {
+            match self {
+                ChallengeHasher::Sha512(hasher) => hasher.finalize().to_vec(),
+                ChallengeHasher::Sha256(hasher) => hasher.finalize().to_vec(),
+                ChallengeHasher::Keccak256(hasher) => hasher.finalize().to_vec(),
+                ChallengeHasher::Blake2b(hasher) => hasher.finalize().to_vec(),
+            }
+        }
+
+        fn challenge(self) -> Scalar<Ed25519> {
+            match self {
+                ChallengeHasher::Sha512(hasher) => Signature::k_from_hasher_with_hash(hasher),
+                ChallengeHasher::Sha256(hasher) => Signature::k_from_hasher_with_hash(hasher),
+                ChallengeHasher::Keccak256(hasher) => Signature::k_from_hasher_with_hash(hasher),
+                ChallengeHasher::Blake2b(hasher) => Signature::k_from_hasher_with_hash(hasher),
+            }
+        }
+    }
+
+    #[napi]
+    /// Create ephemeral key from deterministic secret. A non-empty `domain` is mixed into the
+    /// nonce derivation so applications sharing a key derive distinct nonces, and `signers`
+    /// (in any order) binds the nonce to the signer set so another subset signing the same
+    /// message derives a different one. Every signer of a session must pass the same values.
+    pub fn ephemeral_key_create(key_id: String, message: Vec<u8>, index: u16, domain: Option<Vec<u8>>, signers: Option<Vec<u16>>, hash: Option<ChallengeHash>) -> Result<String> {
+        crate::guard::guard("ephemeral_key_create", || {
+            check_message_size(&message)?;
+            let mut keys_store = lock(keys_store());
+            let key = keys_store.get(&key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+            key.require_phase(&key_id, KeyPhase::KeypairConstructed, "ephemeral_key_create")?;
+
+            let ephemeral_key = match key.ephemeral_mode {
+                EphemeralMode::Deterministic => with_challenge_hash!(hash, H => {
+                    EphemeralKey::ephermeral_key_create_from_deterministic_secret_with_hash_rng::<H>(
+                        &key.keys,
+                        &message,
+                        &domain.unwrap_or_default(),
+                        &signers.unwrap_or_default(),
+                        index,
+                        &mut *lock(rng_source()),
+                    )
+                }),
+                EphemeralMode::Random => EphemeralKey::ephemeral_key_create_random_rng(index, &mut *lock(rng_source())),
+            };
+
+            let eph_key_id = ephemeral_key_id_for(key_id, index);
+            lock(ephemeral_keys_store()).insert(eph_key_id.clone(), ephemeral_key, |_| false);
+            lock(ephemeral_messages_store()).remove(&eph_key_id);
+            Ok(eph_key_id)
+        })
+    }
+
+    #[napi]
+    /// Switch how `ephemeral_key_create` derives this key's nonces from now on; the long-lived
+    /// key is untouched. Every signer of a session must be in the same mode.
+    pub fn set_ephemeral_mode(key_id: String, mode: EphemeralMode) -> Result<()> {
+        crate::guard::guard("set_ephemeral_mode", || {
+            let mut keys = lock(keys_store());
+            let key = keys.get_mut(&key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+            key.ephemeral_mode = mode;
+            Ok(())
+        })
+    }
+
+    #[napi]
+    /// The nonce derivation mode of a key, for signers to agree on before a session
+    pub fn get_ephemeral_mode(key_id: String) -> Result<EphemeralMode> {
+        crate::guard::guard("get_ephemeral_mode", || {
+            let mut keys = lock(keys_store());
+            let key = keys.get(&key_id)
+                .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+            Ok(key.ephemeral_mode)
+        })
+    }
+
+    #[napi]
+    /// Idempotent `ephemeral_key_create` within one process: returns the id of the ephemeral
+    /// key already made by this function for the same key, index, message, `domain`, `signers`
+    /// and `hash`, and only creates one when there is none or it was made for other inputs.
+    /// The record is kept in memory only and the nonce mixes in fresh randomness, so after a
+    /// restart this creates a new ephemeral key with a different R; a session interrupted by
+    /// a restart must start over.
+    pub fn ephemeral_key_get_or_create(key_id: String, message: Vec<u8>, index: u16, domain: Option<Vec<u8>>, signers: Option<Vec<u16>>, hash: Option<ChallengeHash>) -> Result<String> {
+        crate::guard::guard("ephemeral_key_get_or_create", || {
+            let eph_key_id = ephemeral_key_id_for(key_id.clone(), index);
+            let mut sorted_signers = signers.clone().unwrap_or_default();
+            sorted_signers.sort_unstable();
+            let domain_bytes = domain.clone().unwrap_or_default();
+            let mut hasher = Sha512::new()
+                .chain((message.len() as u64).to_be_bytes())
+                .chain(&message)
+                .chain((domain_bytes.len() as u64).to_be_bytes())
+                .chain(&domain_bytes)
+                .chain((sorted_signers.len() as u64).to_be_bytes());
+            for signer in &sorted_signers {
+                hasher = hasher.chain(signer.to_be_bytes());
+            }
+            let digest = hasher
+                .chain(format!("{:?}", hash.unwrap_or(ChallengeHash::Sha512)))
+                .finalize()
+                .to_vec();
+            let recorded = lock(ephemeral_messages_store()).get(&eph_key_id) == Some(&digest);
+            if recorded && lock(ephemeral_keys_store()).get(&eph_key_id).is_some() {
+                return Ok(eph_key_id);
+            }
+            let eph_key_id = ephemeral_key_create(key_id, message, index, domain, signers, hash)?;
+            lock(ephemeral_messages_store()).insert(eph_key_id.clone(), digest, |_| false);
+            Ok(eph_key_id)
+        })
+    }
+
+    #[napi]
+    /// `ephemeral_key_create` for a string message, signed as its UTF-8 bytes
+    pub fn ephemeral_key_create_str(key_id: String, message: String, index: u16, domain: Option<Vec<u8>>, signers: Option<Vec<u16>>, hash: Option<ChallengeHash>) -> Result<String> {
+        crate::guard::guard("ephemeral_key_create_str", || {
+            ephemeral_key_create(key_id, message.into_bytes(), index, domain, signers, hash)
+        })
+    }
+
+    #[napi]
+    /// Get ephemeral R point
+    pub fn get_ephemeral_R(eph_key_id: String) -> Result<SerializablePoint> {
+        crate::guard::guard("get_ephemeral_R", || {
+            let mut eph_keys_store = lock(ephemeral_keys_store());
+            let eph_key = eph_keys_store.get(&eph_key_id)
+                .ok_or_else(|| IdKind::EphemeralKey.not_found(&eph_key_id))?;
+            Ok(point_to_serializable(&eph_key.R_i))
+        })
+    }
+
+    #[napi]
+    /// Ephemeral Phase 1 broadcast
+    pub fn ephemeral_phase1_broadcast(eph_key_id: String) -> Result<serde_json::Value> {
+        crate::guard::guard("ephemeral_phase1_broadcast", || {
+            let mut eph_keys_store = lock(ephemeral_keys_store());
+            let eph_key = eph_keys_store.get_mut(&eph_key_id)
+                .ok_or_else(|| IdKind::EphemeralKey.not_found(&eph_key_id))?;
+
+            let (bcm1, blind_factor) = eph_key.phase1_broadcast_rng(&mut *lock(rng_source()));
+        
+            to_json("ephemeral_phase1_broadcast", &BroadcastResult {
+                commitment: bigint_to_serializable(&bcm1.com),
+                blind_factor: Some(bigint_to_serializable(&blind_factor)),
+            })
+        })
+    }
+
+    #[napi]
+    /// `ephemeral_phase1_broadcast` with the blind factor zero-padded to `blind_factor_length()`
+    /// bytes
+    pub fn ephemeral_phase1_broadcast_fixed(eph_key_id: String) -> Result<serde_json::Value> {
+        crate::guard::guard("ephemeral_phase1_broadcast_fixed", || fix_blind_factor(ephemeral_phase1_broadcast(eph_key_id)?))
+    }
+
+    #[napi]
+    #[allow(clippy::too_many_arguments)]
+    /// Ephemeral Phase 1 verify and Phase 2 distribute; `share_envelopes` pairs each nonce share with its signer.
+    /// `strict_subgroup` reports an R with a small-order component as `NotInSubgroup`.
+    pub fn ephemeral_phase1_verify_com_phase2_distribute(
+        eph_key_id: String,
+        threshold: u16,
+        share_count: u16,
+        blind_factors: Vec<SerializableBigInt>,
+        R_points: Vec<SerializablePoint>,
+        commitments: Vec<SerializableBigInt>,
+        parties: Vec<u16>,
+        strict_subgroup: Option<bool>,
+    ) -> Result<serde_json::Value> {
+        crate::guard::guard("ephemeral_phase1_verify_com_phase2_distribute", || {
+            let mut eph_keys_store = lock(ephemeral_keys_store());
+            let eph_key = eph_keys_store.get_mut(&eph_key_id)
+                .ok_or_else(|| IdKind::EphemeralKey.not_found(&eph_key_id))?;
+            check_sharing_params(threshold, share_count, &parties)?;
+            if strict_subgroup.unwrap_or(false) {
+                check_subgroup("R", &R_points)?;
+            }
+
+            let params = Parameters { threshold, share_count };
+        
+            let blind_vec: Vec<BigInt> = blind_factors.iter().map(serializable_to_bigint).collect();
+            let R_vec: Vec<Point<Ed25519>> = R_points.iter()
+                .map(serializable_to_point)
+                .collect::<Result<Vec<_>>>()?;
+        
+            let bc1_vec: Vec<thresholdsig::KeyGenBroadcastMessage1> = commitments.iter()
+                .map(|com| thresholdsig::KeyGenBroadcastMessage1 { com: serializable_to_bigint(com) })
+                .collect();
+
+            let (vss, secret_shares) = eph_key.phase1_verify_com_phase2_distribute_rng(
+                &params,
+                &blind_vec,
+                &R_vec,
+                &bc1_vec,
+                &parties,
+                &mut *lock(rng_source()),
+            ).map_err(|e| napi::Error::new(Status::GenericFailure, format!("{:?}", e)))?;
+
+            let vss_serializable = SerializableVerifiableSS {
+                threshold: vss.parameters.threshold,
+                share_count: vss.parameters.share_count,
+                commitments: vss.commitments.iter().map(point_to_serializable).collect(),
+            };
+
+            let secret_shares_serializable: Vec<SerializableScalar> = secret_shares.iter()
+                .map(scalar_to_serializable)
+                .collect();
+
+            // share i is for the signer at parties[i]
+            let share_envelopes: Vec<SerializableSecretShareEnvelope> = parties.iter()
+                .zip(secret_shares_serializable.iter())
+                .map(|(&recipient_index, share)| SerializableSecretShareEnvelope {
+                    recipient_index,
+                    share: share.clone(),
+                })
+                .collect();
+
+            to_json("phase2_distribution", &DistributeResult {
+                vss: vss_serializable,
+                secret_shares: secret_shares_serializable,
+                share_envelopes,
+            })
+        })
+    }
+
+    #[napi]
+    #[allow(clippy::too_many_arguments)]
+    /// Ephemeral Phase 2 verify and construct keypair; `strict_shares` rejects non-canonical secret shares
+    pub fn ephemeral_phase2_verify_vss_construct_keypair(
+        eph_key_id: String,
+        threshold: u16,
+        share_count: u16,
+        R_points: Vec<SerializablePoint>,
+        secret_shares: Vec<SerializableScalar>,
+        vss_schemes: Vec<SerializableVerifiableSS>,
+        index: u16,
+        strict_shares: Option<bool>,
+    ) -> Result<SerializableEphemeralSharedKeys> {
+        crate::guard::guard("ephemeral_phase2_verify_vss_construct_keypair", || {
+            check_share_count(usize::from(share_count))?;
+            if strict_shares.unwrap_or(false) {
+                check_canonical_shares(&secret_shares)?;
+            }
+
+            let mut eph_keys_store = lock(ephemeral_keys_store());
+            let eph_key = eph_keys_store.get_mut(&eph_key_id)
+                .ok_or_else(|| IdKind::EphemeralKey.not_found(&eph_key_id))?;
+
+            let params = Parameters { threshold, share_count };
+        
+            let R_vec: Vec<Point<Ed25519>> = R_points.iter()
+                .map(serializable_to_point)
+                .collect::<Result<Vec<_>>>()?;
+        
+            let secret_shares_vec: Vec<Scalar<Ed25519>> = secret_shares.iter()
+                .map(serializable_to_scalar)
+                .collect::<Result<Vec<_>>>()?;
+
+            let vss_scheme_vec: Vec<VerifiableSS<Ed25519>> = vss_schemes.iter()
+                .map(serializable_to_vss)
+                .collect::<Result<Vec<_>>>()?;
+
+            let ephemeral_shared_keys = eph_key.phase2_verify_vss_construct_keypair(
+                &params,
+                &R_vec,
+                &secret_shares_vec,
+                &vss_scheme_vec,
+                index,
+            ).map_err(|e| napi::Error::new(Status::GenericFailure, format!("{:?}", e)))?;
+
+            Ok(SerializableEphemeralSharedKeys {
+                R: point_to_serializable(&ephemeral_shared_keys.R),
+                r_i: scalar_to_serializable(&ephemeral_shared_keys.r_i),
+            })
+        })
+    }
+
+    #[napi]
+    /// Compute local signature; with `expected_R`, first check the ephemeral key signs under
+    /// the R the coordinator will aggregate with
     pub fn compute_local_sig(
         message: Vec<u8>,
         ephemeral_shared_keys: SerializableEphemeralSharedKeys,
         shared_keys: SerializableSharedKeys,
+        expected_R: Option<SerializablePoint>,
+        hash: Option<ChallengeHash>,
     ) -> Result<SerializableLocalSig> {
+        crate::guard::guard("compute_local_sig", || {
+            compute_local_sig_bytes(&message, ephemeral_shared_keys, shared_keys, expected_R, hash)
+        })
+    }
+
+    #[napi]
+    /// `compute_local_sig` reading the message straight out of a Node Buffer, without the
+    /// copy napi makes for `Vec<u8>`
+    pub fn compute_local_sig_buffer(
+        message: Buffer,
+        ephemeral_shared_keys: SerializableEphemeralSharedKeys,
+        shared_keys: SerializableSharedKeys,
+        expected_R: Option<SerializablePoint>,
+        hash: Option<ChallengeHash>,
+    ) -> Result<SerializableLocalSig> {
+        crate::guard::guard("compute_local_sig_buffer", || {
+            compute_local_sig_bytes(&message, ephemeral_shared_keys, shared_keys, expected_R, hash)
+        })
+    }
+
+    // Shared by compute_local_sig and compute_local_sig_buffer
+    pub(crate) fn compute_local_sig_bytes(
+        message: &[u8],
+        ephemeral_shared_keys: SerializableEphemeralSharedKeys,
+        shared_keys: SerializableSharedKeys,
+        expected_R: Option<SerializablePoint>,
+        hash: Option<ChallengeHash>,
+    ) -> Result<SerializableLocalSig> {
+        check_message_size(message)?;
         let local_eph_key = EphemeralSharedKeys {
             R: serializable_to_point(&ephemeral_shared_keys.R)?,
             r_i: serializable_to_scalar(&ephemeral_shared_keys.r_i)?,
         };
+        if let Some(expected_R) = expected_R {
+            check_R_consistent("ephemeral shared key R", &local_eph_key.R, &serializable_to_point(&expected_R)?)?;
+        }
+
+        let local_private_key = serializable_to_shared_keys(&shared_keys)?;
+
+        let local_sig = with_challenge_hash!(hash, H => LocalSig::compute_with_hash::<H>(message, &local_eph_key, &local_private_key));
+
+        Ok(SerializableLocalSig {
+            gamma_i: scalar_to_serializable(&local_sig.gamma_i),
+            k: scalar_to_serializable(&local_sig.k),
+        })
+    }
+
+    #[napi]
+    /// `compute_local_sig` for a string message, signed as its UTF-8 bytes
+    pub fn compute_local_sig_str(
+        message: String,
+        ephemeral_shared_keys: SerializableEphemeralSharedKeys,
+        shared_keys: SerializableSharedKeys,
+        expected_R: Option<SerializablePoint>,
+        hash: Option<ChallengeHash>,
+    ) -> Result<SerializableLocalSig> {
+        crate::guard::guard("compute_local_sig_str", || {
+            compute_local_sig(message.into_bytes(), ephemeral_shared_keys, shared_keys, expected_R, hash)
+        })
+    }
+
+    #[napi]
+    /// Compute local signatures for a batch of messages under one key; the i-th message
+    /// is signed with the i-th ephemeral key, under the challenge hash `hash` as in
+    /// `compute_local_sig`
+    pub fn compute_local_sigs_batch(
+        messages: Vec<Vec<u8>>,
+        ephemeral_shared_keys: Vec<SerializableEphemeralSharedKeys>,
+        shared_keys: SerializableSharedKeys,
+        hash: Option<ChallengeHash>,
+    ) -> Result<Vec<SerializableLocalSig>> {
+        crate::guard::guard("compute_local_sigs_batch", || {
+            if messages.len() != ephemeral_shared_keys.len() {
+                return Err(napi::Error::new(
+                    Status::InvalidArg,
+                    format!("Got {} messages but {} ephemeral keys", messages.len(), ephemeral_shared_keys.len()),
+                ));
+            }
+            for message in &messages {
+                check_message_size(message)?;
+            }
+
+            let local_private_key = serializable_to_shared_keys(&shared_keys)?;
+
+            messages.iter()
+                .zip(ephemeral_shared_keys.iter())
+                .map(|(message, ephemeral_shared_keys)| {
+                    let local_eph_key = EphemeralSharedKeys {
+                        R: serializable_to_point(&ephemeral_shared_keys.R)?,
+                        r_i: serializable_to_scalar(&ephemeral_shared_keys.r_i)?,
+                    };
+                    let local_sig = with_challenge_hash!(hash, H => LocalSig::compute_with_hash::<H>(message, &local_eph_key, &local_private_key));
+                    Ok(SerializableLocalSig {
+                        gamma_i: scalar_to_serializable(&local_sig.gamma_i),
+                        k: scalar_to_serializable(&local_sig.k),
+                    })
+                })
+                .collect()
+        })
+    }
+
+    #[napi]
+    /// Start a streaming hasher for the signing challenge H(R || A || M).
+    /// Ed25519 absorbs R and the public key before the message, so the stream can
+    /// only start once the aggregate R of the signing round is known. `hash` is the
+    /// challenge hash of the session, as passed to `ephemeral_key_create`.
+    pub fn sig_hasher_new(R: SerializablePoint, public_key: SerializablePoint, hash: Option<ChallengeHash>) -> Result<String> {
+        crate::guard::guard("sig_hasher_new", || {
+            let R = encoding_of(&serializable_to_point(&R)?);
+            let y = encoding_of(&serializable_to_point(&public_key)?);
+            Ok(insert_sig_hasher(R, y, false, hash))
+        })
+    }
+
+    #[napi]
+    /// Feed the next message chunk into a streaming hasher
+    pub fn sig_hasher_update(hasher_id: String, chunk: Vec<u8>) -> Result<()> {
+        crate::guard::guard("sig_hasher_update", || {
+            let mut hashers = lock(sig_hashers_store());
+            let sig_hasher = hashers.get_mut(&hasher_id)
+                .ok_or_else(|| IdKind::Hasher.not_found(&hasher_id))?;
+            sig_hasher.hasher.update(&chunk);
+            Ok(())
+        })
+    }
+
+    #[napi]
+    /// Compute local signature over a streamed message, consuming the hasher
+    pub fn compute_local_sig_streamed(
+        hasher_id: String,
+        ephemeral_shared_keys: SerializableEphemeralSharedKeys,
+        shared_keys: SerializableSharedKeys,
+    ) -> Result<SerializableLocalSig> {
+        crate::guard::guard("compute_local_sig_streamed", || {
+            let local_eph_key = EphemeralSharedKeys {
+                R: serializable_to_point(&ephemeral_shared_keys.R)?,
+                r_i: serializable_to_scalar(&ephemeral_shared_keys.r_i)?,
+            };
+
+            let local_private_key = serializable_to_shared_keys(&shared_keys)?;
+
+            let sig_hasher = lock(sig_hashers_store()).remove(&hasher_id)
+                .ok_or_else(|| IdKind::Hasher.not_found(&hasher_id))?;
+            if sig_hasher.R != encoding_of(&local_eph_key.R) || sig_hasher.y != encoding_of(&local_private_key.y) {
+                return Err(napi::Error::new(Status::InvalidArg, "Hasher was started for a different R or public key"));
+            }
+
+            let k = sig_hasher.hasher.challenge();
+            let local_sig = LocalSig::compute_with_challenge(k, &local_eph_key, &local_private_key);
+
+            Ok(SerializableLocalSig {
+                gamma_i: scalar_to_serializable(&local_sig.gamma_i),
+                k: scalar_to_serializable(&local_sig.k),
+            })
+        })
+    }
+
+    #[napi]
+    /// Start a streaming hasher to verify `signature` over a message too large to hold at
+    /// once. Ed25519 hashes R and the public key before the message, so both are needed up
+    /// front; `verify_signature_streamed` checks it is given the same ones. `cofactored` and
+    /// `hash` select the equation and challenge hash as they do for `verify_signature`.
+    pub fn verify_hasher_new(
+        signature: SerializableSignature,
+        public_key: SerializablePoint,
+        cofactored: Option<bool>,
+        hash: Option<ChallengeHash>,
+    ) -> Result<String> {
+        crate::guard::guard("verify_hasher_new", || {
+            if !cofactored.unwrap_or(false) {
+                return sig_hasher_new(signature.R, public_key, hash);
+            }
+            let (R, _) = dalek_point(&signature.R, "R")?;
+            let (y, _) = dalek_point(&public_key, "public key")?;
+            Ok(insert_sig_hasher(R, y, true, hash))
+        })
+    }
 
-        let local_private_key = SharedKeys {
-            y: serializable_to_point(&shared_keys.y)?,
-            x_i: serializable_to_scalar(&shared_keys.x_i)?,
-            prefix: serializable_to_scalar(&shared_keys.prefix)?,
-        };
+    // the canonical 32-byte encoding of a curv point
+    fn encoding_of(point: &Point<Ed25519>) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&point.to_bytes(true));
+        bytes
+    }
 
-        let local_sig = LocalSig::compute(&message, &local_eph_key, &local_private_key);
+    fn insert_sig_hasher(R: [u8; 32], y: [u8; 32], cofactored: bool, hash: Option<ChallengeHash>) -> String {
+        let hasher = ChallengeHasher::new(&R, &y, hash);
+        let hasher_id = next_hasher_id();
+        lock(sig_hashers_store()).insert(hasher_id.clone(), SigHasher { R, y, cofactored, hasher }, |_| false);
+        hasher_id
+    }
 
-        Ok(SerializableLocalSig {
-            gamma_i: scalar_to_serializable(&local_sig.gamma_i),
-            k: scalar_to_serializable(&local_sig.k),
+    #[napi]
+    /// Feed the next message chunk into a streaming verify hasher
+    pub fn verify_hasher_update(hasher_id: String, chunk: Vec<u8>) -> Result<()> {
+        crate::guard::guard("verify_hasher_update", || sig_hasher_update(hasher_id, chunk))
+    }
+
+    #[napi]
+    /// `verify_signature` over the message streamed into the hasher, consuming the hasher
+    pub fn verify_signature_streamed(
+        hasher_id: String,
+        signature: SerializableSignature,
+        public_key: SerializablePoint,
+    ) -> Result<bool> {
+        crate::guard::guard("verify_signature_streamed", || {
+            let sig_hasher = lock(sig_hashers_store()).remove(&hasher_id)
+                .ok_or_else(|| IdKind::Hasher.not_found(&hasher_id))?;
+            let mismatch = || napi::Error::new(Status::InvalidArg, "Hasher was started for a different R or public key");
+
+            if sig_hasher.cofactored {
+                let (R_bytes, R) = dalek_point(&signature.R, "R")?;
+                let (y_bytes, y) = dalek_point(&public_key, "public key")?;
+                if sig_hasher.R != R_bytes || sig_hasher.y != y_bytes {
+                    return Err(mismatch());
+                }
+                metrics::record_verification();
+                return verify_digest_with_rules(R, y, &signature.s, &sig_hasher.hasher.digest(), COFACTORED);
+            }
+
+            let sig = Signature {
+                R: serializable_to_point(&signature.R)?,
+                s: serializable_to_scalar(&signature.s)?,
+            };
+            let y = serializable_to_point(&public_key)?;
+            if sig_hasher.R != encoding_of(&sig.R) || sig_hasher.y != encoding_of(&y) {
+                return Err(mismatch());
+            }
+
+            let k = sig_hasher.hasher.challenge();
+            metrics::record_verification();
+            Ok(&sig.s * Point::generator() == sig.R + y * k)
         })
     }
 
     #[napi]
-    /// Verify local signatures
-    pub fn verify_local_sigs(
-        local_sigs: Vec<SerializableLocalSig>,
+    /// Encode a local signature as 64 bytes: gamma_i || k
+    pub fn local_sig_to_bytes(local_sig: SerializableLocalSig) -> Result<Vec<u8>> {
+        crate::guard::guard("local_sig_to_bytes", || {
+            let gamma_i = serializable_to_scalar(&local_sig.gamma_i)?;
+            let k = serializable_to_scalar(&local_sig.k)?;
+            let mut bytes = gamma_i.to_bytes().to_vec();
+            bytes.extend_from_slice(&k.to_bytes());
+            Ok(bytes)
+        })
+    }
+
+    #[napi]
+    /// Decode a 64-byte gamma_i || k local signature
+    pub fn local_sig_from_bytes(bytes: Vec<u8>) -> Result<SerializableLocalSig> {
+        crate::guard::guard("local_sig_from_bytes", || {
+            if bytes.len() != 64 {
+                return Err(napi::Error::new(Status::InvalidArg, format!("Local signature must be 64 bytes, got {}", bytes.len())));
+            }
+            let local_sig = SerializableLocalSig {
+                gamma_i: SerializableScalar { bytes: bytes[..32].to_vec() },
+                k: SerializableScalar { bytes: bytes[32..].to_vec() },
+            };
+            serializable_to_scalar(&local_sig.gamma_i)?;
+            serializable_to_scalar(&local_sig.k)?;
+            Ok(local_sig)
+        })
+    }
+
+    #[napi]
+    /// Number of commitments a VSS scheme for `threshold` carries, threshold + 1. VSS
+    /// inputs with any other count are rejected with `MalformedVss`.
+    pub fn expected_commitment_count(threshold: u16) -> Result<u16> {
+        crate::guard::guard("expected_commitment_count", || {
+            u16::try_from(expected_commitments(threshold))
+                .map_err(|_| napi::Error::new(Status::InvalidArg, format!("Threshold {} leaves no room for a share count", threshold)))
+        })
+    }
+
+    #[napi]
+    /// Encode a VSS scheme as threshold (u16 LE) || share_count (u16 LE) || 32-byte commitments
+    pub fn vss_to_bytes(vss: SerializableVerifiableSS) -> Result<Vec<u8>> {
+        crate::guard::guard("vss_to_bytes", || {
+            let vss = serializable_to_vss(&vss)?;
+            let mut bytes = vss.parameters.threshold.to_le_bytes().to_vec();
+            bytes.extend_from_slice(&vss.parameters.share_count.to_le_bytes());
+            for commitment in &vss.commitments {
+                bytes.extend_from_slice(&commitment.to_bytes(true));
+            }
+            Ok(bytes)
+        })
+    }
+
+    #[napi]
+    /// Decode a VSS scheme encoded by `vss_to_bytes`
+    pub fn vss_from_bytes(bytes: Vec<u8>) -> Result<SerializableVerifiableSS> {
+        crate::guard::guard("vss_from_bytes", || {
+            if bytes.len() < 4 || !(bytes.len() - 4).is_multiple_of(32) {
+                return Err(napi::Error::new(Status::InvalidArg, format!("VSS encoding must be 4 + 32n bytes, got {}", bytes.len())));
+            }
+            let vss = SerializableVerifiableSS {
+                threshold: u16::from_le_bytes([bytes[0], bytes[1]]),
+                share_count: u16::from_le_bytes([bytes[2], bytes[3]]),
+                commitments: bytes[4..].chunks(32)
+                    .map(|chunk| SerializablePoint { bytes: chunk.to_vec() })
+                    .collect(),
+            };
+            serializable_to_vss(&vss)?;
+            Ok(vss)
+        })
+    }
+
+    #[napi]
+    /// Bytes-in, bytes-out `generate_signature`: the summed VSS as encoded by `vss_to_bytes`,
+    /// 64-byte local signatures and a 32-byte R. Returns the 64-byte signature R || s.
+    pub fn generate_signature_from_bytes(
+        vss_sum_bytes: Vec<u8>,
+        local_sig_blobs: Vec<Vec<u8>>,
         parties_index: Vec<u16>,
-        vss_private_keys: Vec<SerializableVerifiableSS>,
-        vss_ephemeral_keys: Vec<SerializableVerifiableSS>,
-    ) -> Result<SerializableVerifiableSS> {
-        let local_sig_vec: Vec<LocalSig> = local_sigs.iter()
-            .map(|ls| LocalSig {
-                gamma_i: serializable_to_scalar(&ls.gamma_i).unwrap(),
-                k: serializable_to_scalar(&ls.k).unwrap(),
-            })
-            .collect();
+        R_bytes: Vec<u8>,
+    ) -> Result<Vec<u8>> {
+        crate::guard::guard("generate_signature_from_bytes", || {
+            if R_bytes.len() != 32 {
+                return Err(napi::Error::new(Status::InvalidArg, format!("R must be 32 bytes, got {}", R_bytes.len())));
+            }
+            let local_sigs = local_sig_blobs.into_iter()
+                .map(local_sig_from_bytes)
+                .collect::<Result<Vec<_>>>()?;
+            let signature = generate_signature(
+                vss_from_bytes(vss_sum_bytes)?,
+                local_sigs,
+                parties_index,
+                SerializablePoint { bytes: R_bytes },
+                None,
+                None,
+                None,
+                None,
+            )?;
+            let mut bytes = signature.R.bytes;
+            bytes.extend(signature.s.bytes);
+            Ok(bytes)
+        })
+    }
+
+    fn deserialize_local_sigs(local_sigs: &[SerializableLocalSig]) -> Result<Vec<LocalSig>> {
+        local_sigs.iter()
+            .map(|ls| Ok(LocalSig {
+                gamma_i: serializable_to_scalar(&ls.gamma_i)?,
+                k: serializable_to_scalar(&ls.k)?,
+            }))
+            .collect()
+    }
+
+    // Shared by verify_local_sigs and aggregate_signature; returns the summed VSS
+    fn verify_local_sigs_internal(
+        local_sig_vec: &[LocalSig],
+        parties_index: &[u16],
+        vss_private_keys: &[SerializableVerifiableSS],
+        vss_ephemeral_keys: &[SerializableVerifiableSS],
+    ) -> Result<VerifiableSS<Ed25519>> {
+        check_non_empty("local_sigs", local_sig_vec.len())?;
+        check_non_empty("parties_index", parties_index.len())?;
+        check_non_empty("vss_private_keys", vss_private_keys.len())?;
+        check_non_empty("vss_ephemeral_keys", vss_ephemeral_keys.len())?;
+        check_participants(parties_index, vss_private_keys[0].threshold)?;
 
-        // Reconstruct VSS schemes - need to use the actual parties array
-        // Key generation VSS uses the original share_count (total parties)
-        let keygen_parties: Vec<u16> = (1..=vss_private_keys[0].share_count).collect();
         let vss_private_keys_vec: Vec<VerifiableSS<Ed25519>> = vss_private_keys.iter()
-            .map(|vss| {
-                let commitments: Vec<Point<Ed25519>> = vss.commitments.iter()
-                    .map(|c| serializable_to_point(c))
-                    .collect::<Result<Vec<_>>>()?;
-                // Create a temporary VerifiableSS to get the parameters structure
-                // Use the key generation parties array to match the original VSS structure
-                let (temp_vss, _) = VerifiableSS::share_at_indices(
-                    vss.threshold,
-                    vss.share_count,
-                    &Scalar::<Ed25519>::zero(), // dummy scalar, we only need the structure
-                    &keygen_parties,
-                );
-                Ok(VerifiableSS {
-                    parameters: temp_vss.parameters,
-                    commitments,
-                })
-            })
+            .map(serializable_to_vss)
             .collect::<Result<Vec<_>>>()?;
-
-        // Ephemeral VSS uses the actual number of signing parties
-        let eph_parties: Vec<u16> = if vss_ephemeral_keys.len() > 0 {
-            (1..=vss_ephemeral_keys[0].share_count).collect()
-        } else {
-            keygen_parties.clone()
-        };
         let vss_ephemeral_keys_vec: Vec<VerifiableSS<Ed25519>> = vss_ephemeral_keys.iter()
-            .map(|vss| {
-                let commitments: Vec<Point<Ed25519>> = vss.commitments.iter()
-                    .map(|c| serializable_to_point(c))
-                    .collect::<Result<Vec<_>>>()?;
-                // Create a temporary VerifiableSS to get the parameters structure
-                // Use the ephemeral parties array to match the ephemeral VSS structure
-                let (temp_vss, _) = VerifiableSS::share_at_indices(
-                    vss.threshold,
-                    vss.share_count,
-                    &Scalar::<Ed25519>::zero(), // dummy scalar, we only need the structure
-                    &eph_parties,
-                );
-                Ok(VerifiableSS {
-                    parameters: temp_vss.parameters,
-                    commitments,
-                })
-            })
+            .map(serializable_to_vss)
             .collect::<Result<Vec<_>>>()?;
 
-        let vss_sum = LocalSig::verify_local_sigs(
-            &local_sig_vec,
-            &parties_index,
+        LocalSig::verify_local_sigs(
+            local_sig_vec,
+            parties_index,
             &vss_private_keys_vec,
             &vss_ephemeral_keys_vec,
-        ).map_err(|e| napi::Error::new(Status::GenericFailure, format!("{:?}", e)))?;
+        ).map_err(|e| napi::Error::new(Status::GenericFailure, format!("{:?}", e)))
+    }
+
+    // Shared by generate_signature and aggregate_signature
+    fn generate_signature_internal(
+        vss_sum: &VerifiableSS<Ed25519>,
+        local_sig_vec: &[LocalSig],
+        parties_index: &[u16],
+        R: Point<Ed25519>,
+    ) -> Result<Signature> {
+        check_participants(parties_index, vss_sum.parameters.threshold)?;
+
+        Ok(thresholdsig::generate(
+            vss_sum,
+            local_sig_vec,
+            parties_index,
+            R,
+        ))
+    }
+
+    #[napi]
+    /// Compute the signing challenge k = H(R || A || M) mod l exactly as the protocol derives it
+    /// (RFC 8032 5.1.6), under the challenge hash `hash` the parties signed with
+    pub fn compute_challenge(
+        R: SerializablePoint,
+        public_key: SerializablePoint,
+        message: Vec<u8>,
+        hash: Option<ChallengeHash>,
+    ) -> Result<SerializableScalar> {
+        crate::guard::guard("compute_challenge", || {
+            check_message_size(&message)?;
+            let R = serializable_to_point(&R)?;
+            let y = serializable_to_point(&public_key)?;
+            let k = challenge_with_hash(&R, &y, &message, hash);
+            Ok(scalar_to_serializable(&k))
+        })
+    }
+
+    #[napi]
+    /// Verify local signatures
+    pub fn verify_local_sigs(
+        local_sigs: Vec<SerializableLocalSig>,
+        parties_index: Vec<u16>,
+        vss_private_keys: Vec<SerializableVerifiableSS>,
+        vss_ephemeral_keys: Vec<SerializableVerifiableSS>,
+    ) -> Result<SerializableVerifiableSS> {
+        crate::guard::guard("verify_local_sigs", || {
+            let local_sig_vec = deserialize_local_sigs(&local_sigs)?;
+
+            let vss_sum = verify_local_sigs_internal(
+                &local_sig_vec,
+                &parties_index,
+                &vss_private_keys,
+                &vss_ephemeral_keys,
+            )?;
+
+            Ok(vss_to_serializable(&vss_sum))
+        })
+    }
 
-        Ok(SerializableVerifiableSS {
-            threshold: vss_sum.parameters.threshold,
-            share_count: vss_sum.parameters.share_count,
-            commitments: vss_sum.commitments.iter().map(point_to_serializable).collect(),
+    #[napi]
+    /// Check the binding equation of one local signature at a single signer:
+    /// gamma_i*G = sum_j E_j(i + 1) + k * sum_j P_j(i + 1), where E_j and P_j are the ephemeral
+    /// and keygen VSS schemes and `party_index` is 0-based. False pins the signature on the
+    /// party it was attributed to, even when the batch check in `verify_local_sigs` cannot say who.
+    pub fn local_sig_binds_party(
+        local_sig: SerializableLocalSig,
+        party_index: u16,
+        vss_private_keys: Vec<SerializableVerifiableSS>,
+        vss_ephemeral_keys: Vec<SerializableVerifiableSS>,
+    ) -> Result<bool> {
+        crate::guard::guard("local_sig_binds_party", || {
+            check_share_count(usize::from(party_index) + 1)?;
+            check_non_empty("vss_private_keys", vss_private_keys.len())?;
+            check_non_empty("vss_ephemeral_keys", vss_ephemeral_keys.len())?;
+            let local_sig = deserialize_local_sigs(std::slice::from_ref(&local_sig))?.remove(0);
+            let point = party_index + 1;
+            let commitment_sum = |schemes: &[SerializableVerifiableSS]| {
+                schemes.iter()
+                    .map(|vss| serializable_to_vss(vss).map(|vss| vss.get_point_commitment(point)))
+                    .try_fold(Point::<Ed25519>::zero(), |acc, p| p.map(|p| acc + p))
+            };
+            let expected = commitment_sum(&vss_ephemeral_keys)? + commitment_sum(&vss_private_keys)? * &local_sig.k;
+            Ok(Point::generator() * &local_sig.gamma_i == expected)
         })
     }
 
     #[napi]
-    /// Generate final signature
+    #[allow(clippy::too_many_arguments)]
+    /// Generate final signature, optionally verifying it against the group public key before returning.
+    /// When `public_key` is given, `R` is checked against the zeroth VSS commitment (R + k*Y) first.
+    /// `hash` is the challenge hash the local signatures were computed with, for the self-verification.
     pub fn generate_signature(
         vss_sum_local_sigs: SerializableVerifiableSS,
         local_sigs: Vec<SerializableLocalSig>,
         parties_index: Vec<u16>,
         R: SerializablePoint,
+        verify_after_generate: Option<bool>,
+        public_key: Option<SerializablePoint>,
+        message: Option<Vec<u8>>,
+        hash: Option<ChallengeHash>,
     ) -> Result<SerializableSignature> {
-        // Reconstruct VSS sum - need to use the actual parties array
-        let parties: Vec<u16> = (1..=vss_sum_local_sigs.share_count).collect();
-        let vss_sum: VerifiableSS<Ed25519> = {
-            let commitments: Vec<Point<Ed25519>> = vss_sum_local_sigs.commitments.iter()
-                .map(|c| serializable_to_point(c))
-                .collect::<Result<Vec<_>>>()?;
-            // Create a temporary VerifiableSS to get the parameters structure
-            // Use the actual parties array to match the original VSS structure
-            let (temp_vss, _) = VerifiableSS::share_at_indices(
-                vss_sum_local_sigs.threshold,
-                vss_sum_local_sigs.share_count,
-                &Scalar::<Ed25519>::zero(), // dummy scalar, we only need the structure
-                &parties,
-            );
-            VerifiableSS {
-                parameters: temp_vss.parameters,
-                commitments,
+        crate::guard::guard("generate_signature", || {
+            if let Some(message) = &message {
+                check_message_size(message)?;
             }
-        };
+            let vss_sum = serializable_to_vss(&vss_sum_local_sigs)?;
+            let local_sig_vec = deserialize_local_sigs(&local_sigs)?;
+            let R_point = serializable_to_point(&R)?;
 
-        let local_sig_vec: Vec<LocalSig> = local_sigs.iter()
-            .map(|ls| LocalSig {
-                gamma_i: serializable_to_scalar(&ls.gamma_i).unwrap(),
-                k: serializable_to_scalar(&ls.k).unwrap(),
+            if let (Some(public_key), Some(first)) = (&public_key, local_sig_vec.first()) {
+                let y = serializable_to_point(public_key)?;
+                check_R_consistent("aggregated commitment at index zero", &(&vss_sum.commitments[0] - &y * &first.k), &R_point)?;
+            }
+
+            let signature = generate_signature_internal(
+                &vss_sum,
+                &local_sig_vec,
+                &parties_index,
+                R_point,
+            )?;
+
+            if verify_after_generate.unwrap_or(false) {
+                let public_key = public_key.ok_or_else(|| napi::Error::new(Status::InvalidArg, "public_key is required when verify_after_generate is set"))?;
+                let message = message.ok_or_else(|| napi::Error::new(Status::InvalidArg, "message is required when verify_after_generate is set"))?;
+                let pk = serializable_to_point(&public_key)?;
+                with_challenge_hash!(hash, H => signature.verify_with_hash::<H>(&message, &pk))
+                    .map_err(|_| napi::Error::new(Status::GenericFailure, "Generated signature failed self-verification"))?;
+            }
+
+            metrics::record_signature();
+            Ok(SerializableSignature {
+                R: point_to_serializable(&signature.R),
+                s: scalar_to_serializable(&signature.s),
+            })
+        })
+    }
+
+    // Split indexed local sigs into the positional local_sigs / parties_index pair, ordered by party
+    fn unzip_indexed_local_sigs(mut local_sigs: Vec<SerializableIndexedLocalSig>) -> (Vec<SerializableLocalSig>, Vec<u16>) {
+        local_sigs.sort_by_key(|sig| sig.party_index);
+        local_sigs.into_iter().map(|sig| (sig.local_sig, sig.party_index)).unzip()
+    }
+
+    #[napi]
+    /// `verify_local_sigs` taking each local signature together with its party index, in any order
+    pub fn verify_indexed_local_sigs(
+        local_sigs: Vec<SerializableIndexedLocalSig>,
+        vss_private_keys: Vec<SerializableVerifiableSS>,
+        vss_ephemeral_keys: Vec<SerializableVerifiableSS>,
+    ) -> Result<SerializableVerifiableSS> {
+        crate::guard::guard("verify_indexed_local_sigs", || {
+            let (local_sigs, parties_index) = unzip_indexed_local_sigs(local_sigs);
+            verify_local_sigs(local_sigs, parties_index, vss_private_keys, vss_ephemeral_keys)
+        })
+    }
+
+    #[napi]
+    /// `generate_signature` taking each local signature together with its party index, in any order
+    pub fn generate_indexed_signature(
+        vss_sum_local_sigs: SerializableVerifiableSS,
+        local_sigs: Vec<SerializableIndexedLocalSig>,
+        R: SerializablePoint,
+        verify_after_generate: Option<bool>,
+        public_key: Option<SerializablePoint>,
+        message: Option<Vec<u8>>,
+        hash: Option<ChallengeHash>,
+    ) -> Result<SerializableSignature> {
+        crate::guard::guard("generate_indexed_signature", || {
+            let (local_sigs, parties_index) = unzip_indexed_local_sigs(local_sigs);
+            generate_signature(vss_sum_local_sigs, local_sigs, parties_index, R, verify_after_generate, public_key, message, hash)
+        })
+    }
+
+    #[napi]
+    #[allow(clippy::too_many_arguments)]
+    /// `generate_signature` with self-verification, returning an auditable receipt of the
+    /// signature, the aggregate R, the signer set and the challenge under `hash`
+    pub fn generate_signature_with_receipt(
+        vss_sum_local_sigs: SerializableVerifiableSS,
+        local_sigs: Vec<SerializableLocalSig>,
+        parties_index: Vec<u16>,
+        R: SerializablePoint,
+        public_key: SerializablePoint,
+        message: Vec<u8>,
+        hash: Option<ChallengeHash>,
+    ) -> Result<SerializableSigningReceipt> {
+        crate::guard::guard("generate_signature_with_receipt", || {
+            check_message_size(&message)?;
+            let message_hash = compute_challenge(R.clone(), public_key.clone(), message.clone(), hash)?;
+            let signature = generate_signature(
+                vss_sum_local_sigs,
+                local_sigs,
+                parties_index.clone(),
+                R,
+                Some(true),
+                Some(public_key),
+                Some(message),
+                hash,
+            )?;
+            Ok(SerializableSigningReceipt {
+                R: signature.R.clone(),
+                signature,
+                parties_index,
+                message_hash,
             })
-            .collect();
+        })
+    }
+
+    #[napi]
+    #[allow(clippy::too_many_arguments)]
+    /// `generate_signature` that also reports which parties contributed. Given the size of the
+    /// full party set as `share_count`, the participants are returned as a bitmap as well.
+    pub fn generate_signature_with_participants(
+        vss_sum_local_sigs: SerializableVerifiableSS,
+        local_sigs: Vec<SerializableLocalSig>,
+        parties_index: Vec<u16>,
+        R: SerializablePoint,
+        share_count: Option<u16>,
+        verify_after_generate: Option<bool>,
+        public_key: Option<SerializablePoint>,
+        message: Option<Vec<u8>>,
+        hash: Option<ChallengeHash>,
+    ) -> Result<SerializableSignedBy> {
+        crate::guard::guard("generate_signature_with_participants", || {
+            let bitmap = share_count
+                .map(|share_count| {
+                    let mut bitmap = vec![0u8; (share_count as usize).div_ceil(8)];
+                    for &i in &parties_index {
+                        if i >= share_count {
+                            return Err(napi::Error::new(Status::InvalidArg, format!("Party index {} out of range for {} parties", i, share_count)));
+                        }
+                        bitmap[i as usize / 8] |= 1 << (i % 8);
+                    }
+                    Ok(bitmap)
+                })
+                .transpose()?;
+            let mut participants = parties_index.clone();
+            participants.sort_unstable();
+            let signature = generate_signature(vss_sum_local_sigs, local_sigs, parties_index, R, verify_after_generate, public_key, message, hash)?;
+            Ok(SerializableSignedBy { signature, participants, bitmap })
+        })
+    }
+
+    #[napi]
+    /// Check whether a scalar encoding is reduced modulo the group order
+    pub fn is_canonical_scalar(s: SerializableScalar) -> Result<bool> {
+        crate::guard::guard("is_canonical_scalar", || {
+            if s.bytes.len() != 32 {
+                return Err(napi::Error::new(Status::InvalidArg, "Invalid scalar bytes length"));
+            }
+            Ok(scalar_bytes_are_canonical(&s.bytes))
+        })
+    }
 
-        let R_point = serializable_to_point(&R)?;
+    // Each dealer's zeroth commitment is its secret times G, so they sum to the shared point:
+    // y for the keygen schemes, R for the ephemeral ones
+    pub(crate) fn sum_zeroth_commitments(name: &'static str, vss_schemes: &[SerializableVerifiableSS]) -> Result<Point<Ed25519>> {
+        check_non_empty(name, vss_schemes.len())?;
+        vss_schemes.iter()
+            .map(|vss| serializable_to_vss(vss).map(|vss| vss.commitments[0].clone()))
+            .try_fold(Point::<Ed25519>::zero(), |acc, point| point.map(|point| acc + point))
+    }
 
-        let signature = thresholdsig::generate(
-            &vss_sum,
-            &local_sig_vec,
-            &parties_index,
-            R_point,
-        );
+    #[napi]
+    /// Derive R independently of the signers by summing the zeroth commitments of the ephemeral
+    /// VSS schemes; with `expected_R`, the R the parties signed under, error if they differ
+    pub fn derive_aggregate_R_from_vss(
+        vss_ephemeral_keys: Vec<SerializableVerifiableSS>,
+        expected_R: Option<SerializablePoint>,
+    ) -> Result<SerializablePoint> {
+        crate::guard::guard("derive_aggregate_R_from_vss", || {
+            let R = sum_zeroth_commitments("vss_ephemeral_keys", &vss_ephemeral_keys)?;
+            if let Some(expected_R) = expected_R {
+                check_R_consistent("aggregated commitment at index zero", &R, &serializable_to_point(&expected_R)?)?;
+            }
+            Ok(point_to_serializable(&R))
+        })
+    }
 
-        Ok(SerializableSignature {
-            R: point_to_serializable(&signature.R),
-            s: scalar_to_serializable(&signature.s),
+    #[napi]
+    /// Verify local signatures and, only if they all check out, aggregate them into the final signature
+    pub fn aggregate_signature(
+        local_sigs: Vec<SerializableLocalSig>,
+        parties_index: Vec<u16>,
+        vss_private_keys: Vec<SerializableVerifiableSS>,
+        vss_ephemeral_keys: Vec<SerializableVerifiableSS>,
+        R: SerializablePoint,
+    ) -> Result<SerializableSignature> {
+        crate::guard::guard("aggregate_signature", || {
+            let local_sig_vec = deserialize_local_sigs(&local_sigs)?;
+            let R_point = serializable_to_point(&R)?;
+
+            let R_sum = sum_zeroth_commitments("vss_ephemeral_keys", &vss_ephemeral_keys)?;
+            check_R_consistent("aggregated commitment at index zero", &R_sum, &R_point)?;
+
+            let vss_sum = verify_local_sigs_internal(
+                &local_sig_vec,
+                &parties_index,
+                &vss_private_keys,
+                &vss_ephemeral_keys,
+            )?;
+            let signature = generate_signature_internal(
+                &vss_sum,
+                &local_sig_vec,
+                &parties_index,
+                R_point,
+            )?;
+
+            metrics::record_signature();
+            Ok(SerializableSignature {
+                R: point_to_serializable(&signature.R),
+                s: scalar_to_serializable(&signature.s),
+            })
         })
     }
 
     #[napi]
-    /// Verify signature
+    /// Verify signature. By default this is the cofactorless equation sB = R + kA, under which
+    /// R and A must be prime-order points. With `cofactored` set it checks [8]sB = [8]R + [8]kA
+    /// instead, which ignores small-order components of R and A, for chains whose consensus
+    /// rules use that variant.
     pub fn verify_signature(
         signature: SerializableSignature,
         message: Vec<u8>,
         public_key: SerializablePoint,
+        cofactored: Option<bool>,
+        hash: Option<ChallengeHash>,
+    ) -> Result<bool> {
+        crate::guard::guard("verify_signature", || {
+            verify_signature_bytes(&signature, &message, &public_key, cofactored, hash)
+        })
+    }
+
+    #[napi]
+    /// `verify_signature` reading the message straight out of a Node Buffer, without the copy
+    /// napi makes for `Vec<u8>`
+    pub fn verify_signature_buffer(
+        signature: SerializableSignature,
+        message: Buffer,
+        public_key: SerializablePoint,
+        cofactored: Option<bool>,
+        hash: Option<ChallengeHash>,
+    ) -> Result<bool> {
+        crate::guard::guard("verify_signature_buffer", || {
+            verify_signature_bytes(&signature, &message, &public_key, cofactored, hash)
+        })
+    }
+
+    // Shared by verify_signature and verify_signature_buffer
+    pub(crate) fn verify_signature_bytes(
+        signature: &SerializableSignature,
+        message: &[u8],
+        public_key: &SerializablePoint,
+        cofactored: Option<bool>,
+        hash: Option<ChallengeHash>,
     ) -> Result<bool> {
+        check_message_size(message)?;
+        if cofactored.unwrap_or(false) {
+            metrics::record_verification();
+            return with_challenge_hash!(hash, H => verify_with_rules::<H>(signature, message, public_key, COFACTORED));
+        }
         let sig = multi_party_eddsa::protocols::Signature {
             R: serializable_to_point(&signature.R)?,
             s: serializable_to_scalar(&signature.s)?,
         };
 
-        let pk = serializable_to_point(&public_key)?;
+        let pk = serializable_to_point(public_key)?;
 
-        match sig.verify(&message, &pk) {
+        metrics::record_verification();
+        match with_challenge_hash!(hash, H => sig.verify_with_hash::<H>(message, &pk)) {
             Ok(_) => Ok(true),
             Err(_) => Ok(false),
         }
     }
+
+    #[napi]
+    /// `verify_signature` for a string message, verified as its UTF-8 bytes
+    pub fn verify_signature_str(
+        signature: SerializableSignature,
+        message: String,
+        public_key: SerializablePoint,
+        cofactored: Option<bool>,
+        hash: Option<ChallengeHash>,
+    ) -> Result<bool> {
+        crate::guard::guard("verify_signature_str", || {
+            verify_signature(signature, message.into_bytes(), public_key, cofactored, hash)
+        })
+    }
+
+    #[napi]
+    /// `verify_signature` against the group key y of a constructed key in the store, so the
+    /// caller neither re-sends the point nor risks passing the wrong one
+    pub fn verify_signature_for_key(
+        shared_key_id: String,
+        signature: SerializableSignature,
+        message: Vec<u8>,
+        cofactored: Option<bool>,
+        hash: Option<ChallengeHash>,
+    ) -> Result<bool> {
+        crate::guard::guard("verify_signature_for_key", || {
+            let y = group_key(&shared_key_id, "verify_signature_for_key")?;
+            verify_signature_bytes(&signature, &message, &point_to_serializable(&y), cofactored, hash)
+        })
+    }
+
+    // What a verifier accepts beyond a canonical s, which every rule set requires
+    #[derive(Clone, Copy)]
+    struct VerifyRules {
+        // [8]sB = [8]R + [8]kA instead of sB = R + kA
+        cofactored: bool,
+        // refuse R or A with a small-order component
+        reject_torsion: bool,
+    }
+
+    const COFACTORED: VerifyRules = VerifyRules { cofactored: true, reject_torsion: false };
+
+    /// Acceptance rules of `verify_signature_strict_chain`. All of them require a canonical s.
+    #[napi(string_enum)]
+    #[derive(Debug, PartialEq)]
+    // napi derives Clone and Copy for string enums, but not in the `noop` build
+    #[cfg_attr(feature = "noop", derive(Clone, Copy))]
+    pub enum ChainPreset {
+        /// RFC 8032 with the cofactorless equation sB = R + kA, what `verify_signature` checks
+        Rfc8032,
+        /// Cofactorless, and R and A must lie in the prime-order subgroup, as strict
+        /// verifiers such as Solana's require
+        SolanaStrict,
+        /// The cofactored equation [8]sB = [8]R + [8]kA of ZIP 215, which ignores any
+        /// small-order component of R and A
+        Zip215,
+    }
+
+    impl ChainPreset {
+        fn rules(&self) -> VerifyRules {
+            match self {
+                ChainPreset::Rfc8032 => VerifyRules { cofactored: false, reject_torsion: false },
+                ChainPreset::SolanaStrict => VerifyRules { cofactored: false, reject_torsion: true },
+                ChainPreset::Zip215 => COFACTORED,
+            }
+        }
+    }
+
+    #[napi]
+    /// Verify under the exact acceptance rules of a target chain (RFC 8032 by default), for
+    /// integrators who must accept or reject the same signatures the chain does. R or A with a
+    /// torsion component can verify under one preset and fail under another.
+    pub fn verify_signature_strict_chain(
+        signature: SerializableSignature,
+        message: Vec<u8>,
+        public_key: SerializablePoint,
+        chain: Option<ChainPreset>,
+    ) -> Result<bool> {
+        crate::guard::guard("verify_signature_strict_chain", || {
+            check_message_size(&message)?;
+            let rules = chain.unwrap_or(ChainPreset::Rfc8032).rules();
+            metrics::record_verification();
+            verify_with_rules::<Sha512>(&signature, &message, &public_key, rules)
+        })
+    }
+
+    // The 32-byte encoding, with or without the point prefix, and the point it decodes to.
+    // curv only admits prime-order points, so the rule-driven equations are evaluated with
+    // curve25519-dalek, whose decoding keeps any torsion component for [8] to clear
+    fn dalek_point(
+        point: &SerializablePoint,
+        name: &'static str,
+    ) -> Result<([u8; 32], curve25519_dalek::edwards::EdwardsPoint)> {
+        let bytes = point_encoding(point)
+            .map_err(|_| napi::Error::from(TssError::InvalidPoint { name }))?;
+        curve25519_dalek::edwards::CompressedEdwardsY(bytes).decompress()
+            .map(|decoded| (bytes, decoded))
+            .ok_or_else(|| napi::Error::from(TssError::InvalidPoint { name }))
+    }
+
+    fn verify_with_rules<H: Digest>(
+        signature: &SerializableSignature,
+        message: &[u8],
+        public_key: &SerializablePoint,
+        rules: VerifyRules,
+    ) -> Result<bool> {
+        let (R_bytes, R) = dalek_point(&signature.R, "R")?;
+        let (A_bytes, A) = dalek_point(public_key, "public key")?;
+        let digest = H::new()
+            .chain(R_bytes)
+            .chain(A_bytes)
+            .chain(message)
+            .finalize();
+        verify_digest_with_rules(R, A, &signature.s, &digest, rules)
+    }
+
+    // `verify_with_rules` given the digest of R || A || M, however it was hashed
+    fn verify_digest_with_rules(
+        R: curve25519_dalek::edwards::EdwardsPoint,
+        A: curve25519_dalek::edwards::EdwardsPoint,
+        s: &SerializableScalar,
+        digest: &[u8],
+        rules: VerifyRules,
+    ) -> Result<bool> {
+        use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+        use curve25519_dalek::scalar::Scalar as DalekScalar;
+
+        let s_bytes: [u8; 32] = s.bytes.as_slice().try_into()
+            .map_err(|_| napi::Error::from(TssError::NonCanonicalScalar { name: "s" }))?;
+        let s = DalekScalar::from_canonical_bytes(s_bytes)
+            .ok_or_else(|| napi::Error::from(TssError::NonCanonicalScalar { name: "s" }))?;
+        // the digest read little-endian, zero-extended for hashes shorter than 64 bytes
+        let mut k_bytes = [0u8; 64];
+        k_bytes[..digest.len()].copy_from_slice(digest);
+        let k = DalekScalar::from_bytes_mod_order_wide(&k_bytes);
+
+        if rules.reject_torsion && !(R.is_torsion_free() && A.is_torsion_free()) {
+            return Ok(false);
+        }
+        let (sB, RkA) = (s * ED25519_BASEPOINT_POINT, R + k * A);
+        Ok(if rules.cofactored {
+            sB.mul_by_cofactor() == RkA.mul_by_cofactor()
+        } else {
+            sB == RkA
+        })
+    }
+
+    #[napi]
+    /// Whether the point lies in the prime-order subgroup, checked by clearing the cofactor
+    /// rather than trusting the encoding; errors if it does not decode to a curve point at all
+    pub fn is_in_prime_order_subgroup(point: SerializablePoint) -> Result<bool> {
+        crate::guard::guard("is_in_prime_order_subgroup", || point_in_prime_order_subgroup(&point, "point"))
+    }
+
+    #[napi]
+    /// Verify signature, reporting why it failed as a TssError ("Code: detail") in `reason`
+    pub fn verify_signature_detailed(
+        signature: SerializableSignature,
+        message: Vec<u8>,
+        public_key: SerializablePoint,
+    ) -> Result<SerializableVerifyResult> {
+        crate::guard::guard("verify_signature_detailed", || {
+            check_message_size(&message)?;
+            let result = check_signature(&signature, &message, &public_key);
+            metrics::record_verification();
+            Ok(SerializableVerifyResult {
+                valid: result.is_ok(),
+                reason: result.err().map(|e| e.to_string()),
+            })
+        })
+    }
+
+    #[napi]
+    /// Index of the first candidate public key the signature verifies under, if any.
+    /// Candidates that are not valid points never match.
+    pub fn signature_binds_public_key(
+        signature: SerializableSignature,
+        message: Vec<u8>,
+        candidate_keys: Vec<SerializablePoint>,
+    ) -> Result<Option<u32>> {
+        crate::guard::guard("signature_binds_public_key", || {
+            check_message_size(&message)?;
+            let sig = multi_party_eddsa::protocols::Signature {
+                R: serializable_to_point(&signature.R)?,
+                s: serializable_to_scalar(&signature.s)?,
+            };
+
+            Ok(candidate_keys.iter()
+                .position(|candidate| match serializable_to_point(candidate) {
+                    Ok(pk) => sig.verify(&message, &pk).is_ok(),
+                    Err(_) => false,
+                })
+                .map(|i| i as u32))
+        })
+    }
+
+    #[napi]
+    /// Index of the first candidate message the signature verifies under `public_key`, if any.
+    /// Useful for finding which of several encodings of a message was actually signed.
+    pub fn find_signed_message(
+        signature: SerializableSignature,
+        candidates: Vec<Vec<u8>>,
+        public_key: SerializablePoint,
+    ) -> Result<Option<u32>> {
+        crate::guard::guard("find_signed_message", || {
+            for message in &candidates {
+                check_message_size(message)?;
+            }
+            let sig = multi_party_eddsa::protocols::Signature {
+                R: serializable_to_point(&signature.R)?,
+                s: serializable_to_scalar(&signature.s)?,
+            };
+            let pk = serializable_to_point(&public_key)?;
+
+            Ok(candidates.iter()
+                .position(|message| sig.verify(message, &pk).is_ok())
+                .map(|i| i as u32))
+        })
+    }
+
+    #[napi]
+    /// Every message of `messages` the signature verifies for under `public_key`. A signature
+    /// bound to one context verifies for exactly one of them; anything else points to a replay
+    /// across contexts or messages that are not actually distinct.
+    pub fn verify_exclusive(
+        signature: SerializableSignature,
+        messages: Vec<Vec<u8>>,
+        public_key: SerializablePoint,
+    ) -> Result<SerializableExclusiveResult> {
+        crate::guard::guard("verify_exclusive", || {
+            let sig = multi_party_eddsa::protocols::Signature {
+                R: serializable_to_point(&signature.R)?,
+                s: serializable_to_scalar(&signature.s)?,
+            };
+            let pk = serializable_to_point(&public_key)?;
+            for message in &messages {
+                check_message_size(message)?;
+            }
+
+            let valid_indices = messages.iter()
+                .enumerate()
+                .filter(|(_, message)| {
+                    metrics::record_verification();
+                    sig.verify(message, &pk).is_ok()
+                })
+                .map(|(i, _)| i as u32)
+                .collect();
+            Ok(SerializableExclusiveResult { valid_indices })
+        })
+    }
+
+    #[napi]
+    /// Combine the group public keys of several independent threshold groups into one key.
+    /// This is group-level aggregation on top of each group's own VSS keygen, not part of it.
+    /// Each key is weighted by a coefficient hashed from the sorted key list (MuSig-style),
+    /// so the result does not depend on input order and no key can be chosen to cancel another.
+    pub fn aggregate_public_keys(keys: Vec<SerializablePoint>) -> Result<SerializablePoint> {
+        crate::guard::guard("aggregate_public_keys", || {
+            if keys.is_empty() {
+                return Err(napi::Error::new(Status::InvalidArg, "No public keys to aggregate"));
+            }
+            let mut points = keys.iter()
+                .map(serializable_to_point)
+                .collect::<Result<Vec<_>>>()?;
+            points.sort_by_key(|point| point.to_bytes(true).to_vec());
+
+            let list_hash = points.iter()
+                .fold(Sha512::new().chain(b"multi-party-eddsa key aggregation list"), |hasher, point| {
+                    hasher.chain(&*point.to_bytes(true))
+                })
+                .finalize();
+            let aggregate = points.iter().fold(Point::<Ed25519>::zero(), |acc, point| {
+                let digest = Sha512::new()
+                    .chain(b"multi-party-eddsa key aggregation coefficient")
+                    .chain(list_hash)
+                    .chain(&*point.to_bytes(true))
+                    .finalize();
+                acc + point * Scalar::<Ed25519>::from_bigint(&BigInt::from_bytes(&digest))
+            });
+            Ok(point_to_serializable(&aggregate))
+        })
+    }
+
+    fn check_signature(
+        signature: &SerializableSignature,
+        message: &[u8],
+        public_key: &SerializablePoint,
+    ) -> std::result::Result<(), TssError> {
+        let R = serializable_to_point(&signature.R)
+            .map_err(|_| TssError::InvalidPoint { name: "R" })?;
+        let pk = serializable_to_point(public_key)
+            .map_err(|_| TssError::InvalidPoint { name: "public key" })?;
+        if signature.s.bytes.len() != 32 || !scalar_bytes_are_canonical(&signature.s.bytes) {
+            return Err(TssError::NonCanonicalScalar { name: "s" });
+        }
+        let s = serializable_to_scalar(&signature.s)
+            .map_err(|_| TssError::NonCanonicalScalar { name: "s" })?;
+
+        multi_party_eddsa::protocols::Signature { R, s }
+            .verify(message, &pk)
+            .map_err(|_| TssError::SignatureMismatch)
+    }
 }
 
+mod roundtrip;
+mod test;
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use crate::store::lock;
+use crate::SerializableMetrics;
+
+// Operation counters for operators scraping the process, e.g. into Prometheus.
+// Each one is bumped once an operation has succeeded.
+static KEYGENS: AtomicU32 = AtomicU32::new(0);
+static SIGNATURES: AtomicU32 = AtomicU32::new(0);
+static VERIFICATIONS: AtomicU32 = AtomicU32::new(0);
+
+fn errors_by_code() -> &'static Mutex<HashMap<&'static str, u32>> {
+    static ERRORS: OnceLock<Mutex<HashMap<&'static str, u32>>> = OnceLock::new();
+    ERRORS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// A keypair was constructed, by a multi-party keygen or a single-party import.
+pub(crate) fn record_keygen() {
+    KEYGENS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// A final signature was produced.
+pub(crate) fn record_signature() {
+    SIGNATURES.fetch_add(1, Ordering::Relaxed);
+}
+
+/// A final signature was checked against a public key, whatever the outcome.
+pub(crate) fn record_verification() {
+    VERIFICATIONS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// A `TssError` crossed the napi boundary.
+pub(crate) fn record_error(code: &'static str) {
+    *lock(errors_by_code()).entry(code).or_insert(0) += 1;
+}
+
+/// Snapshot of the operation counters, with errors keyed by `TssError` code
+#[napi_derive::napi]
+pub fn get_metrics() -> napi::Result<SerializableMetrics> {
+    crate::guard::guard("get_metrics", || {
+        Ok(SerializableMetrics {
+            keygens: KEYGENS.load(Ordering::Relaxed),
+            signatures: SIGNATURES.load(Ordering::Relaxed),
+            verifications: VERIFICATIONS.load(Ordering::Relaxed),
+            errors_by_code: lock(errors_by_code())
+                .iter()
+                .map(|(code, count)| (code.to_string(), *count))
+                .collect(),
+        })
+    })
+}
+
+/// Zero every counter
+#[napi_derive::napi]
+pub fn reset_metrics() -> napi::Result<()> {
+    crate::guard::guard("reset_metrics", || {
+        KEYGENS.store(0, Ordering::Relaxed);
+        SIGNATURES.store(0, Ordering::Relaxed);
+        VERIFICATIONS.store(0, Ordering::Relaxed);
+        lock(errors_by_code()).clear();
+        Ok(())
+    })
+}
@@ -0,0 +1,45 @@
+#[cfg(feature = "rayon")]
+use std::sync::{Arc, Mutex, OnceLock};
+
+#[cfg(feature = "rayon")]
+use crate::store::lock;
+
+#[cfg(feature = "rayon")]
+fn verification_pool() -> &'static Mutex<Option<Arc<rayon::ThreadPool>>> {
+    static POOL: OnceLock<Mutex<Option<Arc<rayon::ThreadPool>>>> = OnceLock::new();
+    POOL.get_or_init(|| Mutex::new(None))
+}
+
+/// Bound the threads used for per-party verification so keygen does not
+/// oversubscribe the Node worker pool; 0 goes back to rayon's global pool.
+/// Only available in builds with the `rayon` feature.
+#[cfg(feature = "rayon")]
+#[napi_derive::napi]
+pub fn set_verification_threads(threads: u32) -> napi::Result<()> {
+    crate::guard::guard("set_verification_threads", || {
+        let pool = match threads {
+            0 => None,
+            n => Some(Arc::new(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(n as usize)
+                    .build()
+                    .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e.to_string()))?,
+            )),
+        };
+        *lock(verification_pool()) = pool;
+        Ok(())
+    })
+}
+
+/// Run per-party verification work, on the configured pool when the `rayon`
+/// feature is enabled.
+pub(crate) fn with_verification_pool<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    #[cfg(feature = "rayon")]
+    {
+        let pool = lock(verification_pool()).clone();
+        if let Some(pool) = pool {
+            return pool.install(f);
+        }
+    }
+    f()
+}
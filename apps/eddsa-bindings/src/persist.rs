@@ -0,0 +1,246 @@
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, NewAead, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use napi::Status;
+use rand::RngCore;
+use redb::{Database, ReadableTable, TableDefinition};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+use crate::rng::rng_source;
+use crate::store::{lock, Backend};
+use crate::threshold_sig::{ephemeral_keys_store, keys_store};
+use crate::{check_encoding_version, ENCODING_VERSION};
+
+// Write-through persistence of the key and ephemeral stores. Each record is
+// serialized to JSON and sealed with ChaCha20-Poly1305 under a key derived
+// from the passphrase with Argon2id; the record name is the associated data,
+// so records cannot be swapped between ids. The salt and a sealed check value
+// live in the meta table, so a wrong passphrase is refused when the file is
+// opened rather than surfacing as missing keys later. Record plaintexts start
+// with the encoding version; records written before it was added are bare JSON.
+
+const RECORDS: TableDefinition<&str, &[u8]> = TableDefinition::new("records");
+const META: TableDefinition<&str, &[u8]> = TableDefinition::new("meta");
+const SALT: &str = "salt";
+const CHECK: &str = "check";
+const NONCE_BYTES: usize = 12;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+fn storage_error(e: impl std::fmt::Display) -> napi::Error {
+    napi::Error::new(
+        Status::GenericFailure,
+        format!("Persistent store error: {}", e),
+    )
+}
+
+struct Vault {
+    db: Database,
+    cipher: ChaCha20Poly1305,
+}
+
+impl Vault {
+    fn open(path: &str, passphrase: &str) -> napi::Result<Vault> {
+        let db = Database::create(path).map_err(storage_error)?;
+        let txn = db.begin_write().map_err(storage_error)?;
+        let salt = {
+            txn.open_table(RECORDS).map_err(storage_error)?;
+            let mut meta = txn.open_table(META).map_err(storage_error)?;
+            let stored = meta
+                .get(SALT)
+                .map_err(storage_error)?
+                .map(|salt| salt.value().to_vec());
+            match stored {
+                Some(salt) => salt,
+                None => {
+                    let mut salt = vec![0u8; 16];
+                    lock(rng_source()).fill_bytes(&mut salt);
+                    meta.insert(SALT, salt.as_slice()).map_err(storage_error)?;
+                    salt
+                }
+            }
+        };
+
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), &salt, &mut *key)
+            .map_err(storage_error)?;
+        let vault = Vault {
+            db,
+            cipher: ChaCha20Poly1305::new(Key::from_slice(&*key)),
+        };
+
+        {
+            let mut meta = txn.open_table(META).map_err(storage_error)?;
+            let check = meta
+                .get(CHECK)
+                .map_err(storage_error)?
+                .map(|check| check.value().to_vec());
+            match check {
+                Some(check) => {
+                    if vault.unseal(CHECK, &check).is_none() {
+                        return Err(napi::Error::new(
+                            Status::InvalidArg,
+                            "Wrong passphrase for persistent store",
+                        ));
+                    }
+                }
+                None => {
+                    let check = vault.seal(CHECK, CHECK.as_bytes())?;
+                    meta.insert(CHECK, check.as_slice())
+                        .map_err(storage_error)?;
+                }
+            }
+        }
+        txn.commit().map_err(storage_error)?;
+        Ok(vault)
+    }
+
+    // nonce || ciphertext
+    fn seal(&self, name: &str, plaintext: &[u8]) -> napi::Result<Vec<u8>> {
+        let mut nonce = [0u8; NONCE_BYTES];
+        lock(rng_source()).fill_bytes(&mut nonce);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| storage_error("encryption failed"))?;
+        let mut sealed = nonce.to_vec();
+        sealed.extend(ciphertext);
+        Ok(sealed)
+    }
+
+    fn unseal(&self, name: &str, sealed: &[u8]) -> Option<Zeroizing<Vec<u8>>> {
+        if sealed.len() < NONCE_BYTES {
+            return None;
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_BYTES);
+        self.cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: name.as_bytes(),
+                },
+            )
+            .ok()
+            .map(Zeroizing::new)
+    }
+
+    fn get(&self, name: &str) -> napi::Result<Option<Zeroizing<Vec<u8>>>> {
+        let txn = self.db.begin_read().map_err(storage_error)?;
+        let table = txn.open_table(RECORDS).map_err(storage_error)?;
+        let Some(sealed) = table.get(name).map_err(storage_error)? else {
+            return Ok(None);
+        };
+        self.unseal(name, sealed.value())
+            .map(Some)
+            .ok_or_else(|| storage_error(format!("record {} failed authentication", name)))
+    }
+
+    fn put(&self, name: &str, plaintext: &[u8]) -> napi::Result<()> {
+        let sealed = self.seal(name, plaintext)?;
+        let txn = self.db.begin_write().map_err(storage_error)?;
+        txn.open_table(RECORDS)
+            .map_err(storage_error)?
+            .insert(name, sealed.as_slice())
+            .map_err(storage_error)?;
+        txn.commit().map_err(storage_error)
+    }
+
+    fn delete(&self, name: &str) -> napi::Result<()> {
+        let txn = self.db.begin_write().map_err(storage_error)?;
+        txn.open_table(RECORDS)
+            .map_err(storage_error)?
+            .remove(name)
+            .map_err(storage_error)?;
+        txn.commit().map_err(storage_error)
+    }
+}
+
+// One store's records in the vault, named `<namespace>/<id>`
+struct Persisted<V> {
+    vault: Arc<Vault>,
+    namespace: &'static str,
+    value: PhantomData<fn() -> V>,
+}
+
+impl<V> Persisted<V> {
+    fn boxed(vault: &Arc<Vault>, namespace: &'static str) -> Box<Self> {
+        Box::new(Persisted {
+            vault: vault.clone(),
+            namespace,
+            value: PhantomData,
+        })
+    }
+
+    fn name(&self, id: &str) -> String {
+        format!("{}/{}", self.namespace, id)
+    }
+}
+
+impl<V: Serialize + DeserializeOwned> Backend<V> for Persisted<V> {
+    fn load(&self, id: &str) -> napi::Result<Option<V>> {
+        let Some(plaintext) = self.vault.get(&self.name(id))? else {
+            return Ok(None);
+        };
+        let json = match plaintext.split_first() {
+            Some((b'{', _)) => &plaintext[..],
+            Some((&version, json)) => {
+                check_encoding_version(version)?;
+                json
+            }
+            None => return Err(storage_error(format!("record {} is empty", id))),
+        };
+        serde_json::from_slice(json)
+            .map(Some)
+            .map_err(storage_error)
+    }
+
+    fn save(&self, id: &str, value: &V) -> napi::Result<()> {
+        let mut plaintext = Zeroizing::new(vec![ENCODING_VERSION]);
+        serde_json::to_writer(&mut *plaintext, value).map_err(storage_error)?;
+        self.vault.put(&self.name(id), &plaintext)
+    }
+
+    fn delete(&self, id: &str) -> napi::Result<()> {
+        self.vault.delete(&self.name(id))
+    }
+}
+
+/// Persist the key and ephemeral stores to the redb file at `path`, encrypted under
+/// `passphrase`. Keys already in memory are written immediately; keys on disk are loaded
+/// the first time an operation asks for them, so a restarted process picks up where it
+/// left off after calling this again with the same path and passphrase.
+/// Only available in builds with the `persistent-store` feature.
+#[napi_derive::napi]
+pub fn use_persistent_store(path: String, passphrase: String) -> napi::Result<()> {
+    crate::guard::guard("use_persistent_store", || {
+        let passphrase = Zeroizing::new(passphrase);
+        let vault = Arc::new(Vault::open(&path, &passphrase)?);
+        lock(keys_store()).set_backend(Persisted::boxed(&vault, "keys"));
+        lock(ephemeral_keys_store()).set_backend(Persisted::boxed(&vault, "ephemeral"));
+        ENABLED.store(true, Ordering::Relaxed);
+        Ok(())
+    })
+}
+
+// Write the changes of the operation that just finished through to disk
+pub(crate) fn flush() -> napi::Result<()> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    lock(keys_store()).flush()?;
+    lock(ephemeral_keys_store()).flush()
+}
@@ -0,0 +1,43 @@
+use std::fmt::Write;
+use std::sync::{Mutex, OnceLock};
+
+use curv::elliptic::curves::{Ed25519, Point};
+
+use crate::store::{lock, Store};
+
+// Peer public keys, R points and VSS commitments recur across calls, so
+// decoded points are memoized instead of being decompressed every time.
+const POINT_CACHE_CAPACITY: usize = 1024;
+
+fn point_cache() -> &'static Mutex<Store<Point<Ed25519>>> {
+    static CACHE: OnceLock<Mutex<Store<Point<Ed25519>>>> = OnceLock::new();
+    CACHE.get_or_init(|| {
+        let mut store = Store::new();
+        store.set_capacity(POINT_CACHE_CAPACITY, |_| false);
+        Mutex::new(store)
+    })
+}
+
+#[cfg(test)]
+thread_local! {
+    // decompressions performed by the current thread, for the cache tests
+    pub(crate) static DECODES: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+}
+
+/// Decode a compressed point, consulting the cache first. Only points that
+/// decode successfully are cached, so a hit is always a valid point.
+pub(crate) fn decode_point(bytes: &[u8; 32]) -> Option<Point<Ed25519>> {
+    let key = bytes.iter().fold(String::with_capacity(64), |mut key, b| {
+        let _ = write!(key, "{:02x}", b);
+        key
+    });
+    if let Some(point) = lock(point_cache()).get(&key) {
+        return Some(point.clone());
+    }
+
+    #[cfg(test)]
+    DECODES.with(|decodes| decodes.set(decodes.get() + 1));
+    let point = Point::from_bytes(bytes).ok()?;
+    lock(point_cache()).insert(key, point.clone(), |_| false);
+    Some(point)
+}
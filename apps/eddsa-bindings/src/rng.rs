@@ -0,0 +1,82 @@
+use std::sync::{Mutex, OnceLock};
+
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+use rand_chacha::ChaCha20Rng;
+
+/// Randomness used by the napi layer for key, blind factor and polynomial
+/// sampling.
+///
+/// Production code always draws from the OS. Builds with the `testing`
+/// feature can swap in a seeded ChaCha20 stream so fuzz failures replay
+/// byte-for-byte; `export_test_vectors` seeds a private stream of its own.
+pub(crate) enum RngSource {
+    Os,
+    Seeded(Box<ChaCha20Rng>),
+}
+
+impl RngSource {
+    // arbitrary-length seeds are hashed down to the 32 bytes ChaCha20 wants
+    pub(crate) fn seeded(seed: &[u8]) -> Self {
+        use rand::SeedableRng;
+        use sha2::{Digest, Sha512};
+
+        let digest = Sha512::digest(seed);
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&digest[..32]);
+        RngSource::Seeded(Box::new(ChaCha20Rng::from_seed(key)))
+    }
+}
+
+impl RngCore for RngSource {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            RngSource::Os => OsRng.next_u32(),
+            RngSource::Seeded(rng) => rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            RngSource::Os => OsRng.next_u64(),
+            RngSource::Seeded(rng) => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            RngSource::Os => OsRng.fill_bytes(dest),
+            RngSource::Seeded(rng) => rng.fill_bytes(dest),
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        match self {
+            RngSource::Os => OsRng.try_fill_bytes(dest),
+            RngSource::Seeded(rng) => rng.try_fill_bytes(dest),
+        }
+    }
+}
+
+impl CryptoRng for RngSource {}
+
+pub(crate) fn rng_source() -> &'static Mutex<RngSource> {
+    static RNG: OnceLock<Mutex<RngSource>> = OnceLock::new();
+    RNG.get_or_init(|| Mutex::new(RngSource::Os))
+}
+
+/// Replace the process RNG with a ChaCha20 stream seeded from `seed` so keygen
+/// and signing runs are reproducible; an empty seed restores the OS RNG.
+/// Only compiled into builds with the `testing` feature.
+#[cfg(any(test, feature = "testing"))]
+#[napi_derive::napi]
+pub fn set_deterministic_rng(seed: Vec<u8>) -> napi::Result<()> {
+    crate::guard::guard("set_deterministic_rng", || {
+        *crate::store::lock(rng_source()) = if seed.is_empty() {
+            RngSource::Os
+        } else {
+            RngSource::seeded(&seed)
+        };
+        Ok(())
+    })
+}
@@ -0,0 +1,185 @@
+use napi_derive::napi;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+
+use crate::threshold_sig;
+use crate::to_json;
+
+// One JSON entry point over the protocol functions, for services exposing
+// them over JSON-RPC. Parameters go by name (the Rust parameter names) or by
+// position; a missing or null parameter is None for optional ones. Values use
+// the serde layout of the Serializable types, so byte fields are arrays of
+// numbers and string enums are their variant names.
+
+/// Error codes of the dispatcher itself; failures inside a method keep the `TssError`
+/// code of their reason (e.g. `InsufficientParticipants`) or else the napi status
+const METHOD_NOT_FOUND: &str = "MethodNotFound";
+const INVALID_PARAMS: &str = "InvalidParams";
+
+struct RpcError {
+    code: String,
+    message: String,
+}
+
+impl From<napi::Error> for RpcError {
+    fn from(e: napi::Error) -> Self {
+        // TssError reasons read "Code: detail"; ad-hoc ones are plain sentences
+        let code = match e.reason.split_once(": ") {
+            Some((code, _)) if code.chars().all(|c| c.is_ascii_alphanumeric()) => code.to_string(),
+            _ => e.status.as_ref().to_string(),
+        };
+        RpcError {
+            code,
+            message: e.reason,
+        }
+    }
+}
+
+fn param<T: DeserializeOwned>(params: &Value, position: usize, name: &str) -> Result<T, RpcError> {
+    let value = match params {
+        Value::Object(params) => params.get(name),
+        Value::Array(params) => params.get(position),
+        Value::Null => None,
+        _ => {
+            return Err(RpcError {
+                code: INVALID_PARAMS.to_string(),
+                message: "params must be an object or an array".to_string(),
+            })
+        }
+    };
+    serde_json::from_value(value.cloned().unwrap_or(Value::Null)).map_err(|e| RpcError {
+        code: INVALID_PARAMS.to_string(),
+        message: format!("{}: {}", name, e),
+    })
+}
+
+// `method(param: Type, ...)` lists, each routed to the threshold_sig function of that name
+macro_rules! dispatch {
+    ($method:expr, $params:expr, { $($name:ident($($arg:ident: $ty:ty),* $(,)?)),* $(,)? }) => {
+        match $method {
+            $(stringify!($name) => {
+                let mut _position = 0;
+                $(
+                    let $arg: $ty = param($params, _position, stringify!($arg))?;
+                    _position += 1;
+                )*
+                Ok(to_json(stringify!($name), &threshold_sig::$name($($arg),*)?)?)
+            })*
+            method => Err(RpcError {
+                code: METHOD_NOT_FOUND.to_string(),
+                message: format!("Unknown method {}", method),
+            }),
+        }
+    };
+}
+
+fn route(method: &str, params: &Value) -> Result<Value, RpcError> {
+    use crate::threshold_sig::{ChallengeHash, CommitmentScheme};
+    use crate::{
+        SerializableBigInt, SerializableEphemeralSharedKeys, SerializableLocalSig,
+        SerializablePoint, SerializableScalar, SerializableSharedKeys, SerializableSignature,
+        SerializableVerifiableSS,
+    };
+
+    dispatch!(method, params, {
+        phase1_create(party_index: u16),
+        phase1_create_from_private_key(party_index: u16, secret: Vec<u8>),
+        get_individual_public_key(key_id: String),
+        get_group_public_key(shared_key_id: String),
+        get_shared_keys(key_id: String),
+        phase1_broadcast(key_id: String, scheme: Option<CommitmentScheme>),
+        phase1_verify_com_phase2_distribute(
+            key_id: String,
+            threshold: u16,
+            share_count: u16,
+            blind_factors: Vec<SerializableBigInt>,
+            public_keys: Vec<SerializablePoint>,
+            commitments: Vec<SerializableBigInt>,
+            parties: Vec<u16>,
+            scheme: Option<CommitmentScheme>,
+            strict_subgroup: Option<bool>,
+        ),
+        phase2_verify_vss_construct_keypair(
+            key_id: String,
+            threshold: u16,
+            share_count: u16,
+            public_keys: Vec<SerializablePoint>,
+            secret_shares: Vec<SerializableScalar>,
+            vss_schemes: Vec<SerializableVerifiableSS>,
+            index: u16,
+            strict_shares: Option<bool>,
+        ),
+        compute_group_public_key(vss_schemes: Vec<SerializableVerifiableSS>),
+        ephemeral_key_create(
+            key_id: String,
+            message: Vec<u8>,
+            index: u16,
+            domain: Option<Vec<u8>>,
+            signers: Option<Vec<u16>>,
+            hash: Option<ChallengeHash>,
+        ),
+        get_ephemeral_R(eph_key_id: String),
+        ephemeral_phase1_broadcast(eph_key_id: String),
+        ephemeral_phase1_verify_com_phase2_distribute(
+            eph_key_id: String,
+            threshold: u16,
+            share_count: u16,
+            blind_factors: Vec<SerializableBigInt>,
+            R_points: Vec<SerializablePoint>,
+            commitments: Vec<SerializableBigInt>,
+            parties: Vec<u16>,
+            strict_subgroup: Option<bool>,
+        ),
+        ephemeral_phase2_verify_vss_construct_keypair(
+            eph_key_id: String,
+            threshold: u16,
+            share_count: u16,
+            R_points: Vec<SerializablePoint>,
+            secret_shares: Vec<SerializableScalar>,
+            vss_schemes: Vec<SerializableVerifiableSS>,
+            index: u16,
+            strict_shares: Option<bool>,
+        ),
+        compute_local_sig(
+            message: Vec<u8>,
+            ephemeral_shared_keys: SerializableEphemeralSharedKeys,
+            shared_keys: SerializableSharedKeys,
+            expected_R: Option<SerializablePoint>,
+            hash: Option<ChallengeHash>,
+        ),
+        verify_local_sigs(
+            local_sigs: Vec<SerializableLocalSig>,
+            parties_index: Vec<u16>,
+            vss_private_keys: Vec<SerializableVerifiableSS>,
+            vss_ephemeral_keys: Vec<SerializableVerifiableSS>,
+        ),
+        aggregate_signature(
+            local_sigs: Vec<SerializableLocalSig>,
+            parties_index: Vec<u16>,
+            vss_private_keys: Vec<SerializableVerifiableSS>,
+            vss_ephemeral_keys: Vec<SerializableVerifiableSS>,
+            R: SerializablePoint,
+        ),
+        verify_signature(
+            signature: SerializableSignature,
+            message: Vec<u8>,
+            public_key: SerializablePoint,
+            cofactored: Option<bool>,
+            hash: Option<ChallengeHash>,
+        ),
+    })
+}
+
+/// Call the protocol function named `method` with `params` (an object by parameter name, or
+/// an array by position), returning `{"result": ...}` or `{"error": {"code", "message"}}`.
+/// Covers keygen, the signing round, aggregation and verification; everything else is
+/// only reachable through its own export.
+#[napi]
+pub fn handle_request(method: String, params: Value) -> Value {
+    let outcome = crate::guard::guard("handle_request", || Ok(route(&method, &params)))
+        .unwrap_or_else(|e| Err(e.into()));
+    match outcome {
+        Ok(result) => json!({ "result": result }),
+        Err(e) => json!({ "error": { "code": e.code, "message": e.message } }),
+    }
+}
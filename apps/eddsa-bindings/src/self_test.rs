@@ -0,0 +1,162 @@
+use std::panic;
+
+use curv::elliptic::curves::{Ed25519, Point};
+use multi_party_eddsa::protocols::thresholdsig::{
+    self, EphemeralKey, EphemeralSharedKeys, Keys, LocalSig, Parameters, SharedKeys,
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+const SELF_TEST_SEED: [u8; 32] = [0x5e; 32];
+const SELF_TEST_MESSAGE: &[u8] = b"multi-party-eddsa self-test";
+
+/// Expected hex encodings of the group key and signature of the self-test run
+pub(crate) struct Golden<'a> {
+    pub(crate) public_key: &'a str,
+    pub(crate) R: &'a str,
+    pub(crate) s: &'a str,
+}
+
+// Known-answer vectors for the fixed-seed 2-of-2 run below. Any change to
+// keygen, nonce derivation, sharing or aggregation shows up as a mismatch.
+pub(crate) const GOLDEN: Golden<'static> = Golden {
+    public_key: "517884bb7fe86c0cf674bff29ab70ff4335ebe5993885659cb8162f67bf4510b",
+    R: "984e709c6139651216e8c9601c35d84879e6874b82cedba0f1e607b2aac229fc",
+    s: "241c099e988090abe1d9eb4d60ea510dda31d0871c0944bd7eea125174a0940a",
+};
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// Keygen and signing for parties 1 and 2 with fixed secrets and a seeded RNG,
+// returning (y, R, s) encodings; None if any protocol step rejects its inputs.
+fn run_fixed_session() -> Option<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+    let mut rng = ChaCha20Rng::from_seed(SELF_TEST_SEED);
+    let params = Parameters {
+        threshold: 1,
+        share_count: 2,
+    };
+    let parties = [1u16, 2];
+
+    let keys: Vec<Keys> = parties
+        .iter()
+        .map(|&i| Keys::phase1_create_from_private_key(i, [i as u8; 32]))
+        .collect();
+    let y_vec: Vec<Point<Ed25519>> = keys.iter().map(|k| k.keypair.public_key.clone()).collect();
+    let (bc1_vec, blind_vec): (Vec<_>, Vec<_>) = keys
+        .iter()
+        .map(|k| k.phase1_broadcast_rng(&mut rng))
+        .unzip();
+    let mut vss_vec = Vec::new();
+    let mut shares_vec = Vec::new();
+    for k in &keys {
+        let (vss, shares) = k
+            .phase1_verify_com_phase2_distribute_rng(
+                &params, &blind_vec, &y_vec, &bc1_vec, &parties, &mut rng,
+            )
+            .ok()?;
+        vss_vec.push(vss);
+        shares_vec.push(shares);
+    }
+    let shared_keys: Vec<SharedKeys> = keys
+        .iter()
+        .enumerate()
+        .map(|(i, k)| {
+            let received: Vec<_> = shares_vec.iter().map(|s| s[i].clone()).collect();
+            k.phase2_verify_vss_construct_keypair(&params, &y_vec, &received, &vss_vec, parties[i])
+                .ok()
+        })
+        .collect::<Option<_>>()?;
+
+    let eph_keys: Vec<EphemeralKey> = keys
+        .iter()
+        .zip(&parties)
+        .map(|(k, &i)| {
+            EphemeralKey::ephermeral_key_create_from_deterministic_secret_rng(
+                k,
+                SELF_TEST_MESSAGE,
+                i,
+                &mut rng,
+            )
+        })
+        .collect();
+    let R_vec: Vec<Point<Ed25519>> = eph_keys.iter().map(|e| e.R_i.clone()).collect();
+    let (eph_bc1_vec, eph_blind_vec): (Vec<_>, Vec<_>) = eph_keys
+        .iter()
+        .map(|e| e.phase1_broadcast_rng(&mut rng))
+        .unzip();
+    let mut eph_vss_vec = Vec::new();
+    let mut eph_shares_vec = Vec::new();
+    for e in &eph_keys {
+        let (vss, shares) = e
+            .phase1_verify_com_phase2_distribute_rng(
+                &params,
+                &eph_blind_vec,
+                &R_vec,
+                &eph_bc1_vec,
+                &parties,
+                &mut rng,
+            )
+            .ok()?;
+        eph_vss_vec.push(vss);
+        eph_shares_vec.push(shares);
+    }
+    let eph_shared_keys: Vec<EphemeralSharedKeys> = eph_keys
+        .iter()
+        .enumerate()
+        .map(|(i, e)| {
+            let received: Vec<_> = eph_shares_vec.iter().map(|s| s[i].clone()).collect();
+            e.phase2_verify_vss_construct_keypair(
+                &params,
+                &R_vec,
+                &received,
+                &eph_vss_vec,
+                parties[i],
+            )
+            .ok()
+        })
+        .collect::<Option<_>>()?;
+
+    let local_sigs: Vec<LocalSig> = eph_shared_keys
+        .iter()
+        .zip(&shared_keys)
+        .map(|(eph, shared)| LocalSig::compute(SELF_TEST_MESSAGE, eph, shared))
+        .collect();
+    let parties_index = [0u16, 1];
+    let vss_sum =
+        LocalSig::verify_local_sigs(&local_sigs, &parties_index, &vss_vec, &eph_vss_vec).ok()?;
+    let signature = thresholdsig::generate(
+        &vss_sum,
+        &local_sigs,
+        &parties_index,
+        eph_shared_keys[0].R.clone(),
+    );
+    signature
+        .verify(SELF_TEST_MESSAGE, &shared_keys[0].y)
+        .ok()?;
+
+    Some((
+        shared_keys[0].y.to_bytes(true).to_vec(),
+        signature.R.to_bytes(true).to_vec(),
+        signature.s.to_bytes().to_vec(),
+    ))
+}
+
+pub(crate) fn self_test_against(golden: &Golden) -> bool {
+    // the core asserts on malformed inputs; a panic here is a failed self-test
+    match panic::catch_unwind(run_fixed_session) {
+        Ok(Some((y, R, s))) => {
+            hex(&y) == golden.public_key && hex(&R) == golden.R && hex(&s) == golden.s
+        }
+        _ => false,
+    }
+}
+
+/// Known-answer test: run a fixed-seed 2-of-2 keygen and signature and compare
+/// the public key and signature to golden vectors. Returns false on any
+/// divergence, so startup can be gated on it.
+#[napi_derive::napi]
+pub fn run_self_test() -> napi::Result<bool> {
+    crate::guard::guard("run_self_test", || Ok(self_test_against(&GOLDEN)))
+}
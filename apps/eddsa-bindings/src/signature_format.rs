@@ -0,0 +1,92 @@
+use napi::bindgen_prelude::Either3;
+use napi::Status;
+
+use crate::threshold_sig;
+use crate::{SerializablePoint, SerializableScalar, SerializableSignature};
+
+// Upstreams hand signatures over in different layouts: the object form, the raw
+// 64-byte R || s, or that encoded as hex or base64. The text encodings have
+// distinct lengths for 64 bytes (128 hex digits, 86 or 88 base64 characters), so
+// a string is never a valid instance of both.
+
+fn unrecognized() -> napi::Error {
+    napi::Error::new(
+        Status::InvalidArg,
+        "Unrecognized signature encoding, expected 64 bytes, 128 hex digits or base64 of 64 bytes",
+    )
+}
+
+// Standard or URL-safe base64, padded or not. Mixing the two alphabets is
+// rejected, as are trailing bits that a canonical encoder would leave zero.
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let body = text.trim_end_matches('=');
+    if text.len() - body.len() > 2 || body.len() % 4 == 1 {
+        return None;
+    }
+    let standard = body.contains(['+', '/']);
+    let url_safe = body.contains(['-', '_']);
+    if standard && url_safe {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(body.len() * 3 / 4);
+    let (mut acc, mut bits) = (0u32, 0u32);
+    for c in body.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            _ => return None,
+        };
+        acc = (acc << 6) | u32::from(value);
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((acc >> bits) as u8);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    (acc == 0).then_some(bytes)
+}
+
+fn decode_signature_text(text: &str) -> napi::Result<Vec<u8>> {
+    let text = text.trim();
+    let hex_digits = text.strip_prefix("0x").unwrap_or(text);
+    if hex_digits.len() == 128 {
+        return hex::decode(hex_digits).map_err(|_| unrecognized());
+    }
+    decode_base64(text).ok_or_else(unrecognized)
+}
+
+/// Verify a signature given as a `SerializableSignature`, 64 bytes of R || s, or those
+/// bytes as a hex string (optionally 0x-prefixed) or base64 (standard or URL-safe).
+/// Input in none of these layouts is an error rather than a failed verification.
+#[napi_derive::napi]
+pub fn verify_signature_auto(
+    input: Either3<Vec<u8>, String, SerializableSignature>,
+    message: Vec<u8>,
+    public_key: SerializablePoint,
+) -> napi::Result<bool> {
+    crate::guard::guard("verify_signature_auto", || {
+        let bytes = match input {
+            Either3::A(bytes) => bytes,
+            Either3::B(text) => decode_signature_text(&text)?,
+            Either3::C(signature) => {
+                return threshold_sig::verify_signature(signature, message, public_key, None, None)
+            }
+        };
+        if bytes.len() != 64 {
+            return Err(unrecognized());
+        }
+        let signature = SerializableSignature {
+            R: SerializablePoint {
+                bytes: bytes[..32].to_vec(),
+            },
+            s: SerializableScalar {
+                bytes: bytes[32..].to_vec(),
+            },
+        };
+        threshold_sig::verify_signature(signature, message, public_key, None, None)
+    })
+}
@@ -0,0 +1,296 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use napi::Status;
+
+use crate::store::{lock, Store};
+use crate::threshold_sig::{self, ChallengeHash, IdKind, KeyPhase};
+use crate::{
+    to_json, SerializableBigInt, SerializableEphemeralSharedKeys, SerializableLocalSig,
+    SerializablePoint, SerializableScalar, SerializableSignature, SerializableVerifiableSS,
+};
+
+// A party's long-lived signing context: its constructed key, the keygen VSS
+// schemes of every party and the keygen threshold.
+#[derive(Clone)]
+pub(crate) struct Signer {
+    key_id: String,
+    vss_schemes: Vec<SerializableVerifiableSS>,
+    party_index: u16,
+    threshold: u16,
+}
+
+// One signature attempt of a signer, advanced by the signer_* round functions
+#[derive(Clone)]
+pub(crate) struct SignerRound {
+    signer_id: String,
+    message: Vec<u8>,
+    eph_key_id: String,
+    hash: Option<ChallengeHash>,
+    // set by signer_distribute
+    signers: Option<Vec<u16>>,
+    // set by signer_construct
+    eph_shared_keys: Option<SerializableEphemeralSharedKeys>,
+}
+
+pub(crate) fn signers_store() -> &'static Mutex<Store<Signer>> {
+    static STORE: OnceLock<Mutex<Store<Signer>>> = OnceLock::new();
+    STORE.get_or_init(|| Mutex::new(Store::new()))
+}
+
+pub(crate) fn signer_rounds_store() -> &'static Mutex<Store<SignerRound>> {
+    static STORE: OnceLock<Mutex<Store<SignerRound>>> = OnceLock::new();
+    STORE.get_or_init(|| Mutex::new(Store::new()))
+}
+
+fn out_of_order(step: &str) -> napi::Error {
+    napi::Error::new(
+        Status::InvalidArg,
+        format!("Round has not completed {}", step),
+    )
+}
+
+// Clone out the round and its signer so no store lock is held across protocol calls
+fn round_context(round_id: &str) -> napi::Result<(SignerRound, Signer)> {
+    let round = lock(signer_rounds_store())
+        .get(round_id)
+        .cloned()
+        .ok_or_else(|| IdKind::SignerRound.not_found(round_id))?;
+    let signer = lock(signers_store())
+        .get(&round.signer_id)
+        .cloned()
+        .ok_or_else(|| IdKind::Signer.not_found(&round.signer_id))?;
+    Ok((round, signer))
+}
+
+fn update_round(round_id: &str, f: impl FnOnce(&mut SignerRound)) -> napi::Result<()> {
+    let mut rounds = lock(signer_rounds_store());
+    let round = rounds
+        .get_mut(round_id)
+        .ok_or_else(|| IdKind::SignerRound.not_found(round_id))?;
+    f(round);
+    Ok(())
+}
+
+/// Bundle a constructed key with the keygen VSS schemes, its party index and the keygen
+/// parameters into one signing handle, so repeated signatures need only the signer id
+#[napi_derive::napi]
+pub fn create_signer(
+    shared_key_id: String,
+    vss: Vec<SerializableVerifiableSS>,
+    party_index: u16,
+    threshold: u16,
+    share_count: u16,
+) -> napi::Result<String> {
+    crate::guard::guard("create_signer", || {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        lock(threshold_sig::keys_store())
+            .get(&shared_key_id)
+            .ok_or_else(|| IdKind::Key.not_found(&shared_key_id))?
+            .require_phase(
+                &shared_key_id,
+                KeyPhase::KeypairConstructed,
+                "create_signer",
+            )?;
+        if threshold >= share_count {
+            return Err(napi::Error::new(
+                Status::InvalidArg,
+                format!(
+                    "threshold {} must be below share_count {}",
+                    threshold, share_count
+                ),
+            ));
+        }
+        if vss.len() != usize::from(share_count) {
+            return Err(napi::Error::new(
+                Status::InvalidArg,
+                format!("Expected {} VSS schemes, got {}", share_count, vss.len()),
+            ));
+        }
+        for scheme in &vss {
+            crate::serializable_to_vss(scheme)?;
+        }
+        if party_index == 0 || party_index > share_count {
+            return Err(napi::Error::new(
+                Status::InvalidArg,
+                format!("Party index {} is outside 1..={}", party_index, share_count),
+            ));
+        }
+
+        let signer_id = format!("signer_{}", COUNTER.fetch_add(1, Ordering::Relaxed));
+        let signer = Signer {
+            key_id: shared_key_id,
+            vss_schemes: vss,
+            party_index,
+            threshold,
+        };
+        lock(signers_store()).insert(signer_id.clone(), signer, |_| false);
+        Ok(signer_id)
+    })
+}
+
+/// Start a signature over `message`, creating the signer's ephemeral key for it. `domain`,
+/// `signers` and `hash` are passed to `ephemeral_key_create`, and `hash` is also the
+/// challenge hash of the local signature; every signer of the round must pass the same values.
+#[napi_derive::napi]
+pub fn signer_start_round(
+    signer_id: String,
+    message: Vec<u8>,
+    domain: Option<Vec<u8>>,
+    signers: Option<Vec<u16>>,
+    hash: Option<ChallengeHash>,
+) -> napi::Result<String> {
+    crate::guard::guard("signer_start_round", || {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        let (key_id, party_index) = {
+            let mut signers = lock(signers_store());
+            let signer = signers
+                .get(&signer_id)
+                .ok_or_else(|| IdKind::Signer.not_found(&signer_id))?;
+            (signer.key_id.clone(), signer.party_index)
+        };
+        let eph_key_id = threshold_sig::ephemeral_key_create(
+            key_id,
+            message.clone(),
+            party_index,
+            domain,
+            signers,
+            hash,
+        )?;
+
+        let round_id = format!("round_{}", COUNTER.fetch_add(1, Ordering::Relaxed));
+        let round = SignerRound {
+            signer_id,
+            message,
+            eph_key_id,
+            hash,
+            signers: None,
+            eph_shared_keys: None,
+        };
+        lock(signer_rounds_store()).insert(round_id.clone(), round, |_| false);
+        Ok(round_id)
+    })
+}
+
+/// Round step 1: the commitment, blind factor and ephemeral point R_i to broadcast
+#[napi_derive::napi]
+pub fn signer_broadcast(round_id: String) -> napi::Result<serde_json::Value> {
+    crate::guard::guard("signer_broadcast", || {
+        let (round, _) = round_context(&round_id)?;
+        let mut broadcast = threshold_sig::ephemeral_phase1_broadcast(round.eph_key_id.clone())?;
+        broadcast["R"] = to_json("R", &threshold_sig::get_ephemeral_R(round.eph_key_id)?)?;
+        Ok(broadcast)
+    })
+}
+
+/// Round step 2: verify the signers' broadcasts and split the nonce into shares, one
+/// envelope per signer. All inputs are ordered like `signers`, the 1-based signer points.
+#[napi_derive::napi]
+pub fn signer_distribute(
+    round_id: String,
+    signers: Vec<u16>,
+    blind_factors: Vec<SerializableBigInt>,
+    R_points: Vec<SerializablePoint>,
+    commitments: Vec<SerializableBigInt>,
+) -> napi::Result<serde_json::Value> {
+    crate::guard::guard("signer_distribute", || {
+        let (round, signer) = round_context(&round_id)?;
+        if !signers.contains(&signer.party_index) {
+            return Err(napi::Error::new(
+                Status::InvalidArg,
+                format!("Signer set does not include party {}", signer.party_index),
+            ));
+        }
+        let distribute = threshold_sig::ephemeral_phase1_verify_com_phase2_distribute(
+            round.eph_key_id,
+            signer.threshold,
+            signers.len() as u16,
+            blind_factors,
+            R_points,
+            commitments,
+            signers.clone(),
+            None,
+        )?;
+        update_round(&round_id, |round| round.signers = Some(signers))?;
+        Ok(distribute)
+    })
+}
+
+/// Round step 3: combine the nonce shares received from every signer (ordered like the
+/// signer set) into this signer's share of the aggregate nonce, returning R
+#[napi_derive::napi]
+pub fn signer_construct(
+    round_id: String,
+    R_points: Vec<SerializablePoint>,
+    secret_shares: Vec<SerializableScalar>,
+    vss_ephemeral: Vec<SerializableVerifiableSS>,
+) -> napi::Result<SerializablePoint> {
+    crate::guard::guard("signer_construct", || {
+        let (round, signer) = round_context(&round_id)?;
+        let signers = round
+            .signers
+            .ok_or_else(|| out_of_order("signer_distribute"))?;
+        let eph_shared_keys = threshold_sig::ephemeral_phase2_verify_vss_construct_keypair(
+            round.eph_key_id,
+            signer.threshold,
+            signers.len() as u16,
+            R_points,
+            secret_shares,
+            vss_ephemeral,
+            signer.party_index,
+            None,
+        )?;
+        let R = eph_shared_keys.R.clone();
+        update_round(&round_id, |round| {
+            round.eph_shared_keys = Some(eph_shared_keys)
+        })?;
+        Ok(R)
+    })
+}
+
+/// Round step 4: this signer's local signature
+#[napi_derive::napi]
+pub fn signer_local_sig(round_id: String) -> napi::Result<SerializableLocalSig> {
+    crate::guard::guard("signer_local_sig", || {
+        let (round, signer) = round_context(&round_id)?;
+        let eph_shared_keys = round
+            .eph_shared_keys
+            .ok_or_else(|| out_of_order("signer_construct"))?;
+        let shared_keys = threshold_sig::get_shared_keys(signer.key_id)?;
+        threshold_sig::compute_local_sig(
+            round.message,
+            eph_shared_keys,
+            shared_keys,
+            None,
+            round.hash,
+        )
+    })
+}
+
+/// Verify every signer's local signature (ordered like the signer set) and aggregate them,
+/// using the keygen VSS schemes held by the signer
+#[napi_derive::napi]
+pub fn signer_aggregate(
+    round_id: String,
+    local_sigs: Vec<SerializableLocalSig>,
+    vss_ephemeral: Vec<SerializableVerifiableSS>,
+) -> napi::Result<SerializableSignature> {
+    crate::guard::guard("signer_aggregate", || {
+        let (round, signer) = round_context(&round_id)?;
+        let signers = round
+            .signers
+            .ok_or_else(|| out_of_order("signer_distribute"))?;
+        let eph_shared_keys = round
+            .eph_shared_keys
+            .ok_or_else(|| out_of_order("signer_construct"))?;
+        threshold_sig::aggregate_signature(
+            local_sigs,
+            signers.iter().map(|p| p - 1).collect(),
+            signer.vss_schemes,
+            vss_ephemeral,
+            eph_shared_keys.R,
+        )
+    })
+}
@@ -0,0 +1,599 @@
+use curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use curv::elliptic::curves::{Ed25519, Scalar};
+#[cfg(not(feature = "noop"))]
+use napi::bindgen_prelude::AsyncTask;
+use napi::Status;
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+
+use crate::abort::AbortSignal;
+use crate::threshold_sig;
+use crate::{
+    check_non_empty, check_participants, check_sharing_params, scalar_to_serializable,
+    serializable_to_scalar, SerializableBigInt, SerializablePoint, SerializableScalar,
+    SerializableSharedKeys, SerializableSignature, SerializableVerifiableSS,
+};
+
+// Dev/test drivers that play every party of a protocol in this one process, so
+// all the secret shares meet in one place. They exist to exercise the round
+// functions end to end and must never stand in for a distributed deployment.
+
+/// Output of `simulate_keygen`: the stored key of each party 1..=n, in order
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableSimulatedKeygen {
+    pub threshold: u16,
+    #[serde(rename = "key_ids")]
+    #[napi(js_name = "keyIds")]
+    pub key_ids: Vec<String>,
+    #[serde(rename = "shared_keys")]
+    #[napi(js_name = "sharedKeys")]
+    pub shared_keys: Vec<SerializableSharedKeys>,
+    #[serde(rename = "vss_schemes")]
+    #[napi(js_name = "vssSchemes")]
+    pub vss_schemes: Vec<SerializableVerifiableSS>,
+    pub y: SerializablePoint,
+}
+
+fn field<T: serde::de::DeserializeOwned>(value: &serde_json::Value, name: &str) -> napi::Result<T> {
+    serde_json::from_value(value[name].clone()).map_err(|e| {
+        napi::Error::new(
+            Status::GenericFailure,
+            format!("Unexpected {} in round output: {}", name, e),
+        )
+    })
+}
+
+fn broadcast_parts(
+    broadcast: serde_json::Value,
+) -> napi::Result<(SerializableBigInt, SerializableBigInt)> {
+    Ok((
+        field(&broadcast, "commitment")?,
+        field(&broadcast, "blind_factor")?,
+    ))
+}
+
+fn distribute_parts(
+    distribute: serde_json::Value,
+) -> napi::Result<(SerializableVerifiableSS, Vec<SerializableScalar>)> {
+    Ok((
+        field(&distribute, "vss")?,
+        field(&distribute, "secret_shares")?,
+    ))
+}
+
+// Shares sent to party i, taken from every party's distribution in order
+fn received_by(secret_shares: &[Vec<SerializableScalar>], i: usize) -> Vec<SerializableScalar> {
+    secret_shares
+        .iter()
+        .map(|shares| shares[i].clone())
+        .collect()
+}
+
+// Keygen for parties 1..=n, whose parameters the caller has checked
+fn run_keygen(
+    threshold: u16,
+    share_count: u16,
+    parties: &[u16],
+    abort: &AbortSignal,
+    operation: &'static str,
+) -> napi::Result<SerializableSimulatedKeygen> {
+    let key_ids = parties
+        .iter()
+        .map(|&i| {
+            abort.check(operation)?;
+            threshold_sig::phase1_create(i)
+        })
+        .collect::<napi::Result<Vec<_>>>()?;
+    let public_keys = key_ids
+        .iter()
+        .map(|id| threshold_sig::get_public_key(id.clone()))
+        .collect::<napi::Result<Vec<_>>>()?;
+    let (commitments, blind_factors): (Vec<_>, Vec<_>) = key_ids
+        .iter()
+        .map(|id| {
+            abort.check(operation)?;
+            broadcast_parts(threshold_sig::phase1_broadcast(id.clone(), None)?)
+        })
+        .collect::<napi::Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+    let (vss_schemes, secret_shares): (Vec<_>, Vec<_>) = key_ids
+        .iter()
+        .map(|id| {
+            abort.check(operation)?;
+            distribute_parts(threshold_sig::phase1_verify_com_phase2_distribute(
+                id.clone(),
+                threshold,
+                share_count,
+                blind_factors.clone(),
+                public_keys.clone(),
+                commitments.clone(),
+                parties.to_vec(),
+                None,
+                None,
+            )?)
+        })
+        .collect::<napi::Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+    let shared_keys = key_ids
+        .iter()
+        .enumerate()
+        .map(|(i, id)| {
+            abort.check(operation)?;
+            threshold_sig::phase2_verify_vss_construct_keypair(
+                id.clone(),
+                threshold,
+                share_count,
+                public_keys.clone(),
+                received_by(&secret_shares, i),
+                vss_schemes.clone(),
+                parties[i],
+                None,
+            )
+        })
+        .collect::<napi::Result<Vec<_>>>()?;
+    let y = shared_keys[0].y.clone();
+    Ok(SerializableSimulatedKeygen {
+        threshold,
+        key_ids,
+        shared_keys,
+        vss_schemes,
+        y,
+    })
+}
+
+// The drivers below run as napi async tasks on the libuv thread pool and resolve a
+// Promise, so the JS thread stays free to `abort` them while they run. The abort token
+// is resolved when the task is created, on the calling thread. The `noop` build has no
+// JS values to resolve to, so there the drivers are only reachable through `run`.
+macro_rules! impl_task {
+    ($task:ty => $output:ty) => {
+        #[cfg(not(feature = "noop"))]
+        impl napi::Task for $task {
+            type Output = $output;
+            type JsValue = $output;
+
+            fn compute(&mut self) -> napi::Result<$output> {
+                self.run()
+            }
+
+            fn resolve(&mut self, _env: napi::Env, output: $output) -> napi::Result<$output> {
+                Ok(output)
+            }
+        }
+    };
+}
+
+/// NOT DISTRIBUTED: run a full t-of-n keygen for parties 1..=n in-process, for tests and
+/// local tooling, resolving once it is done. Aborting `abort_token` stops it before the next
+/// party's step.
+#[cfg(not(feature = "noop"))]
+#[napi(ts_return_type = "Promise<SerializableSimulatedKeygen>")]
+pub fn simulate_keygen(
+    threshold: u16,
+    share_count: u16,
+    abort_token: Option<String>,
+) -> napi::Result<AsyncTask<SimulateKeygen>> {
+    crate::guard::guard("simulate_keygen", || {
+        Ok(AsyncTask::new(SimulateKeygen::new(
+            threshold,
+            share_count,
+            abort_token,
+        )?))
+    })
+}
+
+pub struct SimulateKeygen {
+    threshold: u16,
+    share_count: u16,
+    abort: AbortSignal,
+}
+
+impl SimulateKeygen {
+    pub(crate) fn new(
+        threshold: u16,
+        share_count: u16,
+        abort_token: Option<String>,
+    ) -> napi::Result<Self> {
+        Ok(SimulateKeygen {
+            threshold,
+            share_count,
+            abort: AbortSignal::from_token(abort_token)?,
+        })
+    }
+
+    pub(crate) fn run(&self) -> napi::Result<SerializableSimulatedKeygen> {
+        crate::guard::guard("simulate_keygen", || {
+            threshold_sig::check_share_count(usize::from(self.share_count))?;
+            let parties: Vec<u16> = (1..=self.share_count).collect();
+            check_sharing_params(self.threshold, self.share_count, &parties)?;
+            run_keygen(
+                self.threshold,
+                self.share_count,
+                &parties,
+                &self.abort,
+                "simulate_keygen",
+            )
+        })
+    }
+}
+
+impl_task!(SimulateKeygen => SerializableSimulatedKeygen);
+
+/// NOT DISTRIBUTED: `count` independent `simulate_keygen` runs in one task, for bulk
+/// provisioning in dev and test setups. Each wallet's keys are moved to fresh `batch_` ids
+/// so they do not overwrite one another, and none is left under `key_id_for_party`.
+/// Aborting `abort_token` stops it before the next party's step.
+#[cfg(not(feature = "noop"))]
+#[napi(ts_return_type = "Promise<Array<SerializableSimulatedKeygen>>")]
+pub fn batch_keygen(
+    count: u32,
+    threshold: u16,
+    share_count: u16,
+    abort_token: Option<String>,
+) -> napi::Result<AsyncTask<BatchKeygen>> {
+    crate::guard::guard("batch_keygen", || {
+        Ok(AsyncTask::new(BatchKeygen::new(
+            count,
+            threshold,
+            share_count,
+            abort_token,
+        )?))
+    })
+}
+
+pub struct BatchKeygen {
+    count: u32,
+    threshold: u16,
+    share_count: u16,
+    abort: AbortSignal,
+}
+
+impl BatchKeygen {
+    pub(crate) fn new(
+        count: u32,
+        threshold: u16,
+        share_count: u16,
+        abort_token: Option<String>,
+    ) -> napi::Result<Self> {
+        Ok(BatchKeygen {
+            count,
+            threshold,
+            share_count,
+            abort: AbortSignal::from_token(abort_token)?,
+        })
+    }
+
+    pub(crate) fn run(&self) -> napi::Result<Vec<SerializableSimulatedKeygen>> {
+        crate::guard::guard("batch_keygen", || {
+            threshold_sig::check_share_count(usize::from(self.share_count))?;
+            let parties: Vec<u16> = (1..=self.share_count).collect();
+            check_sharing_params(self.threshold, self.share_count, &parties)?;
+            (0..self.count)
+                .map(|_| {
+                    let mut keygen = run_keygen(
+                        self.threshold,
+                        self.share_count,
+                        &parties,
+                        &self.abort,
+                        "batch_keygen",
+                    )?;
+                    keygen.key_ids = keygen
+                        .key_ids
+                        .iter()
+                        .map(|id| threshold_sig::move_key_to_batch_id(id))
+                        .collect::<napi::Result<Vec<_>>>()?;
+                    Ok(keygen)
+                })
+                .collect()
+        })
+    }
+}
+
+impl_task!(BatchKeygen => Vec<SerializableSimulatedKeygen>);
+
+/// NOT DISTRIBUTED: sign `message` in-process with the keys of `simulate_keygen` held by the
+/// 0-based `parties_index`, running the ephemeral keygen, local signatures, their
+/// verification and aggregation. The signature is verified against `y` before the task
+/// resolves with it. Aborting `abort_token` stops it before the next signer's step.
+#[cfg(not(feature = "noop"))]
+#[napi(ts_return_type = "Promise<SerializableSignature>")]
+pub fn simulate_sign(
+    keygen_result: SerializableSimulatedKeygen,
+    parties_index: Vec<u16>,
+    message: Vec<u8>,
+    abort_token: Option<String>,
+) -> napi::Result<AsyncTask<SimulateSign>> {
+    crate::guard::guard("simulate_sign", || {
+        Ok(AsyncTask::new(SimulateSign::new(
+            keygen_result,
+            parties_index,
+            message,
+            abort_token,
+        )?))
+    })
+}
+
+pub struct SimulateSign {
+    keygen_result: SerializableSimulatedKeygen,
+    parties_index: Vec<u16>,
+    message: Vec<u8>,
+    abort: AbortSignal,
+}
+
+impl SimulateSign {
+    pub(crate) fn new(
+        keygen_result: SerializableSimulatedKeygen,
+        parties_index: Vec<u16>,
+        message: Vec<u8>,
+        abort_token: Option<String>,
+    ) -> napi::Result<Self> {
+        Ok(SimulateSign {
+            keygen_result,
+            parties_index,
+            message,
+            abort: AbortSignal::from_token(abort_token)?,
+        })
+    }
+
+    pub(crate) fn run(&self) -> napi::Result<SerializableSignature> {
+        crate::guard::guard("simulate_sign", || {
+            let (keygen_result, abort) = (&self.keygen_result, &self.abort);
+            let (parties_index, message) = (self.parties_index.clone(), self.message.clone());
+            let threshold = keygen_result.threshold;
+            check_participants(&parties_index, threshold)?;
+            if let Some(&i) = parties_index
+                .iter()
+                .find(|&&i| usize::from(i) >= keygen_result.key_ids.len())
+            {
+                return Err(napi::Error::new(
+                    Status::InvalidArg,
+                    format!("Party index {} out of range", i),
+                ));
+            }
+            let signers: Vec<u16> = parties_index.iter().map(|i| i + 1).collect();
+            let signer_count = signers.len() as u16;
+            let eph_ids = parties_index
+                .iter()
+                .zip(&signers)
+                .map(|(&i, &p)| {
+                    abort.check("simulate_sign")?;
+                    threshold_sig::ephemeral_key_create(
+                        keygen_result.key_ids[usize::from(i)].clone(),
+                        message.clone(),
+                        p,
+                        None,
+                        None,
+                        None,
+                    )
+                })
+                .collect::<napi::Result<Vec<_>>>()?;
+            let R_points = eph_ids
+                .iter()
+                .map(|id| threshold_sig::get_ephemeral_R(id.clone()))
+                .collect::<napi::Result<Vec<_>>>()?;
+            let (commitments, blind_factors): (Vec<_>, Vec<_>) = eph_ids
+                .iter()
+                .map(|id| {
+                    abort.check("simulate_sign")?;
+                    broadcast_parts(threshold_sig::ephemeral_phase1_broadcast(id.clone())?)
+                })
+                .collect::<napi::Result<Vec<_>>>()?
+                .into_iter()
+                .unzip();
+            let (vss_ephemeral, secret_shares): (Vec<_>, Vec<_>) = eph_ids
+                .iter()
+                .map(|id| {
+                    abort.check("simulate_sign")?;
+                    distribute_parts(
+                        threshold_sig::ephemeral_phase1_verify_com_phase2_distribute(
+                            id.clone(),
+                            threshold,
+                            signer_count,
+                            blind_factors.clone(),
+                            R_points.clone(),
+                            commitments.clone(),
+                            signers.clone(),
+                            None,
+                        )?,
+                    )
+                })
+                .collect::<napi::Result<Vec<_>>>()?
+                .into_iter()
+                .unzip();
+            let eph_shared_keys = eph_ids
+                .iter()
+                .enumerate()
+                .map(|(i, id)| {
+                    abort.check("simulate_sign")?;
+                    threshold_sig::ephemeral_phase2_verify_vss_construct_keypair(
+                        id.clone(),
+                        threshold,
+                        signer_count,
+                        R_points.clone(),
+                        received_by(&secret_shares, i),
+                        vss_ephemeral.clone(),
+                        signers[i],
+                        None,
+                    )
+                })
+                .collect::<napi::Result<Vec<_>>>()?;
+            // every signer derives the same R
+            let R = eph_shared_keys[0].R.clone();
+            let local_sigs = eph_shared_keys
+                .into_iter()
+                .zip(&parties_index)
+                .map(|(eph_shared_keys, &i)| {
+                    abort.check("simulate_sign")?;
+                    threshold_sig::compute_local_sig(
+                        message.clone(),
+                        eph_shared_keys,
+                        keygen_result.shared_keys[usize::from(i)].clone(),
+                        None,
+                        None,
+                    )
+                })
+                .collect::<napi::Result<Vec<_>>>()?;
+            let vss_sum = threshold_sig::verify_local_sigs(
+                local_sigs.clone(),
+                parties_index.clone(),
+                keygen_result.vss_schemes.clone(),
+                vss_ephemeral,
+            )?;
+            threshold_sig::generate_signature(
+                vss_sum,
+                local_sigs,
+                parties_index,
+                R,
+                Some(true),
+                Some(keygen_result.y.clone()),
+                Some(message),
+                None,
+            )
+        })
+    }
+}
+
+impl_task!(SimulateSign => SerializableSignature);
+
+// Sign `message` with every key in `key_ids`, using the party indices they were created
+// with. The nonce is shared (m - 1)-of-m among the m signers and the local signatures are
+// interpolated over all of them, so no threshold or keygen VSS is needed: the result only
+// verifies if the shares lie on one polynomial of degree below m. None if the keys do not
+// all share one y.
+fn sign_with_keys(
+    key_ids: &[String],
+    message: &[u8],
+) -> napi::Result<Option<(SerializablePoint, SerializableSignature)>> {
+    threshold_sig::check_share_count(key_ids.len())?;
+    let signers = key_ids
+        .iter()
+        .map(|id| Ok(threshold_sig::get_key_metadata(id.clone())?.party_index))
+        .collect::<napi::Result<Vec<_>>>()?;
+    let shared_keys = key_ids
+        .iter()
+        .map(|id| threshold_sig::get_shared_keys(id.clone()))
+        .collect::<napi::Result<Vec<_>>>()?;
+    let y = shared_keys[0].y.clone();
+    if shared_keys.iter().any(|keys| keys.y.bytes != y.bytes) {
+        return Ok(None);
+    }
+    let signer_count = signers.len() as u16;
+    let threshold = signer_count - 1;
+    let eph_ids = key_ids
+        .iter()
+        .zip(&signers)
+        .map(|(id, &p)| {
+            threshold_sig::ephemeral_key_create(id.clone(), message.to_vec(), p, None, None, None)
+        })
+        .collect::<napi::Result<Vec<_>>>()?;
+    let R_points = eph_ids
+        .iter()
+        .map(|id| threshold_sig::get_ephemeral_R(id.clone()))
+        .collect::<napi::Result<Vec<_>>>()?;
+    let (commitments, blind_factors): (Vec<_>, Vec<_>) = eph_ids
+        .iter()
+        .map(|id| broadcast_parts(threshold_sig::ephemeral_phase1_broadcast(id.clone())?))
+        .collect::<napi::Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+    let (vss_ephemeral, secret_shares): (Vec<_>, Vec<_>) = eph_ids
+        .iter()
+        .map(|id| {
+            distribute_parts(
+                threshold_sig::ephemeral_phase1_verify_com_phase2_distribute(
+                    id.clone(),
+                    threshold,
+                    signer_count,
+                    blind_factors.clone(),
+                    R_points.clone(),
+                    commitments.clone(),
+                    signers.clone(),
+                    None,
+                )?,
+            )
+        })
+        .collect::<napi::Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+    let eph_shared_keys = eph_ids
+        .iter()
+        .enumerate()
+        .map(|(i, id)| {
+            threshold_sig::ephemeral_phase2_verify_vss_construct_keypair(
+                id.clone(),
+                threshold,
+                signer_count,
+                R_points.clone(),
+                received_by(&secret_shares, i),
+                vss_ephemeral.clone(),
+                signers[i],
+                None,
+            )
+        })
+        .collect::<napi::Result<Vec<_>>>()?;
+    let R = eph_shared_keys[0].R.clone();
+    let gammas = eph_shared_keys
+        .into_iter()
+        .zip(shared_keys)
+        .map(|(eph_shared_keys, shared_keys)| {
+            let local_sig = threshold_sig::compute_local_sig(
+                message.to_vec(),
+                eph_shared_keys,
+                shared_keys,
+                None,
+                None,
+            )?;
+            serializable_to_scalar(&local_sig.gamma_i)
+        })
+        .collect::<napi::Result<Vec<_>>>()?;
+    let points: Vec<Scalar<Ed25519>> = signers.iter().map(|&p| Scalar::from(p)).collect();
+    let s = VerifiableSS::<Ed25519>::lagrange_interpolation_at_zero(&points, &gammas);
+    Ok(Some((
+        y,
+        SerializableSignature {
+            R,
+            s: scalar_to_serializable(&s),
+        },
+    )))
+}
+
+/// NOT DISTRIBUTED: whether two sets of constructed keys, such as the shares before and after
+/// a reshare or threshold change, sign as the same key. Each set signs `reference_message`
+/// in-process and both signatures must verify under one y. Each set must hold more than
+/// threshold shares of its sharing; a set that is too small, whose keys disagree on y, or
+/// whose shares do not interpolate to the secret behind y, gives false.
+#[napi]
+pub fn verify_reshare_equivalence(
+    old_shared_key_ids: Vec<String>,
+    new_shared_key_ids: Vec<String>,
+    reference_message: Vec<u8>,
+) -> napi::Result<bool> {
+    crate::guard::guard("verify_reshare_equivalence", || {
+        check_non_empty("old_shared_key_ids", old_shared_key_ids.len())?;
+        check_non_empty("new_shared_key_ids", new_shared_key_ids.len())?;
+        let Some((old_y, old_signature)) = sign_with_keys(&old_shared_key_ids, &reference_message)?
+        else {
+            return Ok(false);
+        };
+        let Some((new_y, new_signature)) = sign_with_keys(&new_shared_key_ids, &reference_message)?
+        else {
+            return Ok(false);
+        };
+        if old_y.bytes != new_y.bytes {
+            return Ok(false);
+        }
+        let verifies = |signature| {
+            threshold_sig::verify_signature(
+                signature,
+                reference_message.clone(),
+                old_y.clone(),
+                None,
+                None,
+            )
+        };
+        Ok(verifies(old_signature)? && verifies(new_signature)?)
+    })
+}
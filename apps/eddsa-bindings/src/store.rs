@@ -0,0 +1,174 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard};
+
+/// Lock one of the process-wide stores, recovering the guard if a thread
+/// panicked while holding it. Stores are only mutated through whole inserts
+/// and removals, so a poisoned store is still consistent, and one errant
+/// call must not brick every later one.
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Durable storage behind a `Store`, keyed by entry id.
+pub(crate) trait Backend<V>: Send {
+    fn load(&self, id: &str) -> napi::Result<Option<V>>;
+    fn save(&self, id: &str, value: &V) -> napi::Result<()>;
+    fn delete(&self, id: &str) -> napi::Result<()>;
+}
+
+/// Id-keyed map backing the key stores, with an optional LRU capacity bound.
+///
+/// Every insert and lookup stamps the entry with a logical clock; once the
+/// store holds more than `capacity` entries the least-recently-used ones are
+/// dropped. Dropping a value zeroizes its curv scalars, so evicted secret
+/// material does not linger in memory.
+///
+/// With a backend attached, lookups that miss in memory fall through to it,
+/// and inserts, mutable lookups and removals are queued for `flush`. Entries
+/// waiting to be flushed are never evicted. `ids` only lists entries in memory.
+pub(crate) struct Store<V> {
+    entries: HashMap<String, (V, u64)>,
+    clock: u64,
+    // 0 means unbounded
+    capacity: usize,
+    backend: Option<Box<dyn Backend<V>>>,
+    dirty: HashSet<String>,
+    removed: HashSet<String>,
+}
+
+impl<V> Store<V> {
+    pub(crate) fn new() -> Self {
+        Store {
+            entries: HashMap::new(),
+            clock: 0,
+            capacity: 0,
+            backend: None,
+            dirty: HashSet::new(),
+            removed: HashSet::new(),
+        }
+    }
+
+    /// Persist through `backend` from now on; entries already in memory are
+    /// written on the next flush.
+    #[cfg_attr(not(feature = "persistent-store"), allow(dead_code))]
+    pub(crate) fn set_backend(&mut self, backend: Box<dyn Backend<V>>) {
+        self.backend = Some(backend);
+        self.dirty = self.entries.keys().cloned().collect();
+    }
+
+    /// Write queued changes to the backend, if there is one.
+    #[cfg_attr(not(feature = "persistent-store"), allow(dead_code))]
+    pub(crate) fn flush(&mut self) -> napi::Result<()> {
+        let Some(backend) = &self.backend else {
+            return Ok(());
+        };
+        while let Some(id) = self.removed.iter().next().cloned() {
+            backend.delete(&id)?;
+            self.removed.remove(&id);
+        }
+        while let Some(id) = self.dirty.iter().next().cloned() {
+            if let Some((value, _)) = self.entries.get(&id) {
+                backend.save(&id, value)?;
+            }
+            self.dirty.remove(&id);
+        }
+        Ok(())
+    }
+
+    // Bring a persisted entry into memory. A record that fails to load is
+    // treated as missing; the backend authenticates records, so it is unusable.
+    fn load(&mut self, id: &str) {
+        if self.entries.contains_key(id) || self.removed.contains(id) {
+            return;
+        }
+        if let Some(Ok(Some(value))) = self.backend.as_ref().map(|backend| backend.load(id)) {
+            let stamp = self.tick();
+            self.entries.insert(id.to_string(), (value, stamp));
+        }
+    }
+
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    /// Set the capacity (0 disables the bound) and evict down to it.
+    /// Entries for which `is_pinned` returns true are never evicted.
+    pub(crate) fn set_capacity(&mut self, capacity: usize, is_pinned: impl Fn(&str) -> bool) {
+        self.capacity = capacity;
+        self.evict(None, is_pinned);
+    }
+
+    pub(crate) fn insert(&mut self, id: String, value: V, is_pinned: impl Fn(&str) -> bool) {
+        let stamp = self.tick();
+        self.entries.insert(id.clone(), (value, stamp));
+        if self.backend.is_some() {
+            self.removed.remove(&id);
+            self.dirty.insert(id.clone());
+        }
+        self.evict(Some(&id), is_pinned);
+    }
+
+    /// Look up an entry, marking it as most recently used.
+    pub(crate) fn get(&mut self, id: &str) -> Option<&V> {
+        self.load(id);
+        let stamp = self.tick();
+        self.entries.get_mut(id).map(|(value, last_used)| {
+            *last_used = stamp;
+            &*value
+        })
+    }
+
+    /// Look up an entry mutably, marking it as most recently used.
+    pub(crate) fn get_mut(&mut self, id: &str) -> Option<&mut V> {
+        self.load(id);
+        if self.backend.is_some() && self.entries.contains_key(id) {
+            self.dirty.insert(id.to_string());
+        }
+        let stamp = self.tick();
+        self.entries.get_mut(id).map(|(value, last_used)| {
+            *last_used = stamp;
+            value
+        })
+    }
+
+    pub(crate) fn remove(&mut self, id: &str) -> Option<V> {
+        self.load(id);
+        if self.backend.is_some() {
+            self.dirty.remove(id);
+            self.removed.insert(id.to_string());
+        }
+        self.entries.remove(id).map(|(value, _)| value)
+    }
+
+    pub(crate) fn ids(&self) -> impl Iterator<Item = &String> {
+        self.entries.keys()
+    }
+
+    // Drop least-recently-used entries until the store fits its capacity. The
+    // entry that was just inserted and pinned entries are skipped; if nothing
+    // else is evictable the store is left temporarily over capacity.
+    fn evict(&mut self, keep: Option<&str>, is_pinned: impl Fn(&str) -> bool) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self.entries.len() > self.capacity {
+            let victim = self
+                .entries
+                .iter()
+                .filter(|(id, _)| {
+                    Some(id.as_str()) != keep && !is_pinned(id) && !self.dirty.contains(*id)
+                })
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(id, _)| id.clone());
+            match victim {
+                Some(id) => {
+                    self.entries.remove(&id);
+                }
+                None => break,
+            }
+        }
+    }
+}
@@ -0,0 +1,4743 @@
+#[cfg(test)]
+mod tests {
+    use crate::store::{lock, Store};
+    use crate::threshold_sig;
+    use crate::{
+        SerializableBigInt, SerializableEphemeralSharedKeys, SerializableKeygenMessage1,
+        SerializableKeygenMessage2, SerializableLocalSig, SerializablePoint, SerializableScalar,
+        SerializableSharedKeys, SerializableSignature, SerializableVerifiableSS,
+    };
+    use curv::elliptic::curves::{Ed25519, Scalar};
+    use std::sync::{Mutex, MutexGuard};
+
+    // The key stores are process-wide, so tests that touch them run one at a
+    // time and each starts from empty stores.
+    fn serial() -> MutexGuard<'static, ()> {
+        static LOCK: Mutex<()> = Mutex::new(());
+        let guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        *lock(threshold_sig::keys_store()) = Store::new();
+        *lock(threshold_sig::ephemeral_keys_store()) = Store::new();
+        *lock(threshold_sig::phase1_sessions_store()) = Store::new();
+        *lock(threshold_sig::ephemeral_messages_store()) = Store::new();
+        *lock(threshold_sig::phase2_sessions_store()) = Store::new();
+        *lock(crate::signer::signers_store()) = Store::new();
+        *lock(crate::signer::signer_rounds_store()) = Store::new();
+        *lock(crate::verify_key::verify_keys_store()) = Store::new();
+        *lock(crate::abort::abort_tokens_store()) = Store::new();
+        guard
+    }
+
+    pub struct Keygen {
+        pub key_ids: Vec<String>,
+        pub shared_keys: Vec<SerializableSharedKeys>,
+        pub vss_schemes: Vec<SerializableVerifiableSS>,
+        pub y: SerializablePoint,
+    }
+
+    pub struct SigningRound {
+        pub signers: Vec<u16>,
+        pub eph_shared_keys: Vec<SerializableEphemeralSharedKeys>,
+        pub local_sigs: Vec<SerializableLocalSig>,
+        pub parties_index: Vec<u16>,
+        pub vss_ephemeral: Vec<SerializableVerifiableSS>,
+        pub R: SerializablePoint,
+    }
+
+    fn broadcast_parts(broadcast: serde_json::Value) -> (SerializableBigInt, SerializableBigInt) {
+        (
+            serde_json::from_value(broadcast["commitment"].clone()).unwrap(),
+            // the deterministic commitment scheme has no blind factor
+            serde_json::from_value(broadcast["blind_factor"].clone())
+                .unwrap_or(SerializableBigInt { bytes: vec![] }),
+        )
+    }
+
+    fn distribute_parts(
+        distribute: serde_json::Value,
+    ) -> (SerializableVerifiableSS, Vec<SerializableScalar>) {
+        (
+            serde_json::from_value(distribute["vss"].clone()).unwrap(),
+            serde_json::from_value(distribute["secret_shares"].clone()).unwrap(),
+        )
+    }
+
+    /// Run a full t-of-n keygen for parties 1..=n through the napi surface.
+    pub fn keygen(t: u16, n: u16) -> Keygen {
+        keygen_with_scheme(t, n, threshold_sig::CommitmentScheme::HashCommitment)
+    }
+
+    pub fn keygen_with_scheme(t: u16, n: u16, scheme: threshold_sig::CommitmentScheme) -> Keygen {
+        let parties: Vec<u16> = (1..=n).collect();
+        let key_ids: Vec<String> = parties
+            .iter()
+            .map(|&i| threshold_sig::phase1_create(i).unwrap())
+            .collect();
+        let public_keys: Vec<SerializablePoint> = key_ids
+            .iter()
+            .map(|id| threshold_sig::get_public_key(id.clone()).unwrap())
+            .collect();
+        let (commitments, blind_factors): (Vec<_>, Vec<_>) = key_ids
+            .iter()
+            .map(|id| {
+                broadcast_parts(threshold_sig::phase1_broadcast(id.clone(), Some(scheme)).unwrap())
+            })
+            .unzip();
+        let (vss_schemes, secret_shares): (Vec<_>, Vec<_>) = key_ids
+            .iter()
+            .map(|id| {
+                distribute_parts(
+                    threshold_sig::phase1_verify_com_phase2_distribute(
+                        id.clone(),
+                        t,
+                        n,
+                        blind_factors.clone(),
+                        public_keys.clone(),
+                        commitments.clone(),
+                        parties.clone(),
+                        Some(scheme),
+                        None,
+                    )
+                    .unwrap(),
+                )
+            })
+            .unzip();
+        let shared_keys: Vec<SerializableSharedKeys> = key_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                let received: Vec<SerializableScalar> = secret_shares
+                    .iter()
+                    .map(|shares| shares[i].clone())
+                    .collect();
+                threshold_sig::phase2_verify_vss_construct_keypair(
+                    id.clone(),
+                    t,
+                    n,
+                    public_keys.clone(),
+                    received,
+                    vss_schemes.clone(),
+                    parties[i],
+                    None,
+                )
+                .unwrap()
+            })
+            .collect();
+        let y = shared_keys[0].y.clone();
+        Keygen {
+            key_ids,
+            shared_keys,
+            vss_schemes,
+            y,
+        }
+    }
+
+    /// Run ephemeral keygen and local signing for the given 1-based signer points.
+    pub fn sign_round(keygen: &Keygen, t: u16, signers: &[u16], message: &[u8]) -> SigningRound {
+        sign_round_with_hash(keygen, t, signers, message, None)
+    }
+
+    pub fn sign_round_with_hash(
+        keygen: &Keygen,
+        t: u16,
+        signers: &[u16],
+        message: &[u8],
+        hash: Option<threshold_sig::ChallengeHash>,
+    ) -> SigningRound {
+        let m = signers.len() as u16;
+        let eph_ids: Vec<String> = signers
+            .iter()
+            .map(|&p| {
+                let key_id = keygen.key_ids[usize::from(p - 1)].clone();
+                threshold_sig::ephemeral_key_create(key_id, message.to_vec(), p, None, None, hash)
+                    .unwrap()
+            })
+            .collect();
+        let R_points: Vec<SerializablePoint> = eph_ids
+            .iter()
+            .map(|id| threshold_sig::get_ephemeral_R(id.clone()).unwrap())
+            .collect();
+        let (commitments, blind_factors): (Vec<_>, Vec<_>) = eph_ids
+            .iter()
+            .map(|id| {
+                broadcast_parts(threshold_sig::ephemeral_phase1_broadcast(id.clone()).unwrap())
+            })
+            .unzip();
+        let (vss_ephemeral, secret_shares): (Vec<_>, Vec<_>) = eph_ids
+            .iter()
+            .map(|id| {
+                distribute_parts(
+                    threshold_sig::ephemeral_phase1_verify_com_phase2_distribute(
+                        id.clone(),
+                        t,
+                        m,
+                        blind_factors.clone(),
+                        R_points.clone(),
+                        commitments.clone(),
+                        signers.to_vec(),
+                        None,
+                    )
+                    .unwrap(),
+                )
+            })
+            .unzip();
+        let eph_shared_keys: Vec<SerializableEphemeralSharedKeys> = eph_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                let received: Vec<SerializableScalar> = secret_shares
+                    .iter()
+                    .map(|shares| shares[i].clone())
+                    .collect();
+                threshold_sig::ephemeral_phase2_verify_vss_construct_keypair(
+                    id.clone(),
+                    t,
+                    m,
+                    R_points.clone(),
+                    received,
+                    vss_ephemeral.clone(),
+                    signers[i],
+                    None,
+                )
+                .unwrap()
+            })
+            .collect();
+        let local_sigs: Vec<SerializableLocalSig> = eph_shared_keys
+            .iter()
+            .zip(signers.iter())
+            .map(|(eph_shared, &p)| {
+                threshold_sig::compute_local_sig(
+                    message.to_vec(),
+                    eph_shared.clone(),
+                    keygen.shared_keys[usize::from(p - 1)].clone(),
+                    None,
+                    hash,
+                )
+                .unwrap()
+            })
+            .collect();
+        let R = eph_shared_keys[0].R.clone();
+        SigningRound {
+            signers: signers.to_vec(),
+            eph_shared_keys,
+            local_sigs,
+            parties_index: signers.iter().map(|p| p - 1).collect(),
+            vss_ephemeral,
+            R,
+        }
+    }
+
+    /// Verify the local signatures of a round and aggregate them.
+    pub fn aggregate(keygen: &Keygen, round: &SigningRound) -> SerializableSignature {
+        let vss_sum = threshold_sig::verify_local_sigs(
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            keygen.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap();
+        threshold_sig::generate_signature(
+            vss_sum,
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            round.R.clone(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap()
+    }
+
+    // The in-process drivers are napi async tasks; run them on this thread instead
+    fn simulate_keygen(
+        t: u16,
+        n: u16,
+        abort_token: Option<String>,
+    ) -> napi::Result<crate::simulate::SerializableSimulatedKeygen> {
+        crate::simulate::SimulateKeygen::new(t, n, abort_token)?.run()
+    }
+
+    fn batch_keygen(
+        count: u32,
+        t: u16,
+        n: u16,
+        abort_token: Option<String>,
+    ) -> napi::Result<Vec<crate::simulate::SerializableSimulatedKeygen>> {
+        crate::simulate::BatchKeygen::new(count, t, n, abort_token)?.run()
+    }
+
+    fn simulate_sign(
+        keygen_result: crate::simulate::SerializableSimulatedKeygen,
+        parties_index: Vec<u16>,
+        message: Vec<u8>,
+        abort_token: Option<String>,
+    ) -> napi::Result<SerializableSignature> {
+        crate::simulate::SimulateSign::new(keygen_result, parties_index, message, abort_token)?
+            .run()
+    }
+
+    #[test]
+    fn test_store_capacity_evicts_least_recently_used() {
+        let _guard = serial();
+        threshold_sig::set_store_capacity(2, 0).unwrap();
+
+        let first = threshold_sig::phase1_create(101).unwrap();
+        let second = threshold_sig::phase1_create(102).unwrap();
+        // touching the first key makes the second one the eviction candidate
+        threshold_sig::get_public_key(first.clone()).unwrap();
+        let third = threshold_sig::phase1_create(103).unwrap();
+
+        assert!(threshold_sig::get_public_key(first.clone()).is_ok());
+        assert!(threshold_sig::get_public_key(second).is_err());
+        assert!(threshold_sig::get_public_key(third.clone()).is_ok());
+
+        let fourth = threshold_sig::phase1_create(104).unwrap();
+        assert!(threshold_sig::get_public_key(first).is_err());
+        assert!(threshold_sig::get_public_key(third).is_ok());
+        assert!(threshold_sig::get_public_key(fourth).is_ok());
+
+        threshold_sig::set_store_capacity(0, 0).unwrap();
+    }
+
+    #[test]
+    fn test_store_capacity_keeps_keys_with_ephemeral_keys() {
+        let _guard = serial();
+        let base = keygen(1, 2).key_ids[0].clone();
+        threshold_sig::ephemeral_key_create(base.clone(), b"msg".to_vec(), 1, None, None, None)
+            .unwrap();
+        threshold_sig::set_store_capacity(1, 0).unwrap();
+
+        let other = threshold_sig::phase1_create(112).unwrap();
+
+        // the base key is pinned by its ephemeral key, so the store stays over capacity
+        assert!(threshold_sig::get_public_key(base).is_ok());
+        assert!(threshold_sig::get_public_key(other).is_ok());
+
+        threshold_sig::set_store_capacity(0, 0).unwrap();
+    }
+
+    #[test]
+    fn test_generate_signature_self_verify() {
+        let _guard = serial();
+        let message = b"self verify".to_vec();
+        let keygen = keygen(1, 2);
+        let round = sign_round(&keygen, 1, &[1, 2], &message);
+        let vss_sum = threshold_sig::verify_local_sigs(
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            keygen.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap();
+
+        let signature = threshold_sig::generate_signature(
+            vss_sum.clone(),
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            round.R.clone(),
+            Some(true),
+            Some(keygen.y.clone()),
+            Some(message.clone()),
+            None,
+        )
+        .unwrap();
+        assert!(threshold_sig::verify_signature(
+            signature,
+            message.clone(),
+            keygen.y.clone(),
+            None,
+            None,
+        )
+        .unwrap());
+
+        // checking against a message other than the one signed fails self-verification
+        let err = threshold_sig::generate_signature(
+            vss_sum,
+            round.local_sigs,
+            round.parties_index,
+            round.R,
+            Some(true),
+            Some(keygen.y.clone()),
+            Some(b"another message".to_vec()),
+            None,
+        )
+        .unwrap_err();
+        assert!(err.reason.contains("self-verification"));
+    }
+
+    #[test]
+    fn test_streamed_local_sig_matches_one_shot() {
+        use threshold_sig::ChallengeHash;
+
+        let _guard = serial();
+        let message = b"a message that arrives in three separate chunks".to_vec();
+        let keygen = keygen(1, 2);
+        for hash in [
+            None,
+            Some(ChallengeHash::Sha256),
+            Some(ChallengeHash::Keccak256),
+            Some(ChallengeHash::Blake2b),
+        ] {
+            let mut round = sign_round_with_hash(&keygen, 1, &[1, 2], &message, hash);
+            let one_shot = aggregate(&keygen, &round);
+
+            round.local_sigs = round
+                .eph_shared_keys
+                .iter()
+                .zip(round.signers.iter())
+                .map(|(eph_shared, &p)| {
+                    let hasher_id =
+                        threshold_sig::sig_hasher_new(round.R.clone(), keygen.y.clone(), hash)
+                            .unwrap();
+                    for chunk in message.chunks(17) {
+                        threshold_sig::sig_hasher_update(hasher_id.clone(), chunk.to_vec())
+                            .unwrap();
+                    }
+                    threshold_sig::compute_local_sig_streamed(
+                        hasher_id,
+                        eph_shared.clone(),
+                        keygen.shared_keys[usize::from(p - 1)].clone(),
+                    )
+                    .unwrap()
+                })
+                .collect();
+            let streamed = aggregate(&keygen, &round);
+
+            assert_eq!(streamed.R.bytes, one_shot.R.bytes);
+            assert_eq!(streamed.s.bytes, one_shot.s.bytes);
+            assert!(threshold_sig::verify_signature(
+                streamed,
+                message.clone(),
+                keygen.y.clone(),
+                None,
+                hash
+            )
+            .unwrap());
+        }
+    }
+
+    #[test]
+    fn test_is_canonical_scalar() {
+        let reduced = SerializableScalar {
+            bytes: Scalar::<Ed25519>::random().to_bytes().to_vec(),
+        };
+        assert!(threshold_sig::is_canonical_scalar(reduced).unwrap());
+
+        let unreduced = SerializableScalar {
+            bytes: vec![0xff; 32],
+        };
+        assert!(!threshold_sig::is_canonical_scalar(unreduced).unwrap());
+
+        let short = SerializableScalar { bytes: vec![1; 31] };
+        assert!(threshold_sig::is_canonical_scalar(short).is_err());
+    }
+
+    #[test]
+    fn test_strict_shares_rejects_non_canonical_share() {
+        let _guard = serial();
+        let key_id = threshold_sig::phase1_create(1).unwrap();
+        let public_key = threshold_sig::get_public_key(key_id.clone()).unwrap();
+        let vss = SerializableVerifiableSS {
+            threshold: 0,
+            share_count: 1,
+            commitments: vec![public_key.clone()],
+        };
+        let err = threshold_sig::phase2_verify_vss_construct_keypair(
+            key_id,
+            0,
+            1,
+            vec![public_key],
+            vec![SerializableScalar {
+                bytes: vec![0xff; 32],
+            }],
+            vec![vss],
+            1,
+            Some(true),
+        )
+        .unwrap_err();
+        assert!(err.reason.contains("not a canonical scalar"));
+    }
+
+    #[test]
+    fn test_key_phase_advances_and_rejects_out_of_order_calls() {
+        let _guard = serial();
+        let key_id = threshold_sig::phase1_create(1).unwrap();
+        assert_eq!(
+            threshold_sig::get_key_phase(key_id.clone()).unwrap(),
+            "Created"
+        );
+
+        let public_key = threshold_sig::get_public_key(key_id.clone()).unwrap();
+        let err = threshold_sig::phase1_verify_com_phase2_distribute(
+            key_id.clone(),
+            0,
+            1,
+            vec![],
+            vec![public_key],
+            vec![],
+            vec![1],
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(err
+            .reason
+            .contains("requires key keys_1 to have reached phase Broadcast"));
+        assert_eq!(threshold_sig::get_key_phase(key_id).unwrap(), "Created");
+
+        let keygen = keygen(1, 2);
+        for key_id in keygen.key_ids {
+            assert_eq!(
+                threshold_sig::get_key_phase(key_id).unwrap(),
+                "KeypairConstructed"
+            );
+        }
+    }
+
+    #[test]
+    fn test_local_sig_bytes_round_trip() {
+        let _guard = serial();
+        let message = b"wire format".to_vec();
+        let keygen = keygen(1, 2);
+        let mut round = sign_round(&keygen, 1, &[1, 2], &message);
+
+        round.local_sigs = round
+            .local_sigs
+            .iter()
+            .map(|local_sig| {
+                let bytes = threshold_sig::local_sig_to_bytes(local_sig.clone()).unwrap();
+                assert_eq!(bytes.len(), 64);
+                let decoded = threshold_sig::local_sig_from_bytes(bytes).unwrap();
+                assert_eq!(decoded.gamma_i.bytes, local_sig.gamma_i.bytes);
+                assert_eq!(decoded.k.bytes, local_sig.k.bytes);
+                decoded
+            })
+            .collect();
+        let signature = aggregate(&keygen, &round);
+        assert!(threshold_sig::verify_signature(signature, message, keygen.y, None, None).unwrap());
+    }
+
+    #[test]
+    fn test_local_sig_from_bytes_rejects_short_input() {
+        let err = threshold_sig::local_sig_from_bytes(vec![0; 63]).unwrap_err();
+        assert!(err.reason.contains("must be 64 bytes"));
+    }
+
+    #[test]
+    fn test_aggregation_rejects_insufficient_participants() {
+        let _guard = serial();
+        let message = b"too few".to_vec();
+        let keygen = keygen(1, 2);
+        let round = sign_round(&keygen, 1, &[1, 2], &message);
+
+        let err = threshold_sig::verify_local_sigs(
+            round.local_sigs[..1].to_vec(),
+            round.parties_index[..1].to_vec(),
+            keygen.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.reason,
+            "InsufficientParticipants: 1 parties provided, 2 required"
+        );
+
+        let vss_sum = threshold_sig::verify_local_sigs(
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            keygen.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap();
+        let err = threshold_sig::generate_signature(
+            vss_sum,
+            round.local_sigs[..1].to_vec(),
+            round.parties_index[..1].to_vec(),
+            round.R,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.reason,
+            "InsufficientParticipants: 1 parties provided, 2 required"
+        );
+    }
+
+    #[test]
+    fn test_aggregate_signature_matches_two_step_flow() {
+        let _guard = serial();
+        let message = b"verify then aggregate".to_vec();
+        let keygen = keygen(1, 3);
+        let mut round = sign_round(&keygen, 1, &[1, 3], &message);
+        let two_step = aggregate(&keygen, &round);
+
+        let combined = threshold_sig::aggregate_signature(
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            keygen.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+            round.R.clone(),
+        )
+        .unwrap();
+        assert_eq!(combined.R.bytes, two_step.R.bytes);
+        assert_eq!(combined.s.bytes, two_step.s.bytes);
+        assert!(
+            threshold_sig::verify_signature(combined, message, keygen.y.clone(), None, None)
+                .unwrap()
+        );
+
+        round.local_sigs[1].gamma_i = round.local_sigs[0].gamma_i.clone();
+        assert!(threshold_sig::aggregate_signature(
+            round.local_sigs,
+            round.parties_index,
+            keygen.vss_schemes,
+            round.vss_ephemeral,
+            round.R,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_compute_challenge_rfc8032_vector() {
+        // RFC 8032 section 7.1, TEST 1 (empty message)
+        let R = SerializablePoint {
+            bytes: hex::decode("e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155")
+                .unwrap(),
+        };
+        let public_key = SerializablePoint {
+            bytes: hex::decode("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+                .unwrap(),
+        };
+        let k = threshold_sig::compute_challenge(R, public_key, vec![], None).unwrap();
+        assert_eq!(
+            hex::encode(k.bytes),
+            "86eabc8e4c96193d290504e7c600df6cf8d8256131ec2c138a3e7e162e525404"
+        );
+    }
+
+    #[test]
+    fn test_seeded_rng_makes_runs_reproducible() {
+        let _guard = serial();
+        let message = b"reproducible".to_vec();
+        let run = || {
+            *lock(threshold_sig::keys_store()) = Store::new();
+            *lock(threshold_sig::ephemeral_keys_store()) = Store::new();
+            crate::rng::set_deterministic_rng(b"fuzz seed".to_vec()).unwrap();
+            let keygen = keygen(1, 2);
+            let round = sign_round(&keygen, 1, &[1, 2], &message);
+            let mut transcript = keygen.y.bytes.clone();
+            for (shared, vss) in keygen.shared_keys.iter().zip(&keygen.vss_schemes) {
+                transcript.extend(&shared.x_i.bytes);
+                vss.commitments
+                    .iter()
+                    .for_each(|c| transcript.extend(&c.bytes));
+            }
+            transcript.extend(&round.R.bytes);
+            round
+                .local_sigs
+                .iter()
+                .for_each(|sig| transcript.extend(&sig.gamma_i.bytes));
+            transcript
+        };
+        let first = run();
+        let second = run();
+        crate::rng::set_deterministic_rng(vec![]).unwrap();
+        assert_eq!(first, second);
+
+        let third = {
+            *lock(threshold_sig::keys_store()) = Store::new();
+            keygen(1, 2).y.bytes
+        };
+        assert_ne!(first[..32], third[..]);
+    }
+
+    #[test]
+    fn test_mismatched_R_is_rejected() {
+        let _guard = serial();
+        let message = b"wrong R".to_vec();
+        let keygen = keygen(1, 2);
+        let round = sign_round(&keygen, 1, &[1, 2], &message);
+        let vss_sum = threshold_sig::verify_local_sigs(
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            keygen.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap();
+        let wrong_R = keygen.y.clone();
+
+        let err = threshold_sig::generate_signature(
+            vss_sum.clone(),
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            wrong_R.clone(),
+            None,
+            Some(keygen.y.clone()),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(err.reason.starts_with("RInconsistent: "));
+        assert!(threshold_sig::generate_signature(
+            vss_sum,
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            round.R.clone(),
+            None,
+            Some(keygen.y.clone()),
+            None,
+            None,
+        )
+        .is_ok());
+
+        let err = threshold_sig::aggregate_signature(
+            round.local_sigs,
+            round.parties_index,
+            keygen.vss_schemes,
+            round.vss_ephemeral,
+            wrong_R,
+        )
+        .unwrap_err();
+        assert!(err.reason.starts_with("RInconsistent: "));
+    }
+
+    #[test]
+    fn test_compute_local_sigs_batch_matches_single_calls() {
+        let _guard = serial();
+        let keygen = keygen(1, 2);
+        let messages = vec![b"first".to_vec(), b"second".to_vec()];
+        let rounds: Vec<SigningRound> = messages
+            .iter()
+            .map(|message| sign_round(&keygen, 1, &[1, 2], message))
+            .collect();
+
+        let batch = threshold_sig::compute_local_sigs_batch(
+            messages.clone(),
+            rounds
+                .iter()
+                .map(|round| round.eph_shared_keys[0].clone())
+                .collect(),
+            keygen.shared_keys[0].clone(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(batch.len(), rounds.len());
+        for (sig, round) in batch.iter().zip(&rounds) {
+            assert_eq!(sig.gamma_i.bytes, round.local_sigs[0].gamma_i.bytes);
+            assert_eq!(sig.k.bytes, round.local_sigs[0].k.bytes);
+        }
+
+        let hash = Some(threshold_sig::ChallengeHash::Keccak256);
+        let round = sign_round_with_hash(&keygen, 1, &[1, 2], &messages[0], hash);
+        let batch = threshold_sig::compute_local_sigs_batch(
+            vec![messages[0].clone()],
+            vec![round.eph_shared_keys[0].clone()],
+            keygen.shared_keys[0].clone(),
+            hash,
+        )
+        .unwrap();
+        assert_eq!(batch[0].gamma_i.bytes, round.local_sigs[0].gamma_i.bytes);
+        assert_eq!(batch[0].k.bytes, round.local_sigs[0].k.bytes);
+
+        let err = threshold_sig::compute_local_sigs_batch(
+            messages,
+            vec![rounds[0].eph_shared_keys[0].clone()],
+            keygen.shared_keys[0].clone(),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(err.reason, "Got 2 messages but 1 ephemeral keys");
+    }
+
+    #[test]
+    fn test_point_cache_skips_repeated_decompression() {
+        use crate::point_cache::{decode_point, DECODES};
+        use curv::elliptic::curves::Point;
+
+        let point = Point::<Ed25519>::generator() * Scalar::<Ed25519>::random();
+        let bytes: [u8; 32] = point.to_bytes(true).to_vec().try_into().unwrap();
+        let before = DECODES.with(|decodes| decodes.get());
+        for _ in 0..100 {
+            assert_eq!(decode_point(&bytes).unwrap(), point);
+        }
+        assert_eq!(DECODES.with(|decodes| decodes.get()) - before, 1);
+    }
+
+    #[test]
+    fn test_cached_point_decode_matches_uncached() {
+        use crate::point_cache::decode_point;
+        use curv::elliptic::curves::Point;
+
+        let point = Point::<Ed25519>::generator() * Scalar::<Ed25519>::random();
+        let bytes: [u8; 32] = point.to_bytes(true).to_vec().try_into().unwrap();
+        let uncached = Point::<Ed25519>::from_bytes(&bytes).unwrap();
+        assert_eq!(decode_point(&bytes).unwrap(), uncached);
+        assert_eq!(decode_point(&bytes).unwrap(), uncached);
+
+        // invalid encodings are rejected every time rather than cached
+        let mut invalid = [0u8; 32];
+        invalid[0] = 2;
+        assert!(Point::<Ed25519>::from_bytes(&invalid).is_err());
+        assert!(decode_point(&invalid).is_none());
+        assert!(decode_point(&invalid).is_none());
+    }
+
+    #[test]
+    fn test_key_metadata_carries_party_index() {
+        let _guard = serial();
+        let key_id = threshold_sig::phase1_create(3).unwrap();
+        let metadata = threshold_sig::get_key_metadata(key_id.clone()).unwrap();
+        assert_eq!(metadata.party_index, 3);
+        assert_eq!(
+            metadata.public_key.bytes,
+            threshold_sig::get_public_key(key_id).unwrap().bytes
+        );
+        assert!(threshold_sig::get_key_metadata("keys_404".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_ephemeral_key_requires_completed_keygen() {
+        let _guard = serial();
+        let key_id = threshold_sig::phase1_create(1).unwrap();
+        let err =
+            threshold_sig::ephemeral_key_create(key_id, b"too early".to_vec(), 1, None, None, None)
+                .unwrap_err();
+        assert_eq!(
+            err.reason,
+            "KeyNotReady: ephemeral_key_create requires key keys_1 to have reached phase KeypairConstructed, but it is in phase Created"
+        );
+
+        let keygen = keygen(1, 2);
+        assert!(threshold_sig::ephemeral_key_create(
+            keygen.key_ids[0].clone(),
+            b"ready".to_vec(),
+            1,
+            None,
+            None,
+            None,
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_verify_signature_detailed_reasons() {
+        let _guard = serial();
+        let message = b"detailed".to_vec();
+        let keygen = keygen(1, 2);
+        let round = sign_round(&keygen, 1, &[1, 2], &message);
+        let signature = aggregate(&keygen, &round);
+
+        let ok = threshold_sig::verify_signature_detailed(
+            signature.clone(),
+            message.clone(),
+            keygen.y.clone(),
+        )
+        .unwrap();
+        assert!(ok.valid);
+        assert_eq!(ok.reason, None);
+
+        // a tampered s that is still canonical fails the verification equation
+        let mut tampered = signature.clone();
+        tampered.s.bytes[0] ^= 1;
+        let result = threshold_sig::verify_signature_detailed(
+            tampered.clone(),
+            message.clone(),
+            keygen.y.clone(),
+        )
+        .unwrap();
+        assert!(!result.valid);
+        assert_eq!(
+            result.reason.unwrap(),
+            "SignatureMismatch: s*G does not equal R + k*A"
+        );
+
+        // pushing s above the group order is reported as non-canonical
+        tampered.s.bytes[31] = 0xff;
+        let result =
+            threshold_sig::verify_signature_detailed(tampered, message.clone(), keygen.y.clone())
+                .unwrap();
+        assert_eq!(
+            result.reason.unwrap(),
+            "NonCanonicalScalar: s is not a canonical 32-byte scalar"
+        );
+
+        // a public key that is not a curve point
+        let mut wrong_key = keygen.y.clone();
+        wrong_key.bytes = vec![0xff; 32];
+        let result =
+            threshold_sig::verify_signature_detailed(signature, message, wrong_key).unwrap();
+        assert!(!result.valid);
+        assert_eq!(
+            result.reason.unwrap(),
+            "InvalidPoint: public key is not a valid Ed25519 point encoding"
+        );
+    }
+
+    #[test]
+    fn test_imported_single_party_key_signs_under_seed_public_key() {
+        let _guard = serial();
+        // RFC 8032 section 7.1, TEST 1
+        let seed = hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+            .unwrap();
+        let expected_public_key =
+            hex::decode("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+                .unwrap();
+
+        let key_id = threshold_sig::import_single_party_key(seed).unwrap();
+        let shared_keys = threshold_sig::get_shared_keys(key_id.clone()).unwrap();
+        assert_eq!(shared_keys.y.bytes, expected_public_key);
+
+        let single = Keygen {
+            key_ids: vec![key_id],
+            vss_schemes: vec![SerializableVerifiableSS {
+                threshold: 0,
+                share_count: 1,
+                commitments: vec![shared_keys.y.clone()],
+            }],
+            y: shared_keys.y.clone(),
+            shared_keys: vec![shared_keys],
+        };
+        let message = b"migrated signer".to_vec();
+        let round = sign_round(&single, 0, &[1], &message);
+        let signature = aggregate(&single, &round);
+        let public_key = SerializablePoint {
+            bytes: expected_public_key,
+        };
+        assert!(
+            threshold_sig::verify_signature(signature, message, public_key, None, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_share_names_the_sending_party() {
+        let _guard = serial();
+        let parties: Vec<u16> = vec![1, 2, 3];
+        let key_ids: Vec<String> = parties
+            .iter()
+            .map(|&i| threshold_sig::phase1_create(i).unwrap())
+            .collect();
+        let public_keys: Vec<SerializablePoint> = key_ids
+            .iter()
+            .map(|id| threshold_sig::get_public_key(id.clone()).unwrap())
+            .collect();
+        let (commitments, blind_factors): (Vec<_>, Vec<_>) = key_ids
+            .iter()
+            .map(|id| broadcast_parts(threshold_sig::phase1_broadcast(id.clone(), None).unwrap()))
+            .unzip();
+
+        let mut forged_blinds = blind_factors.clone();
+        forged_blinds[1] = forged_blinds[0].clone();
+        let err = threshold_sig::phase1_verify_com_phase2_distribute(
+            key_ids[0].clone(),
+            1,
+            3,
+            forged_blinds,
+            public_keys.clone(),
+            commitments.clone(),
+            parties.clone(),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.reason,
+            "InvalidDecommitment: decommitment of party 2 does not open its commitment"
+        );
+
+        let (vss_schemes, secret_shares): (Vec<_>, Vec<_>) = key_ids
+            .iter()
+            .map(|id| {
+                distribute_parts(
+                    threshold_sig::phase1_verify_com_phase2_distribute(
+                        id.clone(),
+                        1,
+                        3,
+                        blind_factors.clone(),
+                        public_keys.clone(),
+                        commitments.clone(),
+                        parties.clone(),
+                        None,
+                        None,
+                    )
+                    .unwrap(),
+                )
+            })
+            .unzip();
+        let mut received: Vec<SerializableScalar> = secret_shares
+            .iter()
+            .map(|shares| shares[0].clone())
+            .collect();
+        received[2] = received[1].clone();
+        let err = threshold_sig::phase2_verify_vss_construct_keypair(
+            key_ids[0].clone(),
+            1,
+            3,
+            public_keys,
+            received,
+            vss_schemes,
+            1,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.reason,
+            "InvalidShare: secret share from party 3 does not match its VSS commitments"
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_verification_matches_sequential() {
+        let _guard = serial();
+        let run = |threads: u32| {
+            *lock(threshold_sig::keys_store()) = Store::new();
+            crate::parallel::set_verification_threads(threads).unwrap();
+            crate::rng::set_deterministic_rng(b"rayon".to_vec()).unwrap();
+            keygen(2, 5).shared_keys
+        };
+        let sequential = run(1);
+        let parallel = run(4);
+        crate::parallel::set_verification_threads(0).unwrap();
+        crate::rng::set_deterministic_rng(vec![]).unwrap();
+
+        for (a, b) in sequential.iter().zip(&parallel) {
+            assert_eq!(a.y.bytes, b.y.bytes);
+            assert_eq!(a.x_i.bytes, b.x_i.bytes);
+        }
+    }
+
+    #[test]
+    fn test_deterministic_commitment_scheme() {
+        let _guard = serial();
+        let keygen = keygen_with_scheme(1, 2, threshold_sig::CommitmentScheme::Deterministic);
+        let message = b"no blind factors".to_vec();
+        let round = sign_round(&keygen, 1, &[1, 2], &message);
+        let signature = aggregate(&keygen, &round);
+        assert!(threshold_sig::verify_signature(signature, message, keygen.y, None, None).unwrap());
+
+        // a verifier expecting the blinded hash commitment rejects deterministic commitments
+        let parties: Vec<u16> = vec![1, 2];
+        let key_ids: Vec<String> = parties
+            .iter()
+            .map(|&i| threshold_sig::phase1_create(i).unwrap())
+            .collect();
+        let public_keys: Vec<SerializablePoint> = key_ids
+            .iter()
+            .map(|id| threshold_sig::get_public_key(id.clone()).unwrap())
+            .collect();
+        let commitments: Vec<SerializableBigInt> = key_ids
+            .iter()
+            .map(|id| {
+                let broadcast = threshold_sig::phase1_broadcast(
+                    id.clone(),
+                    Some(threshold_sig::CommitmentScheme::Deterministic),
+                )
+                .unwrap();
+                assert!(broadcast["blind_factor"].is_null());
+                broadcast_parts(broadcast).0
+            })
+            .collect();
+        let err = threshold_sig::phase1_verify_com_phase2_distribute(
+            key_ids[0].clone(),
+            1,
+            2,
+            vec![SerializableBigInt { bytes: vec![0] }; 2],
+            public_keys,
+            commitments,
+            parties,
+            Some(threshold_sig::CommitmentScheme::HashCommitment),
+            None,
+        )
+        .unwrap_err();
+        assert!(err.reason.starts_with("InvalidDecommitment: "));
+    }
+
+    #[test]
+    fn test_share_envelopes_carry_recipient_index() {
+        let _guard = serial();
+        let parties: Vec<u16> = vec![1, 2, 3];
+        let key_ids: Vec<String> = parties
+            .iter()
+            .map(|&i| threshold_sig::phase1_create(i).unwrap())
+            .collect();
+        let public_keys: Vec<SerializablePoint> = key_ids
+            .iter()
+            .map(|id| threshold_sig::get_public_key(id.clone()).unwrap())
+            .collect();
+        let (commitments, blind_factors): (Vec<_>, Vec<_>) = key_ids
+            .iter()
+            .map(|id| broadcast_parts(threshold_sig::phase1_broadcast(id.clone(), None).unwrap()))
+            .unzip();
+        let distribute = threshold_sig::phase1_verify_com_phase2_distribute(
+            key_ids[0].clone(),
+            1,
+            3,
+            blind_factors,
+            public_keys,
+            commitments,
+            parties,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let envelopes: Vec<crate::SerializableSecretShareEnvelope> =
+            serde_json::from_value(distribute["share_envelopes"].clone()).unwrap();
+        let (_, flat) = distribute_parts(distribute);
+        assert_eq!(
+            envelopes
+                .iter()
+                .map(|e| e.recipient_index)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        for (envelope, share) in envelopes.iter().zip(&flat) {
+            assert_eq!(envelope.share.bytes, share.bytes);
+        }
+    }
+
+    #[test]
+    fn test_poisoned_store_keeps_working() {
+        let _guard = serial();
+        let key_id = threshold_sig::phase1_create(1).unwrap();
+
+        let poisoner = std::thread::spawn(|| {
+            let _store = threshold_sig::keys_store().lock().unwrap();
+            panic!("signing thread died while holding the store");
+        });
+        assert!(poisoner.join().is_err());
+        assert!(threshold_sig::keys_store().is_poisoned());
+
+        assert_eq!(threshold_sig::get_key_phase(key_id).unwrap(), "Created");
+        assert!(threshold_sig::phase1_create(2).is_ok());
+    }
+
+    #[test]
+    fn test_validate_keygen_inputs_flags_bad_commitment() {
+        let _guard = serial();
+        let parties: Vec<u16> = vec![1, 2, 3];
+        let key_ids: Vec<String> = parties
+            .iter()
+            .map(|&i| threshold_sig::phase1_create(i).unwrap())
+            .collect();
+        let public_keys: Vec<SerializablePoint> = key_ids
+            .iter()
+            .map(|id| threshold_sig::get_public_key(id.clone()).unwrap())
+            .collect();
+        let (mut commitments, blind_factors): (Vec<_>, Vec<_>) = key_ids
+            .iter()
+            .map(|id| broadcast_parts(threshold_sig::phase1_broadcast(id.clone(), None).unwrap()))
+            .unzip();
+        commitments[1] = commitments[0].clone();
+
+        let report = threshold_sig::validate_keygen_inputs(
+            1,
+            3,
+            blind_factors,
+            public_keys,
+            commitments,
+            parties,
+            None,
+        )
+        .unwrap();
+        assert!(!report.valid);
+        assert!(report.errors.is_empty());
+        let failing: Vec<u16> = report
+            .parties
+            .iter()
+            .filter(|p| !p.valid)
+            .map(|p| p.party_index)
+            .collect();
+        assert_eq!(failing, vec![2]);
+        assert_eq!(
+            report.parties[1].reason.as_deref(),
+            Some("InvalidDecommitment: decommitment of party 2 does not open its commitment")
+        );
+        // nothing moved past phase 1
+        for key_id in key_ids {
+            assert_eq!(threshold_sig::get_key_phase(key_id).unwrap(), "Broadcast");
+        }
+    }
+
+    #[test]
+    fn test_signature_binds_public_key_picks_matching_candidate() {
+        let _guard = serial();
+        let message = b"which wallet".to_vec();
+        let keygen = keygen(1, 2);
+        let round = sign_round(&keygen, 1, &[1, 2], &message);
+        let signature = aggregate(&keygen, &round);
+
+        let other_keys: Vec<SerializablePoint> = keygen
+            .key_ids
+            .iter()
+            .map(|id| threshold_sig::get_public_key(id.clone()).unwrap())
+            .collect();
+        let not_a_point = SerializablePoint {
+            bytes: vec![0xff; 32],
+        };
+        let candidates = vec![
+            other_keys[0].clone(),
+            not_a_point,
+            keygen.y.clone(),
+            other_keys[1].clone(),
+        ];
+        assert_eq!(
+            threshold_sig::signature_binds_public_key(
+                signature.clone(),
+                message.clone(),
+                candidates
+            )
+            .unwrap(),
+            Some(2)
+        );
+        assert_eq!(
+            threshold_sig::signature_binds_public_key(signature, message, other_keys).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_compute_local_sig_checks_expected_R() {
+        let _guard = serial();
+        let message = b"desync".to_vec();
+        let keygen = keygen(1, 2);
+        let round = sign_round(&keygen, 1, &[1, 2], &message);
+
+        let err = threshold_sig::compute_local_sig(
+            message.clone(),
+            round.eph_shared_keys[0].clone(),
+            keygen.shared_keys[0].clone(),
+            Some(keygen.y.clone()),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.reason,
+            "RInconsistent: ephemeral shared key R does not match the provided R"
+        );
+
+        let local_sig = threshold_sig::compute_local_sig(
+            message,
+            round.eph_shared_keys[0].clone(),
+            keygen.shared_keys[0].clone(),
+            Some(round.R.clone()),
+            None,
+        )
+        .unwrap();
+        assert_eq!(local_sig.gamma_i.bytes, round.local_sigs[0].gamma_i.bytes);
+    }
+
+    #[test]
+    fn test_aggregate_public_keys_is_order_independent_and_resists_rogue_keys() {
+        use curv::elliptic::curves::Point;
+
+        let to_point = |sp: &SerializablePoint| Point::<Ed25519>::from_bytes(&sp.bytes).unwrap();
+        let to_serializable = |p: &Point<Ed25519>| SerializablePoint {
+            bytes: p.to_bytes(true).to_vec(),
+        };
+        let keys: Vec<Point<Ed25519>> = (1u16..=3)
+            .map(|i| Point::generator() * Scalar::<Ed25519>::from(i * 7919))
+            .collect();
+
+        let forward: Vec<SerializablePoint> = keys.iter().map(to_serializable).collect();
+        let mut reversed = forward.clone();
+        reversed.reverse();
+        let aggregate = threshold_sig::aggregate_public_keys(forward).unwrap();
+        assert_eq!(
+            aggregate.bytes,
+            threshold_sig::aggregate_public_keys(reversed)
+                .unwrap()
+                .bytes
+        );
+
+        // a rogue key T - X makes the plain sum equal a key the attacker controls alone
+        let honest = &keys[0];
+        let target = Point::generator() * Scalar::<Ed25519>::from(42u16);
+        let rogue = &target - honest;
+        assert_eq!(honest + &rogue, target);
+        let combined = threshold_sig::aggregate_public_keys(vec![
+            to_serializable(honest),
+            to_serializable(&rogue),
+        ])
+        .unwrap();
+        assert_ne!(to_point(&combined), target);
+        assert!(!to_point(&combined).is_zero());
+
+        assert!(threshold_sig::aggregate_public_keys(vec![]).is_err());
+    }
+
+    #[test]
+    fn test_phase1_collect_orders_out_of_order_broadcasts() {
+        let _guard = serial();
+        let (t, n) = (1, 3);
+        let key_ids: Vec<String> = (1..=n)
+            .map(|i| threshold_sig::phase1_create(i).unwrap())
+            .collect();
+        let public_keys: Vec<SerializablePoint> = key_ids
+            .iter()
+            .map(|id| threshold_sig::get_public_key(id.clone()).unwrap())
+            .collect();
+        let broadcasts: Vec<(SerializableBigInt, SerializableBigInt)> = key_ids
+            .iter()
+            .map(|id| broadcast_parts(threshold_sig::phase1_broadcast(id.clone(), None).unwrap()))
+            .collect();
+
+        for &party in &[3u16, 1, 2] {
+            assert!(!threshold_sig::phase1_ready("session".to_string(), n).unwrap());
+            let i = usize::from(party - 1);
+            let (commitment, blind_factor) = broadcasts[i].clone();
+            threshold_sig::phase1_collect(
+                "session".to_string(),
+                party,
+                commitment,
+                blind_factor,
+                public_keys[i].clone(),
+            )
+            .unwrap();
+        }
+        assert!(threshold_sig::phase1_ready("session".to_string(), n).unwrap());
+
+        // a conflicting resend is rejected, an identical one is not
+        let (commitment, blind_factor) = broadcasts[0].clone();
+        assert!(threshold_sig::phase1_collect(
+            "session".to_string(),
+            1,
+            commitment.clone(),
+            blind_factor.clone(),
+            public_keys[1].clone(),
+        )
+        .is_err());
+        threshold_sig::phase1_collect(
+            "session".to_string(),
+            1,
+            commitment,
+            blind_factor,
+            public_keys[0].clone(),
+        )
+        .unwrap();
+
+        for id in &key_ids {
+            let distribute = threshold_sig::phase1_verify_collected(
+                "session".to_string(),
+                id.clone(),
+                t,
+                n,
+                None,
+            )
+            .unwrap();
+            let envelopes = distribute["share_envelopes"].as_array().unwrap();
+            let recipients: Vec<u64> = envelopes
+                .iter()
+                .map(|envelope| envelope["recipient_index"].as_u64().unwrap())
+                .collect();
+            assert_eq!(recipients, vec![1, 2, 3]);
+        }
+    }
+
+    #[test]
+    fn test_point_decoding_accepts_prefixed_encoding() {
+        use curv::elliptic::curves::Point;
+
+        let point = Point::<Ed25519>::generator() * Scalar::<Ed25519>::from(1234u16);
+        let bare = point.to_bytes(true).to_vec();
+        let mut prefixed = vec![0x40];
+        prefixed.extend_from_slice(&bare);
+
+        let decode = |bytes: Vec<u8>| crate::serializable_to_point(&SerializablePoint { bytes });
+        assert_eq!(decode(bare.clone()).unwrap(), point);
+        assert_eq!(decode(prefixed).unwrap(), point);
+
+        let mut unknown_prefix = vec![0x02];
+        unknown_prefix.extend_from_slice(&bare);
+        assert!(decode(unknown_prefix).is_err());
+        assert!(decode(bare[1..].to_vec()).is_err());
+    }
+
+    #[test]
+    fn test_metrics_count_operations_and_error_codes() {
+        let _guard = serial();
+        crate::metrics::reset_metrics().unwrap();
+        let message = b"observed".to_vec();
+        let keygen = keygen(1, 3);
+        let round = sign_round(&keygen, 1, &[1, 2], &message);
+        let signature = aggregate(&keygen, &round);
+        assert!(
+            threshold_sig::verify_signature(signature, message, keygen.y.clone(), None, None)
+                .unwrap()
+        );
+        assert!(threshold_sig::verify_local_sigs(
+            round.local_sigs[..1].to_vec(),
+            round.parties_index[..1].to_vec(),
+            keygen.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .is_err());
+
+        let metrics = crate::metrics::get_metrics().unwrap();
+        assert_eq!(metrics.keygens, 3);
+        assert_eq!(metrics.signatures, 1);
+        assert_eq!(metrics.verifications, 1);
+        assert_eq!(metrics.errors_by_code.len(), 1);
+        assert_eq!(metrics.errors_by_code["InsufficientParticipants"], 1);
+
+        crate::metrics::reset_metrics().unwrap();
+        let metrics = crate::metrics::get_metrics().unwrap();
+        assert_eq!(metrics.keygens, 0);
+        assert!(metrics.errors_by_code.is_empty());
+    }
+
+    #[test]
+    fn test_string_overloads_match_utf8_bytes() {
+        let _guard = serial();
+        let message = "pay 5€ to Zoë";
+        let keygen = keygen(1, 2);
+
+        let ephemeral_R = |create: &dyn Fn() -> String| {
+            crate::rng::set_deterministic_rng(b"overload".to_vec()).unwrap();
+            let eph_id = create();
+            let R = threshold_sig::get_ephemeral_R(eph_id).unwrap();
+            *lock(threshold_sig::ephemeral_keys_store()) = Store::new();
+            R
+        };
+        let from_str = ephemeral_R(&|| {
+            threshold_sig::ephemeral_key_create_str(
+                keygen.key_ids[0].clone(),
+                message.to_string(),
+                1,
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+        });
+        let from_bytes = ephemeral_R(&|| {
+            threshold_sig::ephemeral_key_create(
+                keygen.key_ids[0].clone(),
+                message.as_bytes().to_vec(),
+                1,
+                None,
+                None,
+                None,
+            )
+            .unwrap()
+        });
+        crate::rng::set_deterministic_rng(vec![]).unwrap();
+        assert_eq!(from_str.bytes, from_bytes.bytes);
+
+        let round = sign_round(&keygen, 1, &[1, 2], message.as_bytes());
+        for i in 0..2 {
+            let local_sig = threshold_sig::compute_local_sig_str(
+                message.to_string(),
+                round.eph_shared_keys[i].clone(),
+                keygen.shared_keys[i].clone(),
+                None,
+                None,
+            )
+            .unwrap();
+            assert_eq!(local_sig.gamma_i.bytes, round.local_sigs[i].gamma_i.bytes);
+        }
+        let signature = aggregate(&keygen, &round);
+        assert!(threshold_sig::verify_signature_str(
+            signature,
+            message.to_string(),
+            keygen.y.clone(),
+            None,
+            None,
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_ephemeral_share_envelopes_route_nonce_shares() {
+        let _guard = serial();
+        let message = b"routed nonces".to_vec();
+        let keygen = keygen(1, 3);
+        let signers: Vec<u16> = vec![1, 3];
+        let eph_ids: Vec<String> = signers
+            .iter()
+            .map(|&p| {
+                let key_id = keygen.key_ids[usize::from(p - 1)].clone();
+                threshold_sig::ephemeral_key_create(key_id, message.clone(), p, None, None, None)
+                    .unwrap()
+            })
+            .collect();
+        let R_points: Vec<SerializablePoint> = eph_ids
+            .iter()
+            .map(|id| threshold_sig::get_ephemeral_R(id.clone()).unwrap())
+            .collect();
+        let (commitments, blind_factors): (Vec<_>, Vec<_>) = eph_ids
+            .iter()
+            .map(|id| {
+                broadcast_parts(threshold_sig::ephemeral_phase1_broadcast(id.clone()).unwrap())
+            })
+            .unzip();
+        let (vss_ephemeral, envelopes): (Vec<_>, Vec<_>) = eph_ids
+            .iter()
+            .map(|id| {
+                let distribute = threshold_sig::ephemeral_phase1_verify_com_phase2_distribute(
+                    id.clone(),
+                    1,
+                    2,
+                    blind_factors.clone(),
+                    R_points.clone(),
+                    commitments.clone(),
+                    signers.clone(),
+                    None,
+                )
+                .unwrap();
+                let envelopes: Vec<crate::SerializableSecretShareEnvelope> =
+                    serde_json::from_value(distribute["share_envelopes"].clone()).unwrap();
+                (distribute_parts(distribute).0, envelopes)
+            })
+            .unzip();
+        for sent in &envelopes {
+            assert_eq!(
+                sent.iter().map(|e| e.recipient_index).collect::<Vec<_>>(),
+                signers
+            );
+        }
+
+        // each signer picks its share out of every sender's envelopes by recipient index
+        let eph_shared_keys: Vec<SerializableEphemeralSharedKeys> = eph_ids
+            .iter()
+            .zip(&signers)
+            .map(|(id, &p)| {
+                let received: Vec<SerializableScalar> = envelopes
+                    .iter()
+                    .map(|sent| {
+                        sent.iter()
+                            .find(|e| e.recipient_index == p)
+                            .unwrap()
+                            .share
+                            .clone()
+                    })
+                    .collect();
+                threshold_sig::ephemeral_phase2_verify_vss_construct_keypair(
+                    id.clone(),
+                    1,
+                    2,
+                    R_points.clone(),
+                    received,
+                    vss_ephemeral.clone(),
+                    p,
+                    None,
+                )
+                .unwrap()
+            })
+            .collect();
+        let local_sigs: Vec<SerializableLocalSig> = eph_shared_keys
+            .iter()
+            .zip(&signers)
+            .map(|(eph_shared, &p)| {
+                threshold_sig::compute_local_sig(
+                    message.clone(),
+                    eph_shared.clone(),
+                    keygen.shared_keys[usize::from(p - 1)].clone(),
+                    None,
+                    None,
+                )
+                .unwrap()
+            })
+            .collect();
+        let round = SigningRound {
+            R: eph_shared_keys[0].R.clone(),
+            signers: signers.clone(),
+            eph_shared_keys,
+            local_sigs,
+            parties_index: signers.iter().map(|p| p - 1).collect(),
+            vss_ephemeral,
+        };
+        let signature = aggregate(&keygen, &round);
+        assert!(
+            threshold_sig::verify_signature(signature, message, keygen.y.clone(), None, None)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_self_test_checks_golden_vectors() {
+        use crate::self_test::{self_test_against, Golden, GOLDEN};
+
+        assert!(crate::self_test::run_self_test().unwrap());
+        let corrupted = Golden {
+            s: "341c099e988090abe1d9eb4d60ea510dda31d0871c0944bd7eea125174a0940a",
+            ..GOLDEN
+        };
+        assert!(!self_test_against(&corrupted));
+    }
+
+    #[test]
+    fn test_same_group_compares_group_keys() {
+        let _guard = serial();
+        let keygen = keygen(1, 2);
+        assert!(
+            threshold_sig::same_group(keygen.key_ids[0].clone(), keygen.key_ids[1].clone())
+                .unwrap()
+        );
+
+        // imports run their own 1-of-1 keygens and get distinct ids
+        let imported_a = threshold_sig::import_single_party_key(vec![7u8; 32]).unwrap();
+        let imported_b = threshold_sig::import_single_party_key(vec![8u8; 32]).unwrap();
+        assert!(!threshold_sig::same_group(imported_a.clone(), imported_b).unwrap());
+        assert!(!threshold_sig::same_group(keygen.key_ids[0].clone(), imported_a).unwrap());
+
+        let pending = threshold_sig::phase1_create(9).unwrap();
+        assert!(threshold_sig::same_group(keygen.key_ids[0].clone(), pending).is_err());
+    }
+
+    #[test]
+    fn test_ephemeral_domain_separates_nonces() {
+        let _guard = serial();
+        let message = b"shared key, two apps".to_vec();
+        let keygen = keygen(1, 2);
+        let R_i = |domain: Option<Vec<u8>>| {
+            crate::rng::set_deterministic_rng(b"domain".to_vec()).unwrap();
+            let eph_id = threshold_sig::ephemeral_key_create(
+                keygen.key_ids[0].clone(),
+                message.clone(),
+                1,
+                domain,
+                None,
+                None,
+            )
+            .unwrap();
+            threshold_sig::get_ephemeral_R(eph_id).unwrap().bytes
+        };
+        let default = R_i(None);
+        let empty = R_i(Some(vec![]));
+        let wallet = R_i(Some(b"wallet".to_vec()));
+        let bridge = R_i(Some(b"bridge".to_vec()));
+        crate::rng::set_deterministic_rng(vec![]).unwrap();
+
+        assert_eq!(default, empty);
+        assert_ne!(wallet, default);
+        assert_ne!(wallet, bridge);
+    }
+
+    #[test]
+    fn test_signing_receipt_records_round() {
+        let _guard = serial();
+        let message = b"audited".to_vec();
+        let keygen = keygen(1, 3);
+        let round = sign_round(&keygen, 1, &[2, 3], &message);
+        let vss_sum = threshold_sig::verify_local_sigs(
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            keygen.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap();
+
+        let receipt = threshold_sig::generate_signature_with_receipt(
+            vss_sum,
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            round.R.clone(),
+            keygen.y.clone(),
+            message.clone(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(receipt.R.bytes, round.R.bytes);
+        assert_eq!(receipt.signature.R.bytes, round.R.bytes);
+        assert_eq!(receipt.parties_index, vec![1, 2]);
+        let challenge = threshold_sig::compute_challenge(
+            round.R.clone(),
+            keygen.y.clone(),
+            message.clone(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(receipt.message_hash.bytes, challenge.bytes);
+        assert_eq!(receipt.message_hash.bytes, round.local_sigs[0].k.bytes);
+        assert!(threshold_sig::verify_signature(
+            receipt.signature,
+            message,
+            keygen.y.clone(),
+            None,
+            None,
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_max_message_size_rejects_oversized_messages() {
+        let _guard = serial();
+        let keygen = keygen(1, 2);
+        let round = sign_round(&keygen, 1, &[1, 2], b"12345678");
+        let signature = aggregate(&keygen, &round);
+        let vss_sum = threshold_sig::verify_local_sigs(
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            keygen.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap();
+
+        threshold_sig::set_max_message_size(8).unwrap();
+        let oversized = b"123456789".to_vec();
+        let expected = "MessageTooLarge: message of 9 bytes exceeds the 8 byte limit";
+        let errors = [
+            threshold_sig::ephemeral_key_create(
+                keygen.key_ids[0].clone(),
+                oversized.clone(),
+                1,
+                None,
+                None,
+                None,
+            )
+            .unwrap_err(),
+            threshold_sig::compute_local_sig(
+                oversized.clone(),
+                round.eph_shared_keys[0].clone(),
+                keygen.shared_keys[0].clone(),
+                None,
+                None,
+            )
+            .unwrap_err(),
+            threshold_sig::compute_local_sigs_batch(
+                vec![b"12345678".to_vec(), oversized.clone()],
+                vec![round.eph_shared_keys[0].clone(); 2],
+                keygen.shared_keys[0].clone(),
+                None,
+            )
+            .unwrap_err(),
+            threshold_sig::signature_binds_public_key(
+                signature.clone(),
+                oversized.clone(),
+                vec![keygen.y.clone()],
+            )
+            .unwrap_err(),
+            threshold_sig::find_signed_message(
+                signature.clone(),
+                vec![b"12345678".to_vec(), oversized.clone()],
+                keygen.y.clone(),
+            )
+            .unwrap_err(),
+            threshold_sig::verify_signature(
+                signature.clone(),
+                oversized.clone(),
+                keygen.y.clone(),
+                None,
+                None,
+            )
+            .unwrap_err(),
+            threshold_sig::verify_signature_detailed(
+                signature.clone(),
+                oversized.clone(),
+                keygen.y.clone(),
+            )
+            .unwrap_err(),
+            threshold_sig::compute_challenge(
+                signature.R.clone(),
+                keygen.y.clone(),
+                oversized.clone(),
+                None,
+            )
+            .unwrap_err(),
+            threshold_sig::generate_signature(
+                vss_sum.clone(),
+                round.local_sigs.clone(),
+                round.parties_index.clone(),
+                round.R.clone(),
+                None,
+                None,
+                Some(oversized.clone()),
+                None,
+            )
+            .unwrap_err(),
+            threshold_sig::generate_signature_with_receipt(
+                vss_sum.clone(),
+                round.local_sigs.clone(),
+                round.parties_index.clone(),
+                round.R.clone(),
+                keygen.y.clone(),
+                oversized,
+                None,
+            )
+            .unwrap_err(),
+        ];
+        let at_limit = threshold_sig::verify_signature(
+            signature,
+            b"12345678".to_vec(),
+            keygen.y.clone(),
+            None,
+            None,
+        );
+        threshold_sig::set_max_message_size(16 * 1024 * 1024).unwrap();
+
+        for err in errors {
+            assert_eq!(err.reason, expected);
+        }
+        assert!(at_limit.unwrap());
+    }
+
+    #[test]
+    fn test_key_id_helpers_match_stored_ids() {
+        let _guard = serial();
+        let message = b"ids".to_vec();
+        let keygen = keygen(1, 2);
+        for (i, key_id) in keygen.key_ids.iter().enumerate() {
+            assert_eq!(threshold_sig::key_id_for_party(i as u16 + 1), *key_id);
+        }
+        let created = threshold_sig::phase1_create_from_private_key(7, vec![3u8; 32]).unwrap();
+        assert_eq!(created, threshold_sig::key_id_for_party(7));
+
+        let eph_id = threshold_sig::ephemeral_key_create(
+            keygen.key_ids[1].clone(),
+            message,
+            2,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            eph_id,
+            threshold_sig::ephemeral_key_id_for(keygen.key_ids[1].clone(), 2)
+        );
+        assert!(lock(threshold_sig::ephemeral_keys_store())
+            .get(&eph_id)
+            .is_some());
+    }
+
+    #[test]
+    fn test_find_signed_message_picks_the_signed_encoding() {
+        let _guard = serial();
+        let signed = br#"{"amount":5,"to":"bob"}"#.to_vec();
+        let keygen = keygen(1, 2);
+        let round = sign_round(&keygen, 1, &[1, 2], &signed);
+        let signature = aggregate(&keygen, &round);
+
+        let candidates = vec![
+            br#"{"to":"bob","amount":5}"#.to_vec(),
+            br#"{"amount": 5, "to": "bob"}"#.to_vec(),
+            signed,
+            br#"{"amount":5,"to":"bob"}
+"#
+            .to_vec(),
+        ];
+        assert_eq!(
+            threshold_sig::find_signed_message(
+                signature.clone(),
+                candidates.clone(),
+                keygen.y.clone()
+            )
+            .unwrap(),
+            Some(2)
+        );
+        assert_eq!(
+            threshold_sig::find_signed_message(signature, candidates[..2].to_vec(), keygen.y)
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_scalar_and_point_helpers() {
+        use crate::arith::*;
+        use curv::elliptic::curves::Point;
+
+        let n = |v: i64| scalar_from_u64(v).unwrap();
+        let g = |v: u16| SerializablePoint {
+            bytes: (Point::<Ed25519>::generator() * Scalar::<Ed25519>::from(v))
+                .to_bytes(true)
+                .to_vec(),
+        };
+        assert_eq!(
+            n(2).bytes,
+            Scalar::<Ed25519>::from(2u16).to_bytes().to_vec()
+        );
+        assert_eq!(scalar_add(n(2), n(3)).unwrap().bytes, n(5).bytes);
+        assert_eq!(scalar_mul(n(4), n(5)).unwrap().bytes, n(20).bytes);
+        assert_eq!(scalar_sub(n(9), n(4)).unwrap().bytes, n(5).bytes);
+        // 3 - 5 wraps to l - 2
+        let wrapped = scalar_sub(n(3), n(5)).unwrap();
+        assert_eq!(scalar_add(wrapped, n(2)).unwrap().bytes, n(0).bytes);
+        let half = scalar_invert(n(2)).unwrap();
+        assert_eq!(scalar_mul(half, n(2)).unwrap().bytes, n(1).bytes);
+        assert!(scalar_invert(n(0)).is_err());
+        assert!(scalar_from_u64(-1).is_err());
+
+        assert_eq!(point_add(g(2), g(3)).unwrap().bytes, g(5).bytes);
+        assert_eq!(point_mul_scalar(g(1), n(7)).unwrap().bytes, g(7).bytes);
+    }
+
+    #[test]
+    fn test_verify_local_sigs_rejects_empty_inputs() {
+        let _guard = serial();
+        let keygen = keygen(1, 2);
+        let round = sign_round(&keygen, 1, &[1, 2], b"empty");
+
+        let err = threshold_sig::verify_local_sigs(
+            vec![],
+            round.parties_index.clone(),
+            keygen.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err.reason, "EmptyInput: local_sigs must not be empty");
+
+        let err = threshold_sig::verify_local_sigs(
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            vec![],
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err.reason, "EmptyInput: vss_private_keys must not be empty");
+
+        let err = threshold_sig::aggregate_signature(
+            round.local_sigs,
+            round.parties_index,
+            keygen.vss_schemes,
+            vec![],
+            round.R,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.reason,
+            "EmptyInput: vss_ephemeral_keys must not be empty"
+        );
+    }
+
+    #[test]
+    fn test_signer_handles_complete_two_of_two_signature() {
+        use crate::signer;
+
+        let _guard = serial();
+        let message = b"handled".to_vec();
+        let keygen = keygen(1, 2);
+        let signer_set: Vec<u16> = vec![1, 2];
+        for (domain, signers, hash) in [
+            (None, None, None),
+            (
+                Some(b"app".to_vec()),
+                Some(signer_set.clone()),
+                Some(threshold_sig::ChallengeHash::Keccak256),
+            ),
+        ] {
+            let signer_ids: Vec<String> = keygen
+                .key_ids
+                .iter()
+                .zip(&signer_set)
+                .map(|(key_id, &p)| {
+                    signer::create_signer(key_id.clone(), keygen.vss_schemes.clone(), p, 1, 2)
+                        .unwrap()
+                })
+                .collect();
+            let round_ids: Vec<String> = signer_ids
+                .iter()
+                .map(|id| {
+                    signer::signer_start_round(
+                        id.clone(),
+                        message.clone(),
+                        domain.clone(),
+                        signers.clone(),
+                        hash,
+                    )
+                    .unwrap()
+                })
+                .collect();
+
+            let broadcasts: Vec<serde_json::Value> = round_ids
+                .iter()
+                .map(|id| signer::signer_broadcast(id.clone()).unwrap())
+                .collect();
+            let R_points: Vec<SerializablePoint> = broadcasts
+                .iter()
+                .map(|b| serde_json::from_value(b["R"].clone()).unwrap())
+                .collect();
+            let (commitments, blind_factors): (Vec<_>, Vec<_>) =
+                broadcasts.into_iter().map(broadcast_parts).unzip();
+
+            let (vss_ephemeral, secret_shares): (Vec<_>, Vec<_>) = round_ids
+                .iter()
+                .map(|id| {
+                    distribute_parts(
+                        signer::signer_distribute(
+                            id.clone(),
+                            signer_set.clone(),
+                            blind_factors.clone(),
+                            R_points.clone(),
+                            commitments.clone(),
+                        )
+                        .unwrap(),
+                    )
+                })
+                .unzip();
+            for (i, id) in round_ids.iter().enumerate() {
+                let received: Vec<SerializableScalar> =
+                    secret_shares.iter().map(|s| s[i].clone()).collect();
+                signer::signer_construct(
+                    id.clone(),
+                    R_points.clone(),
+                    received,
+                    vss_ephemeral.clone(),
+                )
+                .unwrap();
+            }
+            let local_sigs: Vec<SerializableLocalSig> = round_ids
+                .iter()
+                .map(|id| signer::signer_local_sig(id.clone()).unwrap())
+                .collect();
+
+            let signature =
+                signer::signer_aggregate(round_ids[0].clone(), local_sigs, vss_ephemeral).unwrap();
+            assert!(threshold_sig::verify_signature(
+                signature.clone(),
+                message.clone(),
+                keygen.y.clone(),
+                None,
+                hash,
+            )
+            .unwrap());
+            if hash.is_some() {
+                assert!(!threshold_sig::verify_signature(
+                    signature,
+                    message.clone(),
+                    keygen.y.clone(),
+                    None,
+                    None,
+                )
+                .unwrap());
+            }
+
+            // a signer id where a round id is expected, and the other way round
+            let err = signer::signer_broadcast(signer_ids[0].clone()).unwrap_err();
+            assert_eq!(
+                err.reason,
+                format!(
+                    "WrongIdType: {} names a signer, expected the id of a signer round",
+                    signer_ids[0]
+                )
+            );
+            let err =
+                signer::signer_start_round(round_ids[0].clone(), message.clone(), None, None, None)
+                    .unwrap_err();
+            assert!(err.reason.starts_with("WrongIdType: "));
+            let err = signer::signer_local_sig("round_missing".to_string()).unwrap_err();
+            assert_eq!(err.reason, "Round not found");
+        }
+    }
+
+    #[test]
+    fn test_uncompressed_public_key_matches_decompressed_point() {
+        use curv::arithmetic::Converter;
+        use curv::elliptic::curves::Point;
+        use curv::BigInt;
+
+        let _guard = serial();
+        // RFC 8032 base point
+        let (x, y) = crate::affine_coordinates(&Point::<Ed25519>::generator().to_point()).unwrap();
+        assert_eq!(
+            x.to_string(),
+            "15112221349535400772501151409588531511454012693041857206046113283949847762202"
+        );
+        assert_eq!(
+            y.to_string(),
+            "46316835694926478169428394003475163141307993866256225615783033603165251855960"
+        );
+
+        let key_id = threshold_sig::phase1_create_from_private_key(1, vec![9u8; 32]).unwrap();
+        let compressed = threshold_sig::get_public_key(key_id.clone()).unwrap().bytes;
+        let uncompressed = threshold_sig::get_public_key_uncompressed(key_id).unwrap();
+        assert_eq!(uncompressed.len(), 64);
+
+        let point = Point::<Ed25519>::from_bytes(&compressed).unwrap();
+        let (x, y) = crate::affine_coordinates(&point).unwrap();
+        let le = |n: &BigInt| {
+            let mut bytes = n.to_bytes();
+            bytes.reverse();
+            bytes.resize(32, 0);
+            bytes
+        };
+        assert_eq!(uncompressed[..32], le(&x)[..]);
+        assert_eq!(uncompressed[32..], le(&y)[..]);
+
+        // compressing again is y with the parity of x in the top bit
+        let mut recompressed = uncompressed[32..].to_vec();
+        recompressed[31] |= (uncompressed[0] & 1) << 7;
+        assert_eq!(recompressed, compressed);
+    }
+
+    #[test]
+    fn test_generate_signature_from_bytes_matches_object_path() {
+        let _guard = serial();
+        let message = b"bytes in, bytes out".to_vec();
+        let keygen = keygen(1, 3);
+        let round = sign_round(&keygen, 1, &[1, 3], &message);
+        let vss_sum = threshold_sig::verify_local_sigs(
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            keygen.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap();
+        let expected = threshold_sig::generate_signature(
+            vss_sum.clone(),
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            round.R.clone(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let vss_sum_bytes = threshold_sig::vss_to_bytes(vss_sum).unwrap();
+        let blobs: Vec<Vec<u8>> = round
+            .local_sigs
+            .iter()
+            .map(|sig| threshold_sig::local_sig_to_bytes(sig.clone()).unwrap())
+            .collect();
+        let signature = threshold_sig::generate_signature_from_bytes(
+            vss_sum_bytes.clone(),
+            blobs.clone(),
+            round.parties_index.clone(),
+            round.R.bytes.clone(),
+        )
+        .unwrap();
+        assert_eq!(signature[..32], expected.R.bytes[..]);
+        assert_eq!(signature[32..], expected.s.bytes[..]);
+
+        let mut short = blobs;
+        short[1].pop();
+        assert!(threshold_sig::generate_signature_from_bytes(
+            vss_sum_bytes,
+            short,
+            round.parties_index,
+            round.R.bytes,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_signer_set_binds_ephemeral_nonce() {
+        let _guard = serial();
+        let message = b"same message, other signers".to_vec();
+        let keygen = keygen(1, 3);
+        let R_i = |signers: Option<Vec<u16>>| {
+            crate::rng::set_deterministic_rng(b"signer set".to_vec()).unwrap();
+            let eph_id = threshold_sig::ephemeral_key_create(
+                keygen.key_ids[0].clone(),
+                message.clone(),
+                1,
+                None,
+                signers,
+                None,
+            )
+            .unwrap();
+            threshold_sig::get_ephemeral_R(eph_id).unwrap().bytes
+        };
+        let unbound = R_i(None);
+        let empty = R_i(Some(vec![]));
+        let with_2 = R_i(Some(vec![1, 2]));
+        let with_3 = R_i(Some(vec![1, 3]));
+        let with_3_reordered = R_i(Some(vec![3, 1]));
+        crate::rng::set_deterministic_rng(vec![]).unwrap();
+
+        assert_eq!(unbound, empty);
+        assert_ne!(with_2, with_3);
+        assert_ne!(with_2, unbound);
+        assert_eq!(with_3, with_3_reordered);
+    }
+
+    #[test]
+    fn test_cofactored_verification_ignores_torsion() {
+        use curve25519_dalek::constants::{ED25519_BASEPOINT_POINT, EIGHT_TORSION};
+        use curve25519_dalek::scalar::Scalar as DalekScalar;
+        use sha2::{Digest, Sha512};
+
+        let _guard = serial();
+        let message = b"torsion".to_vec();
+        let keygen = keygen(1, 2);
+        let round = sign_round(&keygen, 1, &[1, 2], &message);
+        let honest = aggregate(&keygen, &round);
+        assert!(threshold_sig::verify_signature(
+            honest.clone(),
+            message.clone(),
+            keygen.y.clone(),
+            None,
+            None,
+        )
+        .unwrap());
+        assert!(
+            threshold_sig::verify_signature(honest, message, keygen.y, Some(true), None).unwrap()
+        );
+
+        // a key with an order-8 component: sB = R + kA only when 8 divides k, but the
+        // cofactored equation holds for every message
+        let a = DalekScalar::from(12345u64);
+        let r = DalekScalar::from(67890u64);
+        let A = (a * ED25519_BASEPOINT_POINT + EIGHT_TORSION[1]).compress();
+        let R = (r * ED25519_BASEPOINT_POINT).compress();
+        let (message, k) = (0u8..)
+            .map(|i| {
+                let message = vec![i];
+                let k = DalekScalar::from_hash(
+                    Sha512::new()
+                        .chain(R.as_bytes())
+                        .chain(A.as_bytes())
+                        .chain(&message),
+                );
+                (message, k)
+            })
+            .find(|(_, k)| k.as_bytes()[0] % 8 != 0)
+            .unwrap();
+        let signature = SerializableSignature {
+            R: SerializablePoint {
+                bytes: R.as_bytes().to_vec(),
+            },
+            s: SerializableScalar {
+                bytes: (r + k * a).as_bytes().to_vec(),
+            },
+        };
+        let public_key = SerializablePoint {
+            bytes: A.as_bytes().to_vec(),
+        };
+        assert!(threshold_sig::verify_signature(
+            signature.clone(),
+            message.clone(),
+            public_key.clone(),
+            Some(true),
+            None,
+        )
+        .unwrap());
+        // and with the points in their 0x40-prefixed encoding
+        let prefixed = |point: &SerializablePoint| SerializablePoint {
+            bytes: [&[0x40][..], &point.bytes].concat(),
+        };
+        assert!(threshold_sig::verify_signature(
+            SerializableSignature {
+                R: prefixed(&signature.R),
+                s: signature.s.clone(),
+            },
+            message.clone(),
+            prefixed(&public_key),
+            Some(true),
+            None,
+        )
+        .unwrap());
+        assert!(!matches!(
+            threshold_sig::verify_signature(signature, message, public_key, Some(false), None),
+            Ok(true)
+        ));
+    }
+
+    #[test]
+    fn test_panic_in_exported_function_becomes_error() {
+        let _guard = serial();
+        let kg = keygen(1, 2);
+        crate::guard::PANIC_HOOK.with(|hook| hook.set(Some("get_public_key")));
+        let err = threshold_sig::get_public_key(kg.key_ids[0].clone()).unwrap_err();
+        assert_eq!(err.reason, "Panic: internal panic in get_public_key");
+        assert!(!err.reason.contains("hook fired"));
+
+        // the hook is one-shot and the stores stay usable after the panic
+        assert!(threshold_sig::get_public_key(kg.key_ids[0].clone()).is_ok());
+    }
+
+    #[test]
+    fn test_challenge_hash_variants_sign_and_verify() {
+        let _guard = serial();
+        use threshold_sig::ChallengeHash;
+
+        let kg = keygen(1, 3);
+        let message = b"non-standard challenge".to_vec();
+        let hashes = [
+            ChallengeHash::Sha256,
+            ChallengeHash::Keccak256,
+            ChallengeHash::Blake2b,
+        ];
+        for (i, &hash) in hashes.iter().enumerate() {
+            let round = sign_round_with_hash(&kg, 1, &[1, 3], &message, Some(hash));
+            let signature = aggregate(&kg, &round);
+            for cofactored in [false, true] {
+                assert!(threshold_sig::verify_signature(
+                    signature.clone(),
+                    message.clone(),
+                    kg.y.clone(),
+                    Some(cofactored),
+                    Some(hash),
+                )
+                .unwrap());
+            }
+            // not an RFC 8032 signature, and not valid under any other variant
+            for other in [None, Some(ChallengeHash::Sha512)].into_iter().chain(
+                hashes
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, &h)| Some(h)),
+            ) {
+                assert!(!threshold_sig::verify_signature(
+                    signature.clone(),
+                    message.clone(),
+                    kg.y.clone(),
+                    None,
+                    other,
+                )
+                .unwrap());
+            }
+        }
+
+        // explicit Sha512 is the default, and the default still reproduces the
+        // fixed-seed RFC 8032 known-answer vectors
+        let round = sign_round(&kg, 1, &[2, 3], &message);
+        let signature = aggregate(&kg, &round);
+        assert!(threshold_sig::verify_signature(
+            signature,
+            message,
+            kg.y.clone(),
+            None,
+            Some(ChallengeHash::Sha512),
+        )
+        .unwrap());
+        assert!(crate::self_test::run_self_test().unwrap());
+    }
+
+    #[test]
+    fn test_non_sha512_session_self_verifies_and_reports_its_challenge() {
+        let _guard = serial();
+        let hash = Some(threshold_sig::ChallengeHash::Keccak256);
+        let kg = keygen(1, 3);
+        let message = b"keccak session".to_vec();
+        let round = sign_round_with_hash(&kg, 1, &[1, 2], &message, hash);
+        let vss_sum = threshold_sig::verify_local_sigs(
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            kg.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap();
+        let generate = |hash| {
+            threshold_sig::generate_signature(
+                vss_sum.clone(),
+                round.local_sigs.clone(),
+                round.parties_index.clone(),
+                round.R.clone(),
+                Some(true),
+                Some(kg.y.clone()),
+                Some(message.clone()),
+                hash,
+            )
+        };
+        assert!(generate(hash).is_ok());
+        assert_eq!(
+            generate(None).unwrap_err().reason,
+            "Generated signature failed self-verification"
+        );
+
+        // the challenge reported is the one the parties signed
+        let k =
+            threshold_sig::compute_challenge(round.R.clone(), kg.y.clone(), message.clone(), hash)
+                .unwrap();
+        assert_eq!(k.bytes, round.local_sigs[0].k.bytes);
+        let receipt = threshold_sig::generate_signature_with_receipt(
+            vss_sum.clone(),
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            round.R.clone(),
+            kg.y.clone(),
+            message,
+            hash,
+        )
+        .unwrap();
+        assert_eq!(receipt.message_hash.bytes, k.bytes);
+    }
+
+    #[test]
+    fn test_list_ephemeral_keys_for_filters_by_base_key() {
+        let _guard = serial();
+        let kg = keygen(1, 2);
+        for (key_id, index) in [
+            (&kg.key_ids[0], 1),
+            (&kg.key_ids[0], 2),
+            (&kg.key_ids[1], 2),
+        ] {
+            threshold_sig::ephemeral_key_create(
+                key_id.clone(),
+                b"m".to_vec(),
+                index,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        }
+
+        assert_eq!(
+            threshold_sig::list_ephemeral_keys_for(kg.key_ids[0].clone()).unwrap(),
+            vec![
+                threshold_sig::ephemeral_key_id_for(kg.key_ids[0].clone(), 1),
+                threshold_sig::ephemeral_key_id_for(kg.key_ids[0].clone(), 2),
+            ]
+        );
+        assert_eq!(
+            threshold_sig::list_ephemeral_keys_for(kg.key_ids[1].clone()).unwrap(),
+            vec![threshold_sig::ephemeral_key_id_for(
+                kg.key_ids[1].clone(),
+                2
+            )]
+        );
+        assert!(
+            threshold_sig::list_ephemeral_keys_for("keys_99".to_string())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_import_and_verify_shared_keys_restores_backups() {
+        let _guard = serial();
+        let message = b"restore check".to_vec();
+
+        // a 1-of-1 share is the whole key, so the restore signs with it
+        let single = threshold_sig::import_single_party_key(vec![7u8; 32]).unwrap();
+        let backup = threshold_sig::export_shared_keys(single.clone()).unwrap();
+        let restored =
+            threshold_sig::import_and_verify_shared_keys(backup.clone(), message.clone()).unwrap();
+        assert_eq!(restored.self_test_passed, Some(true));
+        assert_eq!(
+            restored.public_key.bytes,
+            threshold_sig::get_shared_keys(single).unwrap().y.bytes
+        );
+
+        let mut flipped = backup;
+        flipped[40] ^= 0x01;
+        let err =
+            threshold_sig::import_and_verify_shared_keys(flipped, message.clone()).unwrap_err();
+        assert_eq!(err.reason, "Backup checksum mismatch");
+
+        // a restored share of a 2-of-2 key signs alongside the other original share
+        let kg = keygen(1, 2);
+        let restored = threshold_sig::import_and_verify_shared_keys(
+            threshold_sig::export_shared_keys(kg.key_ids[0].clone()).unwrap(),
+            message.clone(),
+        )
+        .unwrap();
+        // a share of a larger key cannot sign alone, so no self test ran
+        assert_eq!(restored.self_test_passed, None);
+        assert_eq!(restored.public_key.bytes, kg.y.bytes);
+        let kg = Keygen {
+            key_ids: vec![restored.shared_key_id, kg.key_ids[1].clone()],
+            ..kg
+        };
+        let signature = aggregate(&kg, &sign_round(&kg, 1, &[1, 2], &message));
+        assert!(
+            threshold_sig::verify_signature(signature, message, kg.y.clone(), None, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_bigint_byte_orders_round_trip() {
+        use crate::bigint::*;
+
+        let value = SerializableBigInt {
+            bytes: vec![0x01, 0x02, 0x03, 0x04],
+        };
+        let be = bigint_to_bytes_be(value.clone()).unwrap();
+        let le = bigint_to_bytes_le(value.clone()).unwrap();
+        assert_eq!(be, vec![0x01, 0x02, 0x03, 0x04]);
+        assert_eq!(le, vec![0x04, 0x03, 0x02, 0x01]);
+        assert_eq!(bigint_from_bytes_be(be).unwrap().bytes, value.bytes);
+        assert_eq!(bigint_from_bytes_le(le).unwrap().bytes, value.bytes);
+        // padding in either order decodes to the same minimal encoding
+        assert_eq!(
+            bigint_from_bytes_be(vec![0, 0, 1, 2, 3, 4]).unwrap().bytes,
+            value.bytes
+        );
+        assert_eq!(
+            bigint_from_bytes_le(vec![4, 3, 2, 1, 0, 0]).unwrap().bytes,
+            value.bytes
+        );
+    }
+
+    #[test]
+    fn test_commitments_use_big_endian_blind_factors() {
+        let _guard = serial();
+        use crate::bigint::*;
+        use curv::arithmetic::Converter;
+        use curv::cryptographic_primitives::commitments::hash_commitment::HashCommitment;
+        use curv::cryptographic_primitives::commitments::traits::Commitment;
+        use curv::elliptic::curves::Point;
+        use curv::BigInt;
+        use sha2::Sha512;
+
+        let key_id = threshold_sig::phase1_create(1).unwrap();
+        let y = threshold_sig::get_public_key(key_id.clone()).unwrap();
+        let (commitment, blind_factor) =
+            broadcast_parts(threshold_sig::phase1_broadcast(key_id, None).unwrap());
+        let y_coord = Point::<Ed25519>::from_bytes(&y.bytes)
+            .unwrap()
+            .y_coord()
+            .unwrap();
+        let commit = |blind: &BigInt| {
+            HashCommitment::<Sha512>::create_commitment_with_user_defined_randomness(
+                &y_coord, blind,
+            )
+            .to_bytes()
+        };
+
+        let be = BigInt::from_bytes(&bigint_to_bytes_be(blind_factor.clone()).unwrap());
+        assert_eq!(commit(&be), commitment.bytes);
+        // reading the blind factor in the other order breaks the commitment
+        let le = BigInt::from_bytes(&bigint_to_bytes_le(blind_factor).unwrap());
+        assert_ne!(commit(&le), commitment.bytes);
+    }
+
+    #[test]
+    fn test_vss_with_wrong_commitment_count_is_malformed() {
+        let _guard = serial();
+        assert_eq!(threshold_sig::expected_commitment_count(2).unwrap(), 3);
+
+        let kg = keygen(2, 3);
+        let mut truncated = kg.vss_schemes[1].clone();
+        truncated.commitments.pop();
+        let err = threshold_sig::vss_to_bytes(truncated.clone()).unwrap_err();
+        assert_eq!(
+            err.reason,
+            "MalformedVss: VSS has 2 commitments, its threshold requires 3"
+        );
+
+        let round = sign_round(&kg, 2, &[1, 2, 3], b"m");
+        let mut vss_private_keys = kg.vss_schemes.clone();
+        vss_private_keys[1] = truncated;
+        let err = threshold_sig::verify_local_sigs(
+            round.local_sigs,
+            round.parties_index,
+            vss_private_keys,
+            round.vss_ephemeral,
+        )
+        .unwrap_err();
+        assert!(err.reason.starts_with("MalformedVss: "));
+    }
+
+    #[test]
+    fn test_indexed_local_sigs_in_any_order_aggregate() {
+        let _guard = serial();
+        let kg = keygen(1, 3);
+        let message = b"indexed".to_vec();
+        let round = sign_round(&kg, 1, &[1, 2, 3], &message);
+
+        let mut indexed: Vec<crate::SerializableIndexedLocalSig> = round
+            .local_sigs
+            .iter()
+            .zip(&round.parties_index)
+            .map(
+                |(local_sig, &party_index)| crate::SerializableIndexedLocalSig {
+                    party_index,
+                    local_sig: local_sig.clone(),
+                },
+            )
+            .collect();
+        indexed.rotate_left(1);
+        indexed.swap(0, 1);
+
+        // the same scrambled order without the pairing is rejected
+        let scrambled: Vec<SerializableLocalSig> =
+            indexed.iter().map(|sig| sig.local_sig.clone()).collect();
+        assert!(threshold_sig::verify_local_sigs(
+            scrambled,
+            round.parties_index.clone(),
+            kg.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .is_err());
+
+        let vss_sum = threshold_sig::verify_indexed_local_sigs(
+            indexed.clone(),
+            kg.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap();
+        let signature = threshold_sig::generate_indexed_signature(
+            vss_sum,
+            indexed,
+            round.R.clone(),
+            Some(true),
+            Some(kg.y.clone()),
+            Some(message.clone()),
+            None,
+        )
+        .unwrap();
+        assert!(
+            threshold_sig::verify_signature(signature, message, kg.y.clone(), None, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_keygen_transcript_verifies_and_detects_tampering() {
+        let _guard = serial();
+        let (t, n) = (1, 3);
+        let session = "audit".to_string();
+        let key_ids: Vec<String> = (1..=n)
+            .map(|i| threshold_sig::phase1_create(i).unwrap())
+            .collect();
+        for (i, id) in key_ids.iter().enumerate() {
+            let (commitment, blind_factor) =
+                broadcast_parts(threshold_sig::phase1_broadcast(id.clone(), None).unwrap());
+            threshold_sig::phase1_collect(
+                session.clone(),
+                i as u16 + 1,
+                commitment,
+                blind_factor,
+                threshold_sig::get_public_key(id.clone()).unwrap(),
+            )
+            .unwrap();
+        }
+        // VSS schemes are only known once parties have verified the broadcasts
+        for id in &key_ids[..2] {
+            threshold_sig::phase1_verify_collected(session.clone(), id.clone(), t, n, None)
+                .unwrap();
+        }
+        assert!(threshold_sig::export_keygen_transcript(session.clone()).is_err());
+        threshold_sig::phase1_verify_collected(session.clone(), key_ids[2].clone(), t, n, None)
+            .unwrap();
+
+        let transcript = threshold_sig::export_keygen_transcript(session).unwrap();
+        assert!(threshold_sig::verify_keygen_transcript(transcript.clone()).unwrap());
+
+        // party 1's commitment starts after the header, its index, key and length prefix
+        let mut tampered = transcript.clone();
+        tampered[2 + 2 + 32 + 2 + 32 + 2] ^= 0x01;
+        assert!(!threshold_sig::verify_keygen_transcript(tampered).unwrap());
+        assert!(!threshold_sig::verify_keygen_transcript(
+            transcript[..transcript.len() - 1].to_vec()
+        )
+        .unwrap());
+        assert!(!threshold_sig::verify_keygen_transcript(vec![]).unwrap());
+    }
+
+    #[test]
+    fn test_registered_verify_key_agrees_with_verify_signature() {
+        let _guard = serial();
+        use crate::verify_key::{register_verify_key, verify_with_registered_key};
+
+        let kg = keygen(1, 3);
+        let handle = register_verify_key(kg.y.clone()).unwrap();
+        let messages: Vec<Vec<u8>> = (0..4u8).map(|i| vec![i; usize::from(i) * 7]).collect();
+        let signatures: Vec<SerializableSignature> = messages
+            .iter()
+            .map(|message| aggregate(&kg, &sign_round(&kg, 1, &[1, 3], message)))
+            .collect();
+
+        let mut tampered_s = signatures[0].clone();
+        tampered_s.s.bytes[0] ^= 0x01;
+        let cases = [
+            (signatures[1].clone(), messages[1].clone()),
+            (signatures[1].clone(), messages[2].clone()),
+            (signatures[3].clone(), messages[3].clone()),
+            (signatures[2].clone(), messages[3].clone()),
+            (tampered_s, messages[0].clone()),
+            (
+                SerializableSignature {
+                    R: signatures[0].R.clone(),
+                    s: signatures[1].s.clone(),
+                },
+                messages[0].clone(),
+            ),
+            (
+                SerializableSignature {
+                    R: SerializablePoint {
+                        bytes: [&[0x40][..], &signatures[2].R.bytes].concat(),
+                    },
+                    s: signatures[2].s.clone(),
+                },
+                messages[2].clone(),
+            ),
+        ];
+        for (signature, message) in cases {
+            let ad_hoc = threshold_sig::verify_signature(
+                signature.clone(),
+                message.clone(),
+                kg.y.clone(),
+                None,
+                None,
+            )
+            .unwrap();
+            let registered =
+                verify_with_registered_key(handle.clone(), signature, message).unwrap();
+            assert_eq!(registered, ad_hoc);
+        }
+        assert!(
+            verify_with_registered_key(handle, signatures[0].clone(), messages[0].clone()).unwrap()
+        );
+
+        assert!(verify_with_registered_key(
+            "vk_missing".to_string(),
+            signatures[0].clone(),
+            messages[0].clone()
+        )
+        .is_err());
+        assert!(register_verify_key(SerializablePoint { bytes: vec![0; 31] }).is_err());
+    }
+
+    #[test]
+    fn test_single_party_threshold_flow_signs_and_verifies() {
+        let _guard = serial();
+        let message = b"one of one".to_vec();
+        let kg = keygen(0, 1);
+        assert_eq!(kg.vss_schemes[0].commitments.len(), 1);
+        // a degree-0 sharing hands the party the whole secret: x_1 * G is the group key
+        assert_eq!(
+            crate::arith::point_mul_scalar(
+                SerializablePoint {
+                    bytes: curv::elliptic::curves::Point::<Ed25519>::generator()
+                        .to_bytes(true)
+                        .to_vec(),
+                },
+                kg.shared_keys[0].x_i.clone(),
+            )
+            .unwrap()
+            .bytes,
+            kg.y.bytes
+        );
+
+        let round = sign_round(&kg, 0, &[1], &message);
+        assert_eq!(round.R.bytes, round.eph_shared_keys[0].R.bytes);
+        let signature = aggregate(&kg, &round);
+        assert!(threshold_sig::verify_signature(
+            signature.clone(),
+            message.clone(),
+            kg.y.clone(),
+            None,
+            None
+        )
+        .unwrap());
+
+        // the degenerate round also works through the signer handles
+        let signer_id =
+            crate::signer::create_signer(kg.key_ids[0].clone(), kg.vss_schemes.clone(), 1, 0, 1)
+                .unwrap();
+        let round_id =
+            crate::signer::signer_start_round(signer_id, message.clone(), None, None, None)
+                .unwrap();
+        let broadcast = crate::signer::signer_broadcast(round_id.clone()).unwrap();
+        let (commitment, blind_factor) = broadcast_parts(broadcast.clone());
+        let R_i: SerializablePoint = serde_json::from_value(broadcast["R"].clone()).unwrap();
+        let (vss, shares) = distribute_parts(
+            crate::signer::signer_distribute(
+                round_id.clone(),
+                vec![1],
+                vec![blind_factor],
+                vec![R_i.clone()],
+                vec![commitment],
+            )
+            .unwrap(),
+        );
+        let R =
+            crate::signer::signer_construct(round_id.clone(), vec![R_i], shares, vec![vss.clone()])
+                .unwrap();
+        let local_sig = crate::signer::signer_local_sig(round_id.clone()).unwrap();
+        let signature =
+            crate::signer::signer_aggregate(round_id, vec![local_sig], vec![vss]).unwrap();
+        assert_eq!(signature.R.bytes, R.bytes);
+        assert!(
+            threshold_sig::verify_signature(signature, message, kg.y.clone(), None, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_sharing_parameters_are_rejected_up_front() {
+        let _guard = serial();
+        let key_id = threshold_sig::phase1_create(1).unwrap();
+        let public_key = threshold_sig::get_public_key(key_id.clone()).unwrap();
+        let (commitment, blind_factor) =
+            broadcast_parts(threshold_sig::phase1_broadcast(key_id.clone(), None).unwrap());
+        let distribute = |threshold: u16, share_count: u16, parties: Vec<u16>| {
+            threshold_sig::phase1_verify_com_phase2_distribute(
+                key_id.clone(),
+                threshold,
+                share_count,
+                vec![blind_factor.clone()],
+                vec![public_key.clone()],
+                vec![commitment.clone()],
+                parties,
+                None,
+                None,
+            )
+            .unwrap_err()
+            .reason
+        };
+        assert_eq!(
+            distribute(0, 0, vec![]),
+            "threshold 0 must be below share_count 0"
+        );
+        assert_eq!(
+            distribute(1, 1, vec![1]),
+            "threshold 1 must be below share_count 1"
+        );
+        assert_eq!(distribute(0, 1, vec![]), "Expected 1 parties, got 0");
+        assert_eq!(distribute(0, 1, vec![0]), "Party indices start at 1");
+        assert_eq!(distribute(1, 3, vec![1, 2]), "Expected 3 parties, got 2");
+        assert_eq!(
+            distribute(1, 3, vec![1, 2, 4]),
+            "Party index 4 out of range 1..=3"
+        );
+    }
+
+    #[test]
+    fn test_try_decode_never_panics_on_random_buffers() {
+        use crate::{try_decode_point, try_decode_scalar};
+        use rand::{Rng, RngCore, SeedableRng};
+
+        let mut rng = rand_chacha::ChaCha20Rng::seed_from_u64(362);
+        for _ in 0..2000 {
+            let mut bytes = vec![0u8; rng.gen_range(0..80)];
+            rng.fill_bytes(&mut bytes);
+            if let Some(point) = try_decode_point(bytes.clone()) {
+                assert_eq!(
+                    try_decode_point(point.bytes.clone()).unwrap().bytes,
+                    point.bytes
+                );
+            }
+            if let Some(scalar) = try_decode_scalar(bytes.clone()) {
+                assert_eq!(scalar.bytes.len(), 32);
+            }
+        }
+
+        let kg = keygen(1, 2);
+        assert_eq!(
+            try_decode_point(kg.y.bytes.clone()).unwrap().bytes,
+            kg.y.bytes
+        );
+        assert!(try_decode_point(vec![0xff; 32]).is_none());
+        assert!(try_decode_scalar(vec![1; 31]).is_none());
+        assert!(try_decode_scalar(vec![1; 32]).is_some());
+    }
+
+    #[test]
+    fn test_generate_signature_with_participants_reports_signers() {
+        let _guard = serial();
+        let kg = keygen(2, 5);
+        let round = sign_round(&kg, 2, &[4, 1, 3], b"who signed");
+        let vss_sum = threshold_sig::verify_local_sigs(
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            kg.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap();
+        let signed = threshold_sig::generate_signature_with_participants(
+            vss_sum,
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            round.R.clone(),
+            Some(5),
+            Some(true),
+            Some(kg.y.clone()),
+            Some(b"who signed".to_vec()),
+            None,
+        )
+        .unwrap();
+        let mut expected = round.parties_index.clone();
+        expected.sort_unstable();
+        assert_eq!(signed.participants, expected);
+        let bitmap = signed.bitmap.unwrap();
+        assert_eq!(bitmap.len(), 1);
+        for i in 0..5u16 {
+            assert_eq!(bitmap[0] >> i & 1 == 1, expected.contains(&i));
+        }
+    }
+
+    #[test]
+    fn test_ephemeral_key_get_or_create_is_idempotent() {
+        let _guard = serial();
+        let kg = keygen(1, 2);
+        let key_id = kg.key_ids[0].clone();
+        let first = threshold_sig::ephemeral_key_get_or_create(
+            key_id.clone(),
+            b"m".to_vec(),
+            1,
+            None,
+            Some(vec![2, 1]),
+            None,
+        )
+        .unwrap();
+        let R = threshold_sig::get_ephemeral_R(first.clone()).unwrap();
+        // the signer set is recorded in any order
+        let second = threshold_sig::ephemeral_key_get_or_create(
+            key_id.clone(),
+            b"m".to_vec(),
+            1,
+            None,
+            Some(vec![1, 2]),
+            None,
+        )
+        .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(
+            threshold_sig::get_ephemeral_R(second).unwrap().bytes,
+            R.bytes
+        );
+
+        // a different message or domain replaces the nonce rather than reusing it
+        let get_or_create = |message: &[u8], domain: Option<Vec<u8>>| {
+            let id = threshold_sig::ephemeral_key_get_or_create(
+                key_id.clone(),
+                message.to_vec(),
+                1,
+                domain,
+                Some(vec![1, 2]),
+                None,
+            )
+            .unwrap();
+            threshold_sig::get_ephemeral_R(id).unwrap().bytes
+        };
+        let other = get_or_create(b"n", None);
+        assert_ne!(other, R.bytes);
+        assert_ne!(get_or_create(b"n", Some(b"app".to_vec())), other);
+    }
+
+    #[test]
+    fn test_scalar_index_conversions_round_trip() {
+        use crate::arith::{scalar_from_index, scalar_from_u64, scalar_sub, scalar_to_u64};
+
+        for index in [0u16, 1, 2, 255, 256, u16::MAX] {
+            let scalar = scalar_from_index(index);
+            assert_eq!(scalar.bytes[..2], index.to_le_bytes());
+            assert_eq!(scalar_to_u64(scalar).unwrap(), Some(i64::from(index)));
+        }
+        let max = scalar_from_u64(i64::MAX).unwrap();
+        assert_eq!(scalar_to_u64(max).unwrap(), Some(i64::MAX));
+
+        // l - 1 is far beyond 64 bits
+        let minus_one = scalar_sub(scalar_from_index(0), scalar_from_index(1)).unwrap();
+        assert_eq!(scalar_to_u64(minus_one).unwrap(), None);
+        let mut big = vec![0u8; 32];
+        big[7] = 0x80;
+        assert_eq!(
+            scalar_to_u64(SerializableScalar { bytes: big }).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_simulate_sign_verifies_under_simulated_keygen() {
+        let _guard = serial();
+        for (t, n, parties_index) in [
+            (0u16, 1u16, vec![0u16]),
+            (1, 2, vec![0, 1]),
+            (1, 3, vec![2, 0]),
+            (2, 4, vec![0, 1, 3]),
+        ] {
+            let kg = simulate_keygen(t, n, None).unwrap();
+            let signature =
+                simulate_sign(kg.clone(), parties_index, b"simulated".to_vec(), None).unwrap();
+            assert!(threshold_sig::verify_signature(
+                signature,
+                b"simulated".to_vec(),
+                kg.y,
+                None,
+                None
+            )
+            .unwrap());
+        }
+        let kg = simulate_keygen(1, 3, None).unwrap();
+        assert!(simulate_sign(kg.clone(), vec![0], b"m".to_vec(), None).is_err());
+        assert!(simulate_sign(kg, vec![0, 3], b"m".to_vec(), None).is_err());
+    }
+
+    #[test]
+    fn test_strict_subgroup_rejects_points_with_torsion() {
+        use curve25519_dalek::constants::{ED25519_BASEPOINT_POINT, EIGHT_TORSION};
+        use curve25519_dalek::scalar::Scalar as DalekScalar;
+
+        let _guard = serial();
+        let mixed = SerializablePoint {
+            bytes: (DalekScalar::from(12345u64) * ED25519_BASEPOINT_POINT + EIGHT_TORSION[1])
+                .compress()
+                .to_bytes()
+                .to_vec(),
+        };
+        assert!(!threshold_sig::is_in_prime_order_subgroup(mixed.clone()).unwrap());
+
+        let key_id = threshold_sig::phase1_create(1).unwrap();
+        let public_key = threshold_sig::get_public_key(key_id.clone()).unwrap();
+        assert!(threshold_sig::is_in_prime_order_subgroup(public_key).unwrap());
+        let (commitment, blind_factor) =
+            broadcast_parts(threshold_sig::phase1_broadcast(key_id.clone(), None).unwrap());
+        let distribute = |strict_subgroup| {
+            threshold_sig::phase1_verify_com_phase2_distribute(
+                key_id.clone(),
+                0,
+                1,
+                vec![blind_factor.clone()],
+                vec![mixed.clone()],
+                vec![commitment.clone()],
+                vec![1],
+                None,
+                strict_subgroup,
+            )
+            .unwrap_err()
+            .reason
+        };
+        assert_eq!(
+            distribute(Some(true)),
+            "NotInSubgroup: public key is not in the prime-order subgroup"
+        );
+        assert!(!distribute(None).starts_with("NotInSubgroup"));
+
+        let kg = keygen(1, 2);
+        let eph_id = threshold_sig::ephemeral_key_create(
+            kg.key_ids[0].clone(),
+            b"m".to_vec(),
+            1,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let (commitment, blind_factor) =
+            broadcast_parts(threshold_sig::ephemeral_phase1_broadcast(eph_id.clone()).unwrap());
+        let err = threshold_sig::ephemeral_phase1_verify_com_phase2_distribute(
+            eph_id,
+            1,
+            2,
+            vec![blind_factor.clone(), blind_factor],
+            vec![mixed.clone(), mixed],
+            vec![commitment.clone(), commitment],
+            vec![1, 2],
+            Some(true),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.reason,
+            "NotInSubgroup: R is not in the prime-order subgroup"
+        );
+    }
+
+    #[test]
+    fn test_enroll_party_joins_existing_group() {
+        let _guard = serial();
+        let kg = keygen(1, 3);
+        let helpers = vec![1u16, 2];
+        let contributions: Vec<Vec<SerializableScalar>> = helpers
+            .iter()
+            .map(|&i| {
+                threshold_sig::enroll_party_contribution(
+                    kg.key_ids[usize::from(i - 1)].clone(),
+                    helpers.clone(),
+                    4,
+                )
+                .unwrap()
+            })
+            .collect();
+        let forwarded_sums: Vec<SerializableScalar> = (0..helpers.len())
+            .map(|k| {
+                threshold_sig::enroll_party_forward(
+                    contributions
+                        .iter()
+                        .map(|pieces| pieces[k].clone())
+                        .collect(),
+                )
+                .unwrap()
+            })
+            .collect();
+        assert!(threshold_sig::enroll_party(
+            4,
+            forwarded_sums[..1].to_vec(),
+            kg.vss_schemes.clone(),
+            kg.y.clone()
+        )
+        .is_err());
+        let key_id =
+            threshold_sig::enroll_party(4, forwarded_sums, kg.vss_schemes.clone(), kg.y.clone())
+                .unwrap();
+
+        let mut key_ids = kg.key_ids.clone();
+        key_ids.push(key_id.clone());
+        let mut shared_keys = kg.shared_keys.clone();
+        shared_keys.push(threshold_sig::get_shared_keys(key_id).unwrap());
+        let enlarged = Keygen {
+            key_ids,
+            shared_keys,
+            vss_schemes: kg.vss_schemes.clone(),
+            y: kg.y.clone(),
+        };
+        let message = b"enrolled".to_vec();
+        let round = sign_round(&enlarged, 1, &[3, 4], &message);
+        let signature = aggregate(&enlarged, &round);
+        assert!(threshold_sig::verify_signature(signature, message, kg.y, None, None).unwrap());
+    }
+
+    #[test]
+    fn test_verify_signature_auto_accepts_each_encoding() {
+        use crate::signature_format::verify_signature_auto;
+        use napi::bindgen_prelude::Either3;
+
+        // RFC 8032 section 7.1, TEST 1
+        let public_key = SerializablePoint {
+            bytes: hex::decode("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+                .unwrap(),
+        };
+        let hex_signature = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e06522490155\
+                             5fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b";
+        let bytes = hex::decode(hex_signature).unwrap();
+        let verify = |input| verify_signature_auto(input, vec![], public_key.clone());
+
+        let inputs = [
+            Either3::A(bytes.clone()),
+            Either3::B(hex_signature.to_string()),
+            Either3::B(format!("0x{}", hex_signature.to_uppercase())),
+            Either3::B(
+                "5VZDAMNgrHKQhuLMgG6CioSHfx645dl02HPgZSJJAVVfuIIVkKM7rMYeOXAc+bRr0lv18FlbviRlUUFDjnoQCw=="
+                    .to_string(),
+            ),
+            Either3::B(
+                "5VZDAMNgrHKQhuLMgG6CioSHfx645dl02HPgZSJJAVVfuIIVkKM7rMYeOXAc-bRr0lv18FlbviRlUUFDjnoQCw"
+                    .to_string(),
+            ),
+            Either3::C(SerializableSignature {
+                R: SerializablePoint {
+                    bytes: bytes[..32].to_vec(),
+                },
+                s: SerializableScalar {
+                    bytes: bytes[32..].to_vec(),
+                },
+            }),
+        ];
+        for input in inputs {
+            assert!(verify(input).unwrap());
+        }
+        assert!(!verify_signature_auto(
+            Either3::A(bytes.clone()),
+            b"x".to_vec(),
+            public_key.clone()
+        )
+        .unwrap());
+
+        for input in [
+            Either3::A(bytes[..63].to_vec()),
+            Either3::B("not a signature".to_string()),
+            Either3::B(hex_signature[..126].to_string()),
+            // both base64 alphabets at once
+            Either3::B(
+                "5VZDAMNgrHKQhuLMgG6CioSHfx645dl02HPgZSJJAVVfuIIVkKM7rMYeOXAc+bRr0lv18FlbviRlUUFDjnoQC_"
+                    .to_string(),
+            ),
+        ] {
+            assert!(verify(input).unwrap_err().reason.starts_with("Unrecognized signature encoding"));
+        }
+    }
+
+    #[test]
+    fn test_share_count_above_cap_is_rejected() {
+        let _guard = serial();
+        let key_id = threshold_sig::phase1_create(1).unwrap();
+        let public_key = threshold_sig::get_public_key(key_id.clone()).unwrap();
+        let (commitment, blind_factor) =
+            broadcast_parts(threshold_sig::phase1_broadcast(key_id.clone(), None).unwrap());
+        let distribute = |share_count: u16| {
+            threshold_sig::phase1_verify_com_phase2_distribute(
+                key_id.clone(),
+                1,
+                share_count,
+                vec![blind_factor.clone()],
+                vec![public_key.clone()],
+                vec![commitment.clone()],
+                vec![1],
+                None,
+                None,
+            )
+            .unwrap_err()
+            .reason
+        };
+        assert_eq!(
+            distribute(u16::MAX),
+            "ShareCountTooLarge: 65535 parties exceed the limit of 1024"
+        );
+
+        // party indices are bounded the same way, before curv adds one to them
+        let kg = keygen(1, 2);
+        let round = sign_round(&kg, 1, &[1, 2], b"cap");
+        let err = threshold_sig::verify_local_sigs(
+            round.local_sigs.clone(),
+            vec![0, u16::MAX],
+            kg.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap_err();
+        assert!(err.reason.starts_with("ShareCountTooLarge"));
+
+        threshold_sig::set_max_share_count(2000).unwrap();
+        let raised = distribute(1500);
+        threshold_sig::set_max_share_count(1024).unwrap();
+        assert_eq!(raised, "Expected 1500 parties, got 1");
+    }
+
+    #[test]
+    fn test_phase2_receive_share_flags_tampered_share_on_arrival() {
+        let _guard = serial();
+        let parties = vec![1u16, 2, 3];
+        let key_ids: Vec<String> = parties
+            .iter()
+            .map(|&i| threshold_sig::phase1_create(i).unwrap())
+            .collect();
+        let public_keys: Vec<SerializablePoint> = key_ids
+            .iter()
+            .map(|id| threshold_sig::get_public_key(id.clone()).unwrap())
+            .collect();
+        let (commitments, blind_factors): (Vec<_>, Vec<_>) = key_ids
+            .iter()
+            .map(|id| broadcast_parts(threshold_sig::phase1_broadcast(id.clone(), None).unwrap()))
+            .unzip();
+        let (vss_schemes, secret_shares): (Vec<_>, Vec<_>) = key_ids
+            .iter()
+            .map(|id| {
+                distribute_parts(
+                    threshold_sig::phase1_verify_com_phase2_distribute(
+                        id.clone(),
+                        1,
+                        3,
+                        blind_factors.clone(),
+                        public_keys.clone(),
+                        commitments.clone(),
+                        parties.clone(),
+                        None,
+                        None,
+                    )
+                    .unwrap(),
+                )
+            })
+            .unzip();
+
+        // party 1 receives the shares dealt to it one by one, out of order
+        let session_id =
+            threshold_sig::phase2_open(key_ids[0].clone(), 1, 3, public_keys.clone()).unwrap();
+        let receive = |from: usize, share: SerializableScalar| {
+            threshold_sig::phase2_receive_share(
+                session_id.clone(),
+                from as u16 + 1,
+                share,
+                vss_schemes[from].clone(),
+            )
+            .unwrap()
+        };
+        assert!(receive(2, secret_shares[2][0].clone()));
+        let tampered = crate::arith::scalar_add(
+            secret_shares[1][0].clone(),
+            crate::arith::scalar_from_index(1),
+        )
+        .unwrap();
+        assert!(!receive(1, tampered));
+        assert!(receive(0, secret_shares[0][0].clone()));
+        assert_eq!(
+            threshold_sig::phase2_construct(session_id.clone())
+                .unwrap_err()
+                .reason,
+            "No share received from party 2"
+        );
+        assert!(receive(1, secret_shares[1][0].clone()));
+        let shared_keys = threshold_sig::phase2_construct(session_id).unwrap();
+
+        let other = threshold_sig::phase2_verify_vss_construct_keypair(
+            key_ids[1].clone(),
+            1,
+            3,
+            public_keys,
+            secret_shares
+                .iter()
+                .map(|shares| shares[1].clone())
+                .collect(),
+            vss_schemes,
+            2,
+            None,
+        )
+        .unwrap();
+        assert_eq!(shared_keys.y.bytes, other.y.bytes);
+    }
+
+    #[test]
+    fn test_repeated_party_index_is_rejected() {
+        let _guard = serial();
+        let kg = keygen(1, 3);
+        let round = sign_round(&kg, 1, &[1, 2], b"twice");
+        let local_sigs = vec![round.local_sigs[0].clone(), round.local_sigs[0].clone()];
+        let err = threshold_sig::verify_local_sigs(
+            local_sigs.clone(),
+            vec![0, 0],
+            kg.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.reason,
+            "DuplicatePartyIndex: party index 0 appears more than once"
+        );
+
+        let vss_sum = threshold_sig::verify_local_sigs(
+            round.local_sigs.clone(),
+            round.parties_index.clone(),
+            kg.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap();
+        let err = threshold_sig::generate_signature(
+            vss_sum,
+            local_sigs,
+            vec![1, 1],
+            round.R.clone(),
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(err.reason.starts_with("DuplicatePartyIndex"));
+
+        // the signer set of an ephemeral round
+        let eph_id = threshold_sig::ephemeral_key_create(
+            kg.key_ids[0].clone(),
+            b"m".to_vec(),
+            1,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let err = threshold_sig::ephemeral_phase1_verify_com_phase2_distribute(
+            eph_id,
+            1,
+            2,
+            vec![],
+            vec![],
+            vec![],
+            vec![1, 1],
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.reason,
+            "DuplicatePartyIndex: party index 1 appears more than once"
+        );
+    }
+
+    #[test]
+    fn test_verify_signature_for_key_matches_bytes_verifier() {
+        let _guard = serial();
+        let kg = keygen(1, 3);
+        let message = b"by key id".to_vec();
+        for hash in [None, Some(threshold_sig::ChallengeHash::Keccak256)] {
+            let round = sign_round_with_hash(&kg, 1, &[1, 3], &message, hash);
+            let signature = aggregate(&kg, &round);
+            for cofactored in [None, Some(true)] {
+                for (message, expected) in [(message.clone(), true), (b"other".to_vec(), false)] {
+                    let by_bytes = threshold_sig::verify_signature(
+                        signature.clone(),
+                        message.clone(),
+                        kg.y.clone(),
+                        cofactored,
+                        hash,
+                    )
+                    .unwrap();
+                    assert_eq!(by_bytes, expected, "{:?} {:?}", hash, cofactored);
+                    for key_id in &kg.key_ids {
+                        assert_eq!(
+                            threshold_sig::verify_signature_for_key(
+                                key_id.clone(),
+                                signature.clone(),
+                                message.clone(),
+                                cofactored,
+                                hash,
+                            )
+                            .unwrap(),
+                            by_bytes
+                        );
+                    }
+                }
+            }
+        }
+
+        // a Keccak256 signature is not valid under the default SHA-512 challenge
+        let round = sign_round_with_hash(
+            &kg,
+            1,
+            &[1, 3],
+            &message,
+            Some(threshold_sig::ChallengeHash::Keccak256),
+        );
+        let signature = aggregate(&kg, &round);
+        assert!(!threshold_sig::verify_signature_for_key(
+            kg.key_ids[0].clone(),
+            signature.clone(),
+            message.clone(),
+            None,
+            None,
+        )
+        .unwrap());
+
+        let fresh = threshold_sig::phase1_create(9).unwrap();
+        let err = threshold_sig::verify_signature_for_key(fresh, signature, message, None, None)
+            .unwrap_err();
+        assert!(err.reason.starts_with("KeyNotReady"));
+    }
+
+    #[test]
+    fn test_exported_test_vectors_revalidate() {
+        let vectors = crate::test_vectors::export_test_vectors(b"vectors".to_vec(), 1, 3).unwrap();
+        assert_eq!(vectors.keygen.len(), 3);
+        assert_eq!(vectors.nonce_round.len(), 2);
+        assert!(crate::test_vectors::check_test_vectors(vectors.clone()).unwrap());
+
+        let again = crate::test_vectors::export_test_vectors(b"vectors".to_vec(), 1, 3).unwrap();
+        assert_eq!(again.signature.s.bytes, vectors.signature.s.bytes);
+
+        let mut tampered = vectors;
+        tampered.local_sigs[0].gamma_i.bytes[0] ^= 1;
+        assert!(!crate::test_vectors::check_test_vectors(tampered).unwrap());
+    }
+
+    #[test]
+    fn test_group_public_key_differs_from_individual_and_verifies() {
+        let _guard = serial();
+        let kg = keygen(1, 3);
+        let message = b"which key".to_vec();
+        let round = sign_round(&kg, 1, &[1, 2], &message);
+        let signature = aggregate(&kg, &round);
+        let group = threshold_sig::get_group_public_key(kg.key_ids[0].clone()).unwrap();
+        assert_eq!(group.bytes, kg.y.bytes);
+        for key_id in &kg.key_ids {
+            let individual = threshold_sig::get_individual_public_key(key_id.clone()).unwrap();
+            assert_eq!(
+                individual.bytes,
+                threshold_sig::get_public_key(key_id.clone()).unwrap().bytes
+            );
+            assert_ne!(individual.bytes, group.bytes);
+            assert!(!threshold_sig::verify_signature(
+                signature.clone(),
+                message.clone(),
+                individual,
+                None,
+                None
+            )
+            .unwrap());
+        }
+        assert!(threshold_sig::verify_signature(signature, message, group, None, None).unwrap());
+
+        let fresh = threshold_sig::phase1_create(9).unwrap();
+        let err = threshold_sig::get_group_public_key(fresh).unwrap_err();
+        assert!(err.reason.starts_with("KeyNotReady"));
+    }
+
+    #[test]
+    fn test_aborted_simulated_keygen_returns_aborted() {
+        use crate::abort::{abort, create_abort_token, release_abort_token};
+        let _guard = serial();
+        let token = create_abort_token().unwrap();
+        assert!(simulate_keygen(1, 3, Some(token.clone())).is_ok());
+
+        assert!(abort(token.clone()).unwrap());
+        let err = simulate_keygen(2, 16, Some(token.clone())).unwrap_err();
+        assert_eq!(err.status, napi::Status::Cancelled);
+        assert!(err.reason.starts_with("Aborted"));
+        // no key is left half-built past the first party
+        assert_eq!(lock(threshold_sig::keys_store()).ids().count(), 3);
+
+        assert!(release_abort_token(token.clone()).unwrap());
+        assert!(!abort(token.clone()).unwrap());
+        assert!(simulate_keygen(1, 3, Some(token.clone())).is_err());
+
+        // a task resolves its token when created, so aborting reaches it while it runs
+        let token = create_abort_token().unwrap();
+        let task = crate::simulate::SimulateKeygen::new(1, 3, Some(token.clone())).unwrap();
+        assert!(abort(token).unwrap());
+        assert!(task.run().unwrap_err().reason.starts_with("Aborted"));
+    }
+
+    #[cfg(not(feature = "noop"))]
+    #[test]
+    fn test_unknown_abort_token_fails_the_call_rather_than_the_task() {
+        let _guard = serial();
+        let kg = simulate_keygen(1, 3, None).unwrap();
+        let missing = || Some("abort_missing".to_string());
+        for err in [
+            crate::simulate::simulate_keygen(1, 3, missing()).err(),
+            crate::simulate::batch_keygen(1, 1, 3, missing()).err(),
+            crate::simulate::simulate_sign(kg, vec![0, 1], b"m".to_vec(), missing()).err(),
+        ] {
+            assert_eq!(err.unwrap().reason, "Abort token not found");
+        }
+    }
+
+    #[test]
+    fn test_blind_factor_with_leading_zero_round_trips_at_fixed_length() {
+        use crate::bigint::*;
+        let mut bytes = vec![0u8, 0];
+        bytes.extend((1..=30).map(|b| b as u8));
+        let minimal = bigint_from_bytes_be(bytes.clone()).unwrap();
+        assert_eq!(minimal.bytes.len(), 30);
+
+        let fixed = blind_factor_to_fixed_bytes(minimal.clone()).unwrap();
+        assert_eq!(fixed.len(), blind_factor_length() as usize);
+        assert_eq!(fixed, bytes);
+        assert_eq!(
+            blind_factor_from_fixed_bytes(fixed).unwrap().bytes,
+            minimal.bytes
+        );
+        assert!(blind_factor_from_fixed_bytes(minimal.bytes).is_err());
+        assert!(blind_factor_to_fixed_bytes(SerializableBigInt { bytes: vec![1; 33] }).is_err());
+    }
+
+    #[test]
+    fn test_fixed_broadcasts_open_their_commitments() {
+        let _guard = serial();
+        let kg = keygen(1, 2);
+        let eph_id = threshold_sig::ephemeral_key_create(
+            kg.key_ids[1].clone(),
+            b"m".to_vec(),
+            2,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let (_, blind_factor) =
+            broadcast_parts(threshold_sig::ephemeral_phase1_broadcast_fixed(eph_id).unwrap());
+        assert_eq!(blind_factor.bytes.len(), 32);
+
+        let parties = vec![1u16, 2];
+        let key_ids: Vec<String> = parties
+            .iter()
+            .map(|&i| threshold_sig::phase1_create(i).unwrap())
+            .collect();
+        let public_keys: Vec<SerializablePoint> = key_ids
+            .iter()
+            .map(|id| threshold_sig::get_public_key(id.clone()).unwrap())
+            .collect();
+        let (commitments, blind_factors): (Vec<_>, Vec<_>) = key_ids
+            .iter()
+            .map(|id| {
+                broadcast_parts(threshold_sig::phase1_broadcast_fixed(id.clone(), None).unwrap())
+            })
+            .unzip();
+        assert!(blind_factors.iter().all(|b| b.bytes.len() == 32));
+        threshold_sig::phase1_verify_com_phase2_distribute(
+            key_ids[0].clone(),
+            1,
+            2,
+            blind_factors,
+            public_keys,
+            commitments,
+            parties,
+            None,
+            None,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_check_vss_consistency_flags_equivocating_dealer() {
+        let _guard = serial();
+        let parties = vec![1u16, 2, 3];
+        let key_ids: Vec<String> = parties
+            .iter()
+            .map(|&i| threshold_sig::phase1_create(i).unwrap())
+            .collect();
+        let public_keys: Vec<SerializablePoint> = key_ids
+            .iter()
+            .map(|id| threshold_sig::get_public_key(id.clone()).unwrap())
+            .collect();
+        let (commitments, blind_factors): (Vec<_>, Vec<_>) = key_ids
+            .iter()
+            .map(|id| broadcast_parts(threshold_sig::phase1_broadcast(id.clone(), None).unwrap()))
+            .unzip();
+        let (vss_schemes, mut shares_matrix): (Vec<_>, Vec<_>) = key_ids
+            .iter()
+            .map(|id| {
+                distribute_parts(
+                    threshold_sig::phase1_verify_com_phase2_distribute(
+                        id.clone(),
+                        1,
+                        3,
+                        blind_factors.clone(),
+                        public_keys.clone(),
+                        commitments.clone(),
+                        parties.clone(),
+                        None,
+                        None,
+                    )
+                    .unwrap(),
+                )
+            })
+            .unzip();
+
+        let report = threshold_sig::check_vss_consistency(
+            vss_schemes.clone(),
+            shares_matrix.clone(),
+            parties.clone(),
+        )
+        .unwrap();
+        assert!(report.consistent);
+        assert!(report.mismatches.is_empty());
+
+        // dealer 2 hands party 3 a share from another polynomial
+        shares_matrix[1][2] = shares_matrix[0][2].clone();
+        let report =
+            threshold_sig::check_vss_consistency(vss_schemes, shares_matrix, parties).unwrap();
+        assert!(!report.consistent);
+        assert!(report.errors.is_empty());
+        assert_eq!(report.equivocating_dealers, vec![2]);
+        assert_eq!(report.mismatches.len(), 1);
+        assert_eq!(
+            (report.mismatches[0].dealer, report.mismatches[0].recipient),
+            (2, 3)
+        );
+    }
+
+    #[test]
+    fn test_derived_aggregate_R_matches_signing_R() {
+        let _guard = serial();
+        let kg = keygen(1, 3);
+        let message = b"derive R".to_vec();
+        let round = sign_round(&kg, 1, &[2, 3], &message);
+        let R = threshold_sig::derive_aggregate_R_from_vss(
+            round.vss_ephemeral.clone(),
+            Some(round.R.clone()),
+        )
+        .unwrap();
+        assert_eq!(R.bytes, round.R.bytes);
+        let signature = aggregate(&kg, &round);
+        assert_eq!(signature.R.bytes, R.bytes);
+        assert!(
+            threshold_sig::verify_signature(signature, message, kg.y.clone(), None, None).unwrap()
+        );
+
+        let err = threshold_sig::derive_aggregate_R_from_vss(round.vss_ephemeral, Some(kg.y))
+            .unwrap_err();
+        assert!(err.reason.starts_with("RInconsistent"));
+        assert!(threshold_sig::derive_aggregate_R_from_vss(vec![], None).is_err());
+    }
+
+    #[cfg(feature = "persistent-store")]
+    #[test]
+    fn test_persistent_store_survives_restart() {
+        use crate::persist::use_persistent_store;
+        let _guard = serial();
+        let path = std::env::temp_dir().join(format!("tss-persist-{}.redb", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let path = path.to_str().unwrap().to_string();
+        let restart = || {
+            *lock(threshold_sig::keys_store()) = Store::new();
+            *lock(threshold_sig::ephemeral_keys_store()) = Store::new();
+        };
+
+        use_persistent_store(path.clone(), "correct horse".to_string()).unwrap();
+        let kg = keygen(1, 2);
+        let eph_id = threshold_sig::ephemeral_key_create(
+            kg.key_ids[0].clone(),
+            b"persisted".to_vec(),
+            1,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let R = threshold_sig::get_ephemeral_R(eph_id.clone()).unwrap();
+
+        restart();
+        assert!(threshold_sig::get_shared_keys(kg.key_ids[0].clone()).is_err());
+        assert!(use_persistent_store(path.clone(), "wrong".to_string()).is_err());
+        use_persistent_store(path.clone(), "correct horse".to_string()).unwrap();
+        assert_eq!(
+            threshold_sig::get_shared_keys(kg.key_ids[1].clone())
+                .unwrap()
+                .x_i
+                .bytes,
+            kg.shared_keys[1].x_i.bytes
+        );
+        assert_eq!(
+            threshold_sig::get_ephemeral_R(eph_id).unwrap().bytes,
+            R.bytes
+        );
+
+        let message = b"after restart".to_vec();
+        let round = sign_round(&kg, 1, &[1, 2], &message);
+        let signature = aggregate(&kg, &round);
+        assert!(
+            threshold_sig::verify_signature(signature, message, kg.y.clone(), None, None).unwrap()
+        );
+
+        restart();
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_local_sig_binds_only_its_own_party() {
+        let _guard = serial();
+        let kg = keygen(1, 3);
+        let round = sign_round(&kg, 1, &[1, 2, 3], b"attribution");
+        for (local_sig, &party_index) in round.local_sigs.iter().zip(&round.parties_index) {
+            assert!(threshold_sig::local_sig_binds_party(
+                local_sig.clone(),
+                party_index,
+                kg.vss_schemes.clone(),
+                round.vss_ephemeral.clone(),
+            )
+            .unwrap());
+        }
+        // the signature of 0-based index 1 is not the one index 2 owes
+        assert!(!threshold_sig::local_sig_binds_party(
+            round.local_sigs[1].clone(),
+            2,
+            kg.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+        )
+        .unwrap());
+    }
+
+    // Counts the bytes allocated on the current thread while `counting_allocations` runs
+    struct CountingAllocator;
+
+    thread_local! {
+        static COUNTING: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+        static ALLOCATED: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+            if COUNTING.try_with(|c| c.get()).unwrap_or(false) {
+                let _ = ALLOCATED.try_with(|a| a.set(a.get() + layout.size()));
+            }
+            std::alloc::System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+            std::alloc::System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn counting_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+        ALLOCATED.with(|a| a.set(0));
+        COUNTING.with(|c| c.set(true));
+        let result = f();
+        COUNTING.with(|c| c.set(false));
+        (result, ALLOCATED.with(|a| a.get()))
+    }
+
+    // Buffer itself only links inside Node, so this drives the borrowed-slice path the
+    // Buffer variants deref into
+    #[test]
+    fn test_buffer_message_path_matches_vec_without_copying() {
+        let _guard = serial();
+        let kg = keygen(1, 2);
+        let message = vec![0x5a; 1 << 20];
+        let round = sign_round(&kg, 1, &[1, 2], &message);
+
+        // napi copies a Buffer into a fresh Vec<u8>, which the clone stands in for
+        let (by_vec, vec_allocated) = counting_allocations(|| {
+            threshold_sig::compute_local_sig(
+                message.clone(),
+                round.eph_shared_keys[0].clone(),
+                kg.shared_keys[0].clone(),
+                None,
+                None,
+            )
+            .unwrap()
+        });
+        let (by_slice, slice_allocated) = counting_allocations(|| {
+            threshold_sig::compute_local_sig_bytes(
+                &message,
+                round.eph_shared_keys[0].clone(),
+                kg.shared_keys[0].clone(),
+                None,
+                None,
+            )
+            .unwrap()
+        });
+        assert_eq!(by_slice.gamma_i.bytes, by_vec.gamma_i.bytes);
+        assert_eq!(by_slice.gamma_i.bytes, round.local_sigs[0].gamma_i.bytes);
+        assert!(vec_allocated >= message.len());
+        assert!(slice_allocated < message.len() / 16);
+
+        let signature = aggregate(&kg, &round);
+        assert!(
+            threshold_sig::verify_signature_bytes(&signature, &message, &kg.y, None, None).unwrap()
+        );
+        assert!(
+            !threshold_sig::verify_signature_bytes(&signature, b"other", &kg.y, None, None)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_zeroed_prefix_is_rejected_on_import_and_signing() {
+        let _guard = serial();
+        let kg = keygen(1, 2);
+        let mut backup = threshold_sig::export_shared_keys(kg.key_ids[0].clone()).unwrap();
+        let body_len = backup.len() - 32;
+        backup[body_len - 32..body_len].fill(0);
+        let checksum = threshold_sig::backup_checksum(&backup[..body_len]);
+        backup[body_len..].copy_from_slice(&checksum);
+        let err =
+            threshold_sig::import_and_verify_shared_keys(backup, b"import".to_vec()).unwrap_err();
+        assert!(err.reason.starts_with("InvalidPrefix"));
+
+        let round = sign_round(&kg, 1, &[1, 2], b"prefix");
+        let mut shared_keys = kg.shared_keys[0].clone();
+        shared_keys.prefix = SerializableScalar { bytes: vec![0; 32] };
+        let err = threshold_sig::compute_local_sig(
+            b"prefix".to_vec(),
+            round.eph_shared_keys[0].clone(),
+            shared_keys.clone(),
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(err.reason.starts_with("InvalidPrefix"));
+
+        shared_keys.prefix = shared_keys.x_i.clone();
+        let err = threshold_sig::compute_local_sig(
+            b"prefix".to_vec(),
+            round.eph_shared_keys[0].clone(),
+            shared_keys,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(err.reason.contains("equals x_i"));
+    }
+
+    #[test]
+    fn test_random_ephemeral_mode_ignores_prefix_and_message() {
+        let _guard = serial();
+        let kg = keygen(1, 2);
+        let key_id = kg.key_ids[0].clone();
+        assert_eq!(
+            threshold_sig::get_ephemeral_mode(key_id.clone()).unwrap(),
+            threshold_sig::EphemeralMode::Deterministic
+        );
+
+        let ephemeral_R = |seed: Option<&[u8]>| {
+            if let Some(seed) = seed {
+                crate::rng::set_deterministic_rng(seed.to_vec()).unwrap();
+            }
+            let eph_id = threshold_sig::ephemeral_key_create(
+                key_id.clone(),
+                b"mode".to_vec(),
+                1,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+            let R = threshold_sig::get_ephemeral_R(eph_id).unwrap();
+            *lock(threshold_sig::ephemeral_keys_store()) = Store::new();
+            R.bytes
+        };
+        let deterministic = ephemeral_R(Some(b"mode"));
+        assert_eq!(deterministic, ephemeral_R(Some(b"mode")));
+
+        threshold_sig::set_ephemeral_mode(key_id.clone(), threshold_sig::EphemeralMode::Random)
+            .unwrap();
+        assert_eq!(
+            threshold_sig::get_ephemeral_mode(key_id.clone()).unwrap(),
+            threshold_sig::EphemeralMode::Random
+        );
+        assert_ne!(deterministic, ephemeral_R(Some(b"mode")));
+        let first = ephemeral_R(None);
+        assert_ne!(first, ephemeral_R(None));
+        crate::rng::set_deterministic_rng(vec![]).unwrap();
+
+        // signing still works with nonces from random mode
+        threshold_sig::set_ephemeral_mode(
+            kg.key_ids[1].clone(),
+            threshold_sig::EphemeralMode::Random,
+        )
+        .unwrap();
+        let round = sign_round(&kg, 1, &[1, 2], b"mode");
+        aggregate(&kg, &round);
+    }
+
+    #[test]
+    fn test_verify_exclusive_finds_the_single_signed_message() {
+        let _guard = serial();
+        let kg = keygen(1, 3);
+        let round = sign_round(&kg, 1, &[2, 3], b"context b");
+        let signature = aggregate(&kg, &round);
+
+        let messages = vec![
+            b"context a".to_vec(),
+            b"context b".to_vec(),
+            b"context c".to_vec(),
+            Vec::new(),
+        ];
+        let result = threshold_sig::verify_exclusive(signature, messages, kg.y.clone()).unwrap();
+        assert_eq!(result.valid_indices, vec![1]);
+    }
+
+    #[test]
+    fn test_keygen_rejects_parties_sharing_a_seed() {
+        let _guard = serial();
+        let seed = [7u8; 32];
+        let key_ids: Vec<String> = vec![
+            threshold_sig::phase1_create_from_private_key(1, seed.to_vec()).unwrap(),
+            threshold_sig::phase1_create(2).unwrap(),
+            threshold_sig::phase1_create_from_private_key(3, seed.to_vec()).unwrap(),
+        ];
+        let public_keys: Vec<SerializablePoint> = key_ids
+            .iter()
+            .map(|id| threshold_sig::get_public_key(id.clone()).unwrap())
+            .collect();
+        let (commitments, blind_factors): (Vec<_>, Vec<_>) = key_ids
+            .iter()
+            .map(|id| broadcast_parts(threshold_sig::phase1_broadcast(id.clone(), None).unwrap()))
+            .unzip();
+        let err = threshold_sig::phase1_verify_com_phase2_distribute(
+            key_ids[1].clone(),
+            1,
+            3,
+            blind_factors,
+            public_keys,
+            commitments,
+            vec![1, 2, 3],
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.reason,
+            "DuplicatePublicKey: public keys 0 and 2 are the same point"
+        );
+    }
+
+    #[test]
+    fn test_single_party_secret_exports_only_for_one_of_one_keys() {
+        let _guard = serial();
+        let seed = vec![9u8; 32];
+        let key_id = threshold_sig::import_single_party_key(seed.clone()).unwrap();
+        let err = threshold_sig::export_single_party_secret(key_id.clone(), false).unwrap_err();
+        assert_eq!(err.status, napi::Status::InvalidArg);
+        assert_eq!(
+            threshold_sig::export_single_party_secret(key_id, true).unwrap(),
+            seed
+        );
+
+        // keys from a private key drop the seed, as they may be shares of a threshold key
+        let secret_key_id =
+            threshold_sig::phase1_create_from_private_key(1, vec![3u8; 32]).unwrap();
+        assert!(lock(threshold_sig::keys_store())
+            .get(&secret_key_id)
+            .unwrap()
+            .seed
+            .is_none());
+        let err =
+            threshold_sig::export_single_party_secret(secret_key_id.clone(), true).unwrap_err();
+        assert!(err.reason.starts_with("KeyNotReady"));
+        // and threshold shares are always refused
+        let kg = keygen(1, 2);
+        let err =
+            threshold_sig::export_single_party_secret(kg.key_ids[0].clone(), true).unwrap_err();
+        assert_eq!(err.reason, "Key is a share of a multi-party key");
+    }
+
+    #[test]
+    fn test_group_public_key_from_vss_matches_phase2() {
+        let _guard = serial();
+        let kg = keygen(2, 4);
+        let y = threshold_sig::compute_group_public_key(kg.vss_schemes.clone()).unwrap();
+        assert_eq!(y.bytes, kg.y.bytes);
+        for shared_keys in &kg.shared_keys {
+            assert_eq!(y.bytes, shared_keys.y.bytes);
+        }
+        let err = threshold_sig::compute_group_public_key(vec![]).unwrap_err();
+        assert_eq!(err.reason, "EmptyInput: vss_schemes must not be empty");
+    }
+
+    #[test]
+    fn test_empty_message_signs_and_verifies_against_reference() {
+        let _guard = serial();
+        let kg = keygen(1, 3);
+        let round = sign_round(&kg, 1, &[1, 3], b"");
+        let signature = aggregate(&kg, &round);
+        assert!(threshold_sig::verify_signature(
+            signature.clone(),
+            vec![],
+            kg.y.clone(),
+            None,
+            None
+        )
+        .unwrap());
+        assert!(threshold_sig::verify_signature(
+            signature.clone(),
+            vec![],
+            kg.y.clone(),
+            Some(true),
+            None
+        )
+        .unwrap());
+        assert!(!threshold_sig::verify_signature(
+            signature.clone(),
+            vec![0],
+            kg.y.clone(),
+            None,
+            None
+        )
+        .unwrap());
+
+        let public_key = ed25519_compact::PublicKey::from_slice(&kg.y.bytes).unwrap();
+        let reference = ed25519_compact::Signature::from_slice(
+            &[signature.R.bytes, signature.s.bytes].concat(),
+        )
+        .unwrap();
+        public_key.verify(b"", &reference).unwrap();
+        assert!(public_key.verify(b"\0", &reference).is_err());
+    }
+
+    #[test]
+    fn test_backups_carry_their_encoding_version() {
+        let _guard = serial();
+        assert_eq!(threshold_sig::encoding_version(), 1);
+        let kg = keygen(0, 1);
+        let backup = threshold_sig::export_shared_keys(kg.key_ids[0].clone()).unwrap();
+        assert_eq!(backup[0], 1);
+        let restored =
+            threshold_sig::import_and_verify_shared_keys(backup.clone(), b"v1".to_vec()).unwrap();
+        assert_eq!(restored.self_test_passed, Some(true));
+        assert_eq!(restored.public_key.bytes, kg.y.bytes);
+
+        // unversioned backups from before the version byte are still read
+        let body_len = backup.len() - 32;
+        let mut legacy = backup[1..body_len].to_vec();
+        legacy.extend(threshold_sig::backup_checksum(&legacy));
+        let restored =
+            threshold_sig::import_and_verify_shared_keys(legacy, b"v0".to_vec()).unwrap();
+        assert_eq!(restored.public_key.bytes, kg.y.bytes);
+
+        let mut future = backup;
+        future[0] = 99;
+        let checksum = threshold_sig::backup_checksum(&future[..body_len]);
+        future[body_len..].copy_from_slice(&checksum);
+        let err =
+            threshold_sig::import_and_verify_shared_keys(future, b"v99".to_vec()).unwrap_err();
+        assert_eq!(
+            err.reason,
+            "UnsupportedEncodingVersion: encoding version 99 is not supported, this build reads versions up to 1"
+        );
+    }
+
+    #[test]
+    fn test_keygen_completes_from_assembled_broadcast_messages() {
+        let _guard = serial();
+        let (t, n) = (1, 3);
+        let key_ids: Vec<String> = (1..=n)
+            .map(|i| threshold_sig::phase1_create(i).unwrap())
+            .collect();
+        let mut messages: Vec<SerializableKeygenMessage1> = key_ids
+            .iter()
+            .map(|id| threshold_sig::phase1_broadcast_message(id.clone(), None).unwrap())
+            .collect();
+        assert_eq!(
+            messages[1].public_key.bytes,
+            threshold_sig::get_public_key(key_ids[1].clone())
+                .unwrap()
+                .bytes
+        );
+        // arrival order does not matter
+        messages.reverse();
+
+        let (vss_schemes, secret_shares): (Vec<_>, Vec<_>) = key_ids
+            .iter()
+            .map(|id| {
+                distribute_parts(
+                    threshold_sig::phase1_verify_com_phase2_distribute_messages(
+                        id.clone(),
+                        t,
+                        n,
+                        messages.clone(),
+                        None,
+                        None,
+                    )
+                    .unwrap(),
+                )
+            })
+            .unzip();
+        let public_keys: Vec<SerializablePoint> = key_ids
+            .iter()
+            .map(|id| threshold_sig::get_public_key(id.clone()).unwrap())
+            .collect();
+        let shared_keys: Vec<SerializableSharedKeys> = key_ids
+            .iter()
+            .enumerate()
+            .map(|(i, id)| {
+                threshold_sig::phase2_verify_vss_construct_keypair(
+                    id.clone(),
+                    t,
+                    n,
+                    public_keys.clone(),
+                    secret_shares
+                        .iter()
+                        .map(|shares: &Vec<SerializableScalar>| shares[i].clone())
+                        .collect(),
+                    vss_schemes.clone(),
+                    i as u16 + 1,
+                    None,
+                )
+                .unwrap()
+            })
+            .collect();
+        assert!(shared_keys
+            .iter()
+            .all(|keys| keys.y.bytes == shared_keys[0].y.bytes));
+
+        let mut duplicated = messages.clone();
+        duplicated[0].party_index = duplicated[1].party_index;
+        let err = threshold_sig::phase1_verify_com_phase2_distribute_messages(
+            key_ids[0].clone(),
+            t,
+            n,
+            duplicated,
+            None,
+            None,
+        )
+        .unwrap_err();
+        assert!(err.reason.starts_with("DuplicatePartyIndex"));
+    }
+
+    #[test]
+    fn test_chain_presets_disagree_on_torsion() {
+        use curve25519_dalek::constants::{ED25519_BASEPOINT_POINT, EIGHT_TORSION};
+        use curve25519_dalek::edwards::EdwardsPoint;
+        use curve25519_dalek::scalar::Scalar as DalekScalar;
+        use curve25519_dalek::traits::IsIdentity;
+        use sha2::{Digest, Sha512};
+        use threshold_sig::ChainPreset;
+
+        let challenge = |R: &EdwardsPoint, A: &EdwardsPoint, message: &[u8]| {
+            let digest = Sha512::new()
+                .chain(R.compress().as_bytes())
+                .chain(A.compress().as_bytes())
+                .chain(message)
+                .finalize();
+            let mut wide = [0u8; 64];
+            wide.copy_from_slice(&digest);
+            DalekScalar::from_bytes_mod_order_wide(&wide)
+        };
+        // R and A encoded after `prefix`, which is empty or the 0x40 point prefix
+        let prefixed_verdicts =
+            |prefix: &[u8], R: EdwardsPoint, s: DalekScalar, A: EdwardsPoint, message: &[u8]| {
+                let encode = |P: EdwardsPoint| SerializablePoint {
+                    bytes: [prefix, P.compress().as_bytes()].concat(),
+                };
+                [
+                    ChainPreset::Rfc8032,
+                    ChainPreset::SolanaStrict,
+                    ChainPreset::Zip215,
+                ]
+                .map(|chain| {
+                    threshold_sig::verify_signature_strict_chain(
+                        SerializableSignature {
+                            R: encode(R),
+                            s: SerializableScalar {
+                                bytes: s.to_bytes().to_vec(),
+                            },
+                        },
+                        message.to_vec(),
+                        encode(A),
+                        Some(chain),
+                    )
+                    .unwrap()
+                })
+            };
+        let verdicts = |R: EdwardsPoint, s: DalekScalar, A: EdwardsPoint, message: &[u8]| {
+            prefixed_verdicts(&[], R, s, A, message)
+        };
+        let a = DalekScalar::from_bytes_mod_order([5; 32]);
+        let r = DalekScalar::from_bytes_mod_order([11; 32]);
+        let torsion = EIGHT_TORSION[1];
+
+        // R carries an order-8 component: only the cofactored equation clears it
+        let A = a * ED25519_BASEPOINT_POINT;
+        let R = r * ED25519_BASEPOINT_POINT + torsion;
+        let s = r + challenge(&R, &A, b"torsion") * a;
+        assert_eq!(verdicts(R, s, A, b"torsion"), [false, false, true]);
+        assert_eq!(
+            prefixed_verdicts(&[0x40], R, s, A, b"torsion"),
+            [false, false, true]
+        );
+
+        // A carries it instead, on a message whose challenge k makes k*T vanish: the
+        // cofactorless equation then holds too, and only the strict preset refuses
+        let A = a * ED25519_BASEPOINT_POINT + torsion;
+        let R = r * ED25519_BASEPOINT_POINT;
+        let message = (0u32..)
+            .map(|i| i.to_le_bytes().to_vec())
+            .find(|message| (challenge(&R, &A, message) * torsion).is_identity())
+            .unwrap();
+        let s = r + challenge(&R, &A, &message) * a;
+        assert_eq!(verdicts(R, s, A, &message), [true, false, true]);
+        assert_eq!(
+            prefixed_verdicts(&[0x40], R, s, A, &message),
+            [true, false, true]
+        );
+
+        // a threshold signature is accepted everywhere
+        let _guard = serial();
+        let kg = keygen(1, 2);
+        let round = sign_round(&kg, 1, &[1, 2], b"chain");
+        let signature = aggregate(&kg, &round);
+        for chain in [
+            None,
+            Some(ChainPreset::SolanaStrict),
+            Some(ChainPreset::Zip215),
+        ] {
+            assert!(threshold_sig::verify_signature_strict_chain(
+                signature.clone(),
+                b"chain".to_vec(),
+                kg.y.clone(),
+                chain
+            )
+            .unwrap());
+        }
+    }
+
+    #[test]
+    fn test_dealt_shares_sign_under_the_original_key() {
+        let _guard = serial();
+        let seed = [42u8; 32];
+        let original = threshold_sig::import_single_party_key(seed.to_vec()).unwrap();
+        let original_key = threshold_sig::get_group_public_key(original).unwrap();
+
+        let deal = crate::dealer::deal_secret(seed.to_vec(), 1, 3).unwrap();
+        assert_eq!(deal.y.bytes, original_key.bytes);
+        assert_eq!(deal.vss.commitments[0].bytes, original_key.bytes);
+        let key_ids: Vec<String> = deal
+            .backups
+            .iter()
+            .map(|backup| {
+                threshold_sig::import_and_verify_shared_keys(backup.clone(), b"deal".to_vec())
+                    .unwrap()
+                    .shared_key_id
+            })
+            .collect();
+        let kg = Keygen {
+            key_ids,
+            shared_keys: deal.shared_keys.clone(),
+            vss_schemes: vec![deal.vss.clone()],
+            y: deal.y.clone(),
+        };
+        let round = sign_round(&kg, 1, &[1, 3], b"dealt");
+        let signature = aggregate(&kg, &round);
+        assert!(threshold_sig::verify_signature(
+            signature,
+            b"dealt".to_vec(),
+            original_key,
+            None,
+            None
+        )
+        .unwrap());
+
+        let err = crate::dealer::deal_secret(vec![0; 31], 1, 3).unwrap_err();
+        assert_eq!(err.reason, "Secret must be 32 bytes");
+    }
+
+    #[test]
+    fn test_streamed_verification_matches_one_shot() {
+        let _guard = serial();
+        let kg = keygen(1, 2);
+        let message: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        let tampered = [&message[..5000], b"x", &message[5001..]].concat();
+
+        for hash in [
+            None,
+            Some(threshold_sig::ChallengeHash::Sha256),
+            Some(threshold_sig::ChallengeHash::Keccak256),
+            Some(threshold_sig::ChallengeHash::Blake2b),
+        ] {
+            let round = sign_round_with_hash(&kg, 1, &[1, 2], &message, hash);
+            let signature = aggregate(&kg, &round);
+            for cofactored in [None, Some(true)] {
+                let streamed = |message: &[u8]| {
+                    let hasher_id = threshold_sig::verify_hasher_new(
+                        signature.clone(),
+                        kg.y.clone(),
+                        cofactored,
+                        hash,
+                    )
+                    .unwrap();
+                    for chunk in message.chunks(999) {
+                        threshold_sig::verify_hasher_update(hasher_id.clone(), chunk.to_vec())
+                            .unwrap();
+                    }
+                    threshold_sig::verify_signature_streamed(
+                        hasher_id,
+                        signature.clone(),
+                        kg.y.clone(),
+                    )
+                    .unwrap()
+                };
+                let one_shot = |message: &[u8]| {
+                    threshold_sig::verify_signature(
+                        signature.clone(),
+                        message.to_vec(),
+                        kg.y.clone(),
+                        cofactored,
+                        hash,
+                    )
+                    .unwrap()
+                };
+                assert!(streamed(&message), "{:?} {:?}", hash, cofactored);
+                assert_eq!(streamed(&message), one_shot(&message));
+                assert!(!streamed(&tampered), "{:?} {:?}", hash, cofactored);
+                assert_eq!(streamed(&tampered), one_shot(&tampered));
+            }
+        }
+
+        // a signature under one hash does not verify when streamed under another
+        let round = sign_round(&kg, 1, &[1, 2], &message);
+        let signature = aggregate(&kg, &round);
+        let hasher_id = threshold_sig::verify_hasher_new(
+            signature.clone(),
+            kg.y.clone(),
+            None,
+            Some(threshold_sig::ChallengeHash::Keccak256),
+        )
+        .unwrap();
+        threshold_sig::verify_hasher_update(hasher_id.clone(), message.clone()).unwrap();
+        assert!(!threshold_sig::verify_signature_streamed(
+            hasher_id,
+            signature.clone(),
+            kg.y.clone()
+        )
+        .unwrap());
+
+        let other_key = threshold_sig::get_public_key(kg.key_ids[0].clone()).unwrap();
+        for cofactored in [None, Some(true)] {
+            let hasher_id =
+                threshold_sig::verify_hasher_new(signature.clone(), kg.y.clone(), cofactored, None)
+                    .unwrap();
+            let err = threshold_sig::verify_signature_streamed(
+                hasher_id,
+                signature.clone(),
+                other_key.clone(),
+            )
+            .unwrap_err();
+            assert_eq!(
+                err.reason,
+                "Hasher was started for a different R or public key"
+            );
+        }
+    }
+
+    #[test]
+    fn test_handle_request_dispatches_and_wraps_results() {
+        let _guard = serial();
+        use serde_json::{json, Value};
+
+        let created =
+            crate::rpc::handle_request("phase1_create".into(), json!({ "party_index": 1 }));
+        let key_id = created["result"].as_str().unwrap();
+        assert!(key_id.starts_with("keys_"));
+        assert_eq!(created.as_object().unwrap().len(), 1);
+
+        let message = b"dispatched".to_vec();
+        let kg = keygen(1, 3);
+        let round = sign_round(&kg, 1, &[1, 3], &message);
+        let signature = aggregate(&kg, &round);
+        let by_name = json!({
+            "signature": signature,
+            "message": message,
+            "public_key": kg.y,
+        });
+        assert_eq!(
+            crate::rpc::handle_request("verify_signature".into(), by_name.clone()),
+            json!({ "result": true })
+        );
+        let by_position = json!([signature, b"tampered".to_vec(), kg.y, Value::Null, "Sha512"]);
+        assert_eq!(
+            crate::rpc::handle_request("verify_signature".into(), by_position),
+            json!({ "result": false })
+        );
+
+        let error_code = |response: Value| {
+            let error = response["error"].as_object().unwrap().clone();
+            assert!(response.get("result").is_none());
+            assert!(error["message"].as_str().is_some());
+            error["code"].as_str().unwrap().to_string()
+        };
+        assert_eq!(
+            error_code(crate::rpc::handle_request(
+                "no_such_method".into(),
+                json!({})
+            )),
+            "MethodNotFound"
+        );
+        assert_eq!(
+            error_code(crate::rpc::handle_request(
+                "phase1_create".into(),
+                json!({ "party_index": "one" })
+            )),
+            "InvalidParams"
+        );
+        assert_eq!(
+            error_code(crate::rpc::handle_request(
+                "get_shared_keys".into(),
+                json!(["keys_missing"])
+            )),
+            "InvalidArg"
+        );
+    }
+
+    #[test]
+    fn test_key_lookup_rejects_an_ephemeral_id() {
+        let _guard = serial();
+        let kg = keygen(1, 2);
+        let eph_key_id = threshold_sig::ephemeral_key_create(
+            kg.key_ids[0].clone(),
+            b"m".to_vec(),
+            1,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+
+        let err = threshold_sig::get_public_key(eph_key_id.clone()).unwrap_err();
+        assert_eq!(
+            err.reason,
+            format!(
+                "WrongIdType: {} names an ephemeral key, expected the id of a key",
+                eph_key_id
+            )
+        );
+        let err = threshold_sig::get_ephemeral_R(kg.key_ids[0].clone()).unwrap_err();
+        assert!(err.reason.starts_with("WrongIdType: "));
+
+        let err = threshold_sig::get_public_key("keys_missing".to_string()).unwrap_err();
+        assert_eq!(err.reason, "Key not found");
+    }
+
+    #[test]
+    fn test_weighted_shares_sign_once_the_threshold_weight_is_met() {
+        let _guard = serial();
+        let seed = [7u8; 32];
+        let deal = crate::dealer::deal_secret_weighted(seed.to_vec(), vec![5, 1, 1, 1], 6).unwrap();
+        assert_eq!((deal.threshold, deal.share_count), (5, 8));
+        assert_eq!(deal.parties[0].share_indices, vec![1, 2, 3, 4, 5]);
+        assert_eq!(deal.parties[3].share_indices, vec![8]);
+
+        // every share is imported as its own key, in share index order
+        let key_ids: Vec<String> = deal
+            .parties
+            .iter()
+            .flat_map(|party| party.backups.iter())
+            .map(|backup| {
+                threshold_sig::import_and_verify_shared_keys(backup.clone(), b"weighted".to_vec())
+                    .unwrap()
+                    .shared_key_id
+            })
+            .collect();
+        let kg = Keygen {
+            key_ids,
+            shared_keys: deal
+                .parties
+                .iter()
+                .flat_map(|party| party.shared_keys.clone())
+                .collect(),
+            vss_schemes: vec![deal.vss.clone()],
+            y: deal.y.clone(),
+        };
+        // the weight-5 party and the first weight-1 party
+        let signers: Vec<u16> = deal.parties[0]
+            .share_indices
+            .iter()
+            .chain(&deal.parties[1].share_indices)
+            .copied()
+            .collect();
+        let round = sign_round(&kg, deal.threshold, &signers, b"weighted");
+        let signature = aggregate(&kg, &round);
+        assert!(threshold_sig::verify_signature(
+            signature,
+            b"weighted".to_vec(),
+            deal.y.clone(),
+            None,
+            None
+        )
+        .unwrap());
+
+        let err =
+            crate::dealer::deal_secret_weighted(seed.to_vec(), vec![5, 1, 1, 1], 9).unwrap_err();
+        assert_eq!(
+            err.reason,
+            "threshold_weight 9 must be between 1 and the total weight 8"
+        );
+        let err = crate::dealer::deal_secret_weighted(seed.to_vec(), vec![5, 0], 2).unwrap_err();
+        assert_eq!(err.reason, "Weights must be at least 1");
+    }
+
+    #[test]
+    fn test_debug_lagrange_points_match_hand_computed_coefficients() {
+        use crate::arith::{debug_lagrange_points, scalar_add, scalar_from_index, scalar_mul};
+
+        // parties 1 and 3 of a 2-of-3 sharing: points 1 and 3, so
+        // lambda_1 = 3 / (3 - 1) = 3/2 and lambda_3 = 1 / (1 - 3) = -1/2
+        let points = debug_lagrange_points(vec![0, 2], 3).unwrap();
+        assert_eq!(points[0].party_index, 0);
+        assert_eq!(points[0].eval_point.bytes, scalar_from_index(1).bytes);
+        assert_eq!(points[1].eval_point.bytes, scalar_from_index(3).bytes);
+        let doubled = |coefficient: &SerializableScalar| {
+            scalar_mul(coefficient.clone(), scalar_from_index(2)).unwrap()
+        };
+        assert_eq!(
+            doubled(&points[0].coefficient).bytes,
+            scalar_from_index(3).bytes
+        );
+        let zero = scalar_add(doubled(&points[1].coefficient), scalar_from_index(1)).unwrap();
+        assert_eq!(zero.bytes, scalar_from_index(0).bytes);
+
+        let err = debug_lagrange_points(vec![0, 3], 3).unwrap_err();
+        assert_eq!(err.reason, "Party index 3 is out of range for 3 shares");
+    }
+
+    #[test]
+    fn test_serialization_failure_is_a_clean_error() {
+        use serde::ser::{Error, Serialize, Serializer};
+
+        struct Unserializable;
+        impl Serialize for Unserializable {
+            fn serialize<S: Serializer>(&self, _: S) -> Result<S::Ok, S::Error> {
+                Err(S::Error::custom("no JSON form"))
+            }
+        }
+
+        let err = crate::to_json("mock", &Unserializable).unwrap_err();
+        assert_eq!(err.status, napi::Status::GenericFailure);
+        assert_eq!(
+            err.reason,
+            "SerializationFailed: mock could not be serialized: no JSON form"
+        );
+    }
+
+    #[test]
+    fn test_batch_keygen_provisions_distinct_wallets_that_all_sign() {
+        let _guard = serial();
+        let wallets = batch_keygen(10, 1, 3, None).unwrap();
+        assert_eq!(wallets.len(), 10);
+        let distinct: std::collections::HashSet<Vec<u8>> = wallets
+            .iter()
+            .map(|wallet| wallet.y.bytes.clone())
+            .collect();
+        assert_eq!(distinct.len(), 10);
+
+        for wallet in wallets {
+            assert!(wallet.key_ids.iter().all(|id| id.starts_with("batch_")));
+            let y = wallet.y.clone();
+            let signature = simulate_sign(wallet, vec![0, 2], b"batch".to_vec(), None).unwrap();
+            assert!(
+                threshold_sig::verify_signature(signature, b"batch".to_vec(), y, None, None)
+                    .unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_reshare_equivalence_accepts_same_secret_and_rejects_a_changed_one() {
+        use crate::simulate::verify_reshare_equivalence;
+
+        let _guard = serial();
+        let kg = keygen(1, 3);
+        // an honest reshare: party 4 enrolled by parties 1 and 2, signing with party 3
+        let helpers = vec![1u16, 2];
+        let contributions: Vec<Vec<SerializableScalar>> = helpers
+            .iter()
+            .map(|&i| {
+                threshold_sig::enroll_party_contribution(
+                    kg.key_ids[usize::from(i - 1)].clone(),
+                    helpers.clone(),
+                    4,
+                )
+                .unwrap()
+            })
+            .collect();
+        let forwarded_sums = (0..helpers.len())
+            .map(|k| {
+                threshold_sig::enroll_party_forward(
+                    contributions
+                        .iter()
+                        .map(|pieces| pieces[k].clone())
+                        .collect(),
+                )
+                .unwrap()
+            })
+            .collect();
+        let enrolled =
+            threshold_sig::enroll_party(4, forwarded_sums, kg.vss_schemes.clone(), kg.y.clone())
+                .unwrap();
+        let old = kg.key_ids[..2].to_vec();
+        let reshared = vec![kg.key_ids[2].clone(), enrolled];
+        assert!(verify_reshare_equivalence(old.clone(), reshared, b"cutover".to_vec()).unwrap());
+
+        // a reshare that lost the secret: shares of a different key
+        let changed: Vec<String> = crate::dealer::deal_secret(vec![9; 32], 1, 2)
+            .unwrap()
+            .backups
+            .into_iter()
+            .map(|backup| {
+                threshold_sig::import_and_verify_shared_keys(backup, vec![])
+                    .unwrap()
+                    .shared_key_id
+            })
+            .collect();
+        assert!(!verify_reshare_equivalence(old.clone(), changed, b"cutover".to_vec()).unwrap());
+        // below the threshold the shares cannot sign
+        assert!(
+            !verify_reshare_equivalence(old, kg.key_ids[2..].to_vec(), b"cutover".to_vec())
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_keygen_frames_round_trip_and_reject_damage() {
+        use crate::frame::*;
+
+        let _guard = serial();
+        let key_id = threshold_sig::phase1_create(1).unwrap();
+        let message1 = threshold_sig::phase1_broadcast_message(key_id, None).unwrap();
+        let frame1 = encode_keygen_round1(message1.clone()).unwrap();
+        assert_eq!(&frame1[..4], b"TSKG");
+        let decoded1 = decode_keygen_round1(frame1.clone()).unwrap();
+        assert_eq!(decoded1.party_index, message1.party_index);
+        assert_eq!(decoded1.public_key.bytes, message1.public_key.bytes);
+        assert_eq!(decoded1.commitment.bytes, message1.commitment.bytes);
+        assert_eq!(decoded1.blind_factor.bytes, message1.blind_factor.bytes);
+
+        let kg = keygen(1, 3);
+        let message2 = SerializableKeygenMessage2 {
+            party_index: 1,
+            recipient_index: 2,
+            vss: kg.vss_schemes[0].clone(),
+            share: kg.shared_keys[1].x_i.clone(),
+        };
+        let frame2 = encode_keygen_round2(message2.clone()).unwrap();
+        let decoded2 = decode_keygen_round2(frame2.clone()).unwrap();
+        assert_eq!((decoded2.party_index, decoded2.recipient_index), (1, 2));
+        assert_eq!(decoded2.vss.threshold, message2.vss.threshold);
+        assert_eq!(decoded2.vss.share_count, message2.vss.share_count);
+        let commitments = |vss: &SerializableVerifiableSS| -> Vec<Vec<u8>> {
+            vss.commitments.iter().map(|c| c.bytes.clone()).collect()
+        };
+        assert_eq!(commitments(&decoded2.vss), commitments(&message2.vss));
+        assert_eq!(decoded2.share.bytes, message2.share.bytes);
+
+        let err = decode_keygen_round2(frame2[..frame2.len() - 1].to_vec()).unwrap_err();
+        assert_eq!(
+            err.reason,
+            format!(
+                "Frame body is {} bytes, its header says {}",
+                frame2.len() - 11,
+                frame2.len() - 10
+            )
+        );
+        let err = decode_keygen_round1(frame1[..6].to_vec()).unwrap_err();
+        assert_eq!(err.reason, "Frame is truncated");
+        let mut newer = frame1.clone();
+        newer[4] = 2;
+        assert!(decode_keygen_round1(newer)
+            .unwrap_err()
+            .reason
+            .starts_with("UnsupportedEncodingVersion: "));
+        let err = decode_keygen_round1(frame2).unwrap_err();
+        assert_eq!(err.reason, "Expected a round 1 frame, got round 2");
+    }
+
+    #[test]
+    fn test_sig_collector_rejects_a_bad_local_sig_and_finalizes_from_good_ones() {
+        use crate::collector::*;
+
+        let _guard = serial();
+        let kg = keygen(1, 3);
+        let message = b"collected".to_vec();
+        let round = sign_round(&kg, 1, &[1, 2, 3], &message);
+        sig_collector_new(
+            "collect".into(),
+            round.R.clone(),
+            message.clone(),
+            kg.vss_schemes.clone(),
+            round.vss_ephemeral.clone(),
+            None,
+        )
+        .unwrap();
+
+        let add = |i: usize, local_sig: SerializableLocalSig| {
+            sig_collector_add("collect".into(), round.parties_index[i], local_sig)
+        };
+        // party 2 signs another message under the same R; arriving first, it must not
+        // decide the challenge the honest signatures are held to
+        let other = threshold_sig::compute_local_sig(
+            b"other".to_vec(),
+            round.eph_shared_keys[1].clone(),
+            kg.shared_keys[1].clone(),
+            None,
+            None,
+        )
+        .unwrap();
+        assert!(!add(1, other).unwrap());
+        assert!(add(0, round.local_sigs[0].clone()).unwrap());
+        assert!(sig_collector_finalize("collect".into()).is_err());
+        // party 1's signature attributed to party 2
+        assert!(!add(1, round.local_sigs[0].clone()).unwrap());
+        assert!(add(2, round.local_sigs[2].clone()).unwrap());
+        assert!(add(0, round.local_sigs[0].clone())
+            .unwrap_err()
+            .reason
+            .starts_with("DuplicatePartyIndex: "));
+
+        let signature = sig_collector_finalize("collect".into()).unwrap();
+        assert!(
+            threshold_sig::verify_signature(signature, message, kg.y.clone(), None, None).unwrap()
+        );
+        assert_eq!(
+            sig_collector_finalize("collect".into()).unwrap_err().reason,
+            "Signature collector not found"
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn test_debug_expanded_secret_matches_rfc8032_expansion() {
+        use curv::elliptic::curves::{Ed25519, Scalar};
+        use sha2::{Digest, Sha512};
+
+        let _guard = serial();
+        let seed = [0x9du8; 32];
+        let key_id = threshold_sig::phase1_create_from_private_key(1, seed.to_vec()).unwrap();
+        let expanded = crate::testing::debug_expanded_secret(key_id).unwrap();
+
+        let h = Sha512::digest(&seed);
+        let mut a = [0u8; 32];
+        a.copy_from_slice(&h[..32]);
+        a[0] &= 248;
+        a[31] &= 63;
+        a[31] |= 64;
+        let reduced = |bytes: &[u8]| {
+            Scalar::<Ed25519>::from_bytes(bytes)
+                .unwrap()
+                .to_bytes()
+                .to_vec()
+        };
+        assert_eq!(expanded.prefix.bytes, reduced(&h[32..]));
+        assert_eq!(expanded.scalar.bytes, reduced(&a));
+    }
+
+    #[test]
+    fn test_unknown_phase1_session_is_not_found() {
+        let _guard = serial();
+        let key_id = threshold_sig::phase1_create(1).unwrap();
+        let err =
+            threshold_sig::phase1_verify_collected("sess1".into(), key_id, 1, 2, None).unwrap_err();
+        assert_eq!(err.reason, "Session not found");
+        let err = threshold_sig::export_keygen_transcript("sess1".into()).unwrap_err();
+        assert_eq!(err.reason, "Session not found");
+        let err = threshold_sig::phase2_construct("sess1".into()).unwrap_err();
+        assert!(err.reason.starts_with("WrongIdType: "));
+    }
+}
@@ -0,0 +1,323 @@
+use multi_party_eddsa::protocols::thresholdsig::{
+    self, EphemeralKey, KeyGenBroadcastMessage1, Keys, LocalSig, Parameters,
+};
+use napi::Status;
+use napi_derive::napi;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::rng::RngSource;
+use crate::{
+    bigint_to_serializable, check_sharing_params, point_to_serializable, scalar_to_serializable,
+    serializable_to_point, serializable_to_scalar, to_json, vss_to_serializable,
+    SerializableBigInt, SerializableEphemeralSharedKeys, SerializableLocalSig, SerializablePoint,
+    SerializableScalar, SerializableSharedKeys, SerializableSignature, SerializableVerifiableSS,
+};
+
+// Known-answer vectors for other implementations of the protocol. Every value is
+// drawn from a ChaCha20 stream keyed with the first 32 bytes of SHA-512(seed), in
+// this order: the 32-byte secret of each party 1..=n, each party's keygen blind
+// factor, each party's sharing polynomial, then for signers 1..=t+1 the 32 bytes
+// mixed into each nonce, each blind factor and each polynomial. The JSON layout is the serde form of
+// `SerializableTestVectors`: snake_case field names, points as 32-byte compressed
+// encodings, scalars as 32 little-endian bytes, commitments and blind factors as
+// big-endian magnitudes, and every per-party list in party order.
+
+/// The message every vector set signs
+pub const TEST_VECTOR_MESSAGE: &[u8] = b"multi-party-eddsa test vector";
+
+/// What one party publishes and deals in a keygen or nonce round
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableDealerVector {
+    /// The party's public key in keygen, its R_i in the nonce round
+    pub point: SerializablePoint,
+    pub commitment: SerializableBigInt,
+    #[serde(rename = "blind_factor")]
+    #[napi(js_name = "blindFactor")]
+    pub blind_factor: SerializableBigInt,
+    pub vss: SerializableVerifiableSS,
+    /// The share dealt to each party of the round, in party order
+    #[serde(rename = "secret_shares")]
+    #[napi(js_name = "secretShares")]
+    pub secret_shares: Vec<SerializableScalar>,
+}
+
+/// Output of `export_test_vectors`: every intermediate value of one t-of-n keygen and
+/// one signature of `TEST_VECTOR_MESSAGE` by parties 1..=t+1
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableTestVectors {
+    pub seed: Vec<u8>,
+    pub threshold: u16,
+    #[serde(rename = "share_count")]
+    #[napi(js_name = "shareCount")]
+    pub share_count: u16,
+    pub message: Vec<u8>,
+    /// 0-based indices of the signers
+    #[serde(rename = "parties_index")]
+    #[napi(js_name = "partiesIndex")]
+    pub parties_index: Vec<u16>,
+    pub keygen: Vec<SerializableDealerVector>,
+    #[serde(rename = "shared_keys")]
+    #[napi(js_name = "sharedKeys")]
+    pub shared_keys: Vec<SerializableSharedKeys>,
+    #[serde(rename = "nonce_round")]
+    #[napi(js_name = "nonceRound")]
+    pub nonce_round: Vec<SerializableDealerVector>,
+    #[serde(rename = "eph_shared_keys")]
+    #[napi(js_name = "ephSharedKeys")]
+    pub eph_shared_keys: Vec<SerializableEphemeralSharedKeys>,
+    #[serde(rename = "local_sigs")]
+    #[napi(js_name = "localSigs")]
+    pub local_sigs: Vec<SerializableLocalSig>,
+    pub signature: SerializableSignature,
+}
+
+fn protocol_error<E: std::fmt::Debug>(e: E) -> napi::Error {
+    napi::Error::new(Status::GenericFailure, format!("{:?}", e))
+}
+
+fn dealer_vector(
+    point: &curv::elliptic::curves::Point<curv::elliptic::curves::Ed25519>,
+    bc1: &KeyGenBroadcastMessage1,
+    blind_factor: &curv::BigInt,
+    vss: &curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS<
+        curv::elliptic::curves::Ed25519,
+    >,
+    secret_shares: &[curv::elliptic::curves::Scalar<curv::elliptic::curves::Ed25519>],
+) -> SerializableDealerVector {
+    SerializableDealerVector {
+        point: point_to_serializable(point),
+        commitment: bigint_to_serializable(&bc1.com),
+        blind_factor: bigint_to_serializable(blind_factor),
+        vss: vss_to_serializable(vss),
+        secret_shares: secret_shares.iter().map(scalar_to_serializable).collect(),
+    }
+}
+
+// Shares sent to party i, taken from every party's distribution in order
+fn received_by<T: Clone>(secret_shares: &[Vec<T>], i: usize) -> Vec<T> {
+    secret_shares
+        .iter()
+        .map(|shares| shares[i].clone())
+        .collect()
+}
+
+fn derive_test_vectors(
+    seed: Vec<u8>,
+    threshold: u16,
+    share_count: u16,
+) -> napi::Result<SerializableTestVectors> {
+    let parties: Vec<u16> = (1..=share_count).collect();
+    check_sharing_params(threshold, share_count, &parties)?;
+    let mut rng = RngSource::seeded(&seed);
+    let params = Parameters {
+        threshold,
+        share_count,
+    };
+
+    let keys: Vec<Keys> = parties
+        .iter()
+        .map(|&i| {
+            let mut secret = [0u8; 32];
+            rng.fill_bytes(&mut secret);
+            Keys::phase1_create_from_private_key(i, secret)
+        })
+        .collect();
+    let (bc1_vec, blind_vec): (Vec<_>, Vec<_>) = keys
+        .iter()
+        .map(|key| key.phase1_broadcast_rng(&mut rng))
+        .unzip();
+    let y_vec: Vec<_> = keys
+        .iter()
+        .map(|key| key.keypair.public_key.clone())
+        .collect();
+    let (vss_vec, shares_vec): (Vec<_>, Vec<_>) = keys
+        .iter()
+        .map(|key| {
+            key.phase1_verify_com_phase2_distribute_rng(
+                &params, &blind_vec, &y_vec, &bc1_vec, &parties, &mut rng,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(protocol_error)?
+        .into_iter()
+        .unzip();
+    let shared_keys = keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| {
+            key.phase2_verify_vss_construct_keypair(
+                &params,
+                &y_vec,
+                &received_by(&shares_vec, i),
+                &vss_vec,
+                parties[i],
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(protocol_error)?;
+
+    let parties_index: Vec<u16> = (0..=threshold).collect();
+    let signers: Vec<u16> = parties_index.iter().map(|i| i + 1).collect();
+    let eph_params = Parameters {
+        threshold,
+        share_count: signers.len() as u16,
+    };
+    let eph_keys: Vec<EphemeralKey> = signers
+        .iter()
+        .map(|&p| {
+            EphemeralKey::ephermeral_key_create_from_deterministic_secret_rng(
+                &keys[usize::from(p - 1)],
+                TEST_VECTOR_MESSAGE,
+                p,
+                &mut rng,
+            )
+        })
+        .collect();
+    let (eph_bc1_vec, eph_blind_vec): (Vec<_>, Vec<_>) = eph_keys
+        .iter()
+        .map(|eph_key| eph_key.phase1_broadcast_rng(&mut rng))
+        .unzip();
+    let R_vec: Vec<_> = eph_keys.iter().map(|eph_key| eph_key.R_i.clone()).collect();
+    let (eph_vss_vec, eph_shares_vec): (Vec<_>, Vec<_>) = eph_keys
+        .iter()
+        .map(|eph_key| {
+            eph_key.phase1_verify_com_phase2_distribute_rng(
+                &eph_params,
+                &eph_blind_vec,
+                &R_vec,
+                &eph_bc1_vec,
+                &signers,
+                &mut rng,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(protocol_error)?
+        .into_iter()
+        .unzip();
+    let eph_shared_keys = eph_keys
+        .iter()
+        .enumerate()
+        .map(|(i, eph_key)| {
+            eph_key.phase2_verify_vss_construct_keypair(
+                &eph_params,
+                &R_vec,
+                &received_by(&eph_shares_vec, i),
+                &eph_vss_vec,
+                signers[i],
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(protocol_error)?;
+
+    let local_sigs: Vec<LocalSig> = eph_shared_keys
+        .iter()
+        .zip(&parties_index)
+        .map(|(eph_shared_key, &i)| {
+            LocalSig::compute(
+                TEST_VECTOR_MESSAGE,
+                eph_shared_key,
+                &shared_keys[usize::from(i)],
+            )
+        })
+        .collect();
+    let vss_sum = LocalSig::verify_local_sigs(&local_sigs, &parties_index, &vss_vec, &eph_vss_vec)
+        .map_err(protocol_error)?;
+    let signature = thresholdsig::generate(
+        &vss_sum,
+        &local_sigs,
+        &parties_index,
+        eph_shared_keys[0].R.clone(),
+    );
+
+    Ok(SerializableTestVectors {
+        seed,
+        threshold,
+        share_count,
+        message: TEST_VECTOR_MESSAGE.to_vec(),
+        parties_index,
+        keygen: (0..keys.len())
+            .map(|i| {
+                dealer_vector(
+                    &y_vec[i],
+                    &bc1_vec[i],
+                    &blind_vec[i],
+                    &vss_vec[i],
+                    &shares_vec[i],
+                )
+            })
+            .collect(),
+        shared_keys: shared_keys
+            .iter()
+            .map(|shared_key| SerializableSharedKeys {
+                y: point_to_serializable(&shared_key.y),
+                x_i: scalar_to_serializable(&shared_key.x_i),
+                prefix: scalar_to_serializable(&shared_key.prefix),
+            })
+            .collect(),
+        nonce_round: (0..eph_keys.len())
+            .map(|i| {
+                dealer_vector(
+                    &R_vec[i],
+                    &eph_bc1_vec[i],
+                    &eph_blind_vec[i],
+                    &eph_vss_vec[i],
+                    &eph_shares_vec[i],
+                )
+            })
+            .collect(),
+        eph_shared_keys: eph_shared_keys
+            .iter()
+            .map(|eph_shared_key| SerializableEphemeralSharedKeys {
+                R: point_to_serializable(&eph_shared_key.R),
+                r_i: scalar_to_serializable(&eph_shared_key.r_i),
+            })
+            .collect(),
+        local_sigs: local_sigs
+            .iter()
+            .map(|local_sig| SerializableLocalSig {
+                gamma_i: scalar_to_serializable(&local_sig.gamma_i),
+                k: scalar_to_serializable(&local_sig.k),
+            })
+            .collect(),
+        signature: SerializableSignature {
+            R: point_to_serializable(&signature.R),
+            s: scalar_to_serializable(&signature.s),
+        },
+    })
+}
+
+/// Derive a reproducible t-of-n keygen and one signature of `TEST_VECTOR_MESSAGE` by
+/// parties 1..=t+1 from `seed`, returning every intermediate value. Seeds are public
+/// here, so the keys in a vector set must never protect anything.
+#[napi]
+pub fn export_test_vectors(
+    seed: Vec<u8>,
+    threshold: u16,
+    share_count: u16,
+) -> napi::Result<SerializableTestVectors> {
+    crate::guard::guard("export_test_vectors", || {
+        derive_test_vectors(seed, threshold, share_count)
+    })
+}
+
+/// Replay `vectors` from their seed and check every value matches and the signature verifies
+/// under the group key; false on any difference
+#[napi]
+pub fn check_test_vectors(vectors: SerializableTestVectors) -> napi::Result<bool> {
+    crate::guard::guard("check_test_vectors", || {
+        let expected =
+            derive_test_vectors(vectors.seed.clone(), vectors.threshold, vectors.share_count)?;
+        if to_json("test_vectors", &expected)? != to_json("test_vectors", &vectors)? {
+            return Ok(false);
+        }
+        let signature = multi_party_eddsa::protocols::Signature {
+            R: serializable_to_point(&vectors.signature.R)?,
+            s: serializable_to_scalar(&vectors.signature.s)?,
+        };
+        let y = serializable_to_point(&vectors.shared_keys[0].y)?;
+        Ok(signature.verify(&vectors.message, &y).is_ok())
+    })
+}
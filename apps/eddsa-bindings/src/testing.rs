@@ -0,0 +1,37 @@
+use napi_derive::napi;
+use serde::{Deserialize, Serialize};
+
+use crate::store::lock;
+use crate::threshold_sig::{keys_store, IdKind};
+use crate::{scalar_to_serializable, SerializableScalar};
+
+// Debugging exports that read secret key material. This module only exists in
+// builds with the `testing` feature, which is off by default.
+
+/// The expanded secret of a key, as RFC 8032 derives it from the seed
+#[napi(object)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializableExpandedSecret {
+    /// The clamped secret scalar a, reduced mod l
+    pub scalar: SerializableScalar,
+    /// The nonce prefix, the upper half of SHA-512(seed)
+    pub prefix: SerializableScalar,
+}
+
+/// TESTING ONLY: the secret scalar and nonce prefix `Keys` holds for `key_id`, to reproduce
+/// the RFC 8032 expansion when chasing a signature mismatch. A key restored from a backup
+/// holds its share and stored prefix instead. Only compiled with the `testing` feature.
+#[napi]
+pub fn debug_expanded_secret(key_id: String) -> napi::Result<SerializableExpandedSecret> {
+    crate::guard::guard("debug_expanded_secret", || {
+        let mut keys = lock(keys_store());
+        let key = keys
+            .get(&key_id)
+            .ok_or_else(|| IdKind::Key.not_found(&key_id))?;
+        let expanded = key.keys.keypair.expanded_private_key();
+        Ok(SerializableExpandedSecret {
+            scalar: scalar_to_serializable(expanded.private_key()),
+            prefix: scalar_to_serializable(&expanded.prefix),
+        })
+    })
+}
@@ -0,0 +1,85 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use curve25519_dalek::constants::ED25519_BASEPOINT_TABLE;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsBasepointTable};
+use curve25519_dalek::scalar::Scalar as DalekScalar;
+use napi::Status;
+use sha2::{Digest, Sha512};
+
+use crate::error::TssError;
+use crate::store::{lock, Store};
+use crate::{metrics, serializable_to_point, SerializablePoint, SerializableSignature};
+
+// A public key registered for repeated verification: its canonical encoding,
+// hashed into every challenge, and a fixed-base table for multiples of it, so
+// [k]A costs table lookups instead of a variable-base scalar multiplication.
+pub(crate) struct VerifyKey {
+    encoding: [u8; 32],
+    table: EdwardsBasepointTable,
+}
+
+pub(crate) fn verify_keys_store() -> &'static Mutex<Store<Arc<VerifyKey>>> {
+    static STORE: OnceLock<Mutex<Store<Arc<VerifyKey>>>> = OnceLock::new();
+    STORE.get_or_init(|| Mutex::new(Store::new()))
+}
+
+/// Precompute the verification tables of a public key and return a handle for
+/// `verify_with_registered_key`. Worth it for keys that verify many signatures.
+#[napi_derive::napi]
+pub fn register_verify_key(public_key: SerializablePoint) -> napi::Result<String> {
+    crate::guard::guard("register_verify_key", || {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+        // the same decoding and subgroup checks as verify_signature
+        let encoding: [u8; 32] = serializable_to_point(&public_key)?
+            .to_bytes(true)
+            .as_ref()
+            .try_into()
+            .map_err(|_| napi::Error::from(TssError::InvalidPoint { name: "public key" }))?;
+        let A = CompressedEdwardsY(encoding)
+            .decompress()
+            .ok_or_else(|| napi::Error::from(TssError::InvalidPoint { name: "public key" }))?;
+        let key = VerifyKey {
+            encoding,
+            table: EdwardsBasepointTable::create(&A),
+        };
+
+        let handle = format!("vk_{}", COUNTER.fetch_add(1, Ordering::Relaxed));
+        lock(verify_keys_store()).insert(handle.clone(), Arc::new(key), |_| false);
+        Ok(handle)
+    })
+}
+
+/// `verify_signature` under a key registered with `register_verify_key`
+#[napi_derive::napi]
+pub fn verify_with_registered_key(
+    handle: String,
+    signature: SerializableSignature,
+    message: Vec<u8>,
+) -> napi::Result<bool> {
+    crate::guard::guard("verify_with_registered_key", || {
+        crate::threshold_sig::check_message_size(&message)?;
+        let key = lock(verify_keys_store())
+            .get(&handle)
+            .cloned()
+            .ok_or_else(|| napi::Error::new(Status::InvalidArg, "Verify key not found"))?;
+        // R with or without the point prefix, compared as its 32-byte encoding
+        let R = crate::point_encoding(&signature.R)
+            .map_err(|_| napi::Error::from(TssError::InvalidPoint { name: "R" }))?;
+        let s = signature
+            .s
+            .bytes
+            .as_slice()
+            .try_into()
+            .ok()
+            .and_then(DalekScalar::from_canonical_bytes)
+            .ok_or_else(|| napi::Error::from(TssError::NonCanonicalScalar { name: "s" }))?;
+        let k = DalekScalar::from_hash(Sha512::new().chain(R).chain(key.encoding).chain(&message));
+
+        // [s]B - [k]A is a canonical encoding of R exactly when s*G = R + k*A
+        let R_expected = &s * &ED25519_BASEPOINT_TABLE - &k * &key.table;
+        metrics::record_verification();
+        Ok(R_expected.compress().to_bytes() == R)
+    })
+}
@@ -23,6 +23,9 @@ extern crate rand;
 extern crate serde_json;
 extern crate sha2;
 
+#[cfg(feature = "rayon")]
+extern crate rayon;
+
 #[cfg(test)]
 extern crate ed25519_dalek;
 #[cfg(test)]
@@ -44,8 +47,21 @@ use std::fmt;
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", &self)
+        write!(f, "{:?}", self)
     }
 }
 
 impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+
+    #[test]
+    fn test_error_display_names_the_variant() {
+        assert_eq!(Error::InvalidKey.to_string(), "InvalidKey");
+        assert_eq!(Error::InvalidSS.to_string(), "InvalidSS");
+        assert_eq!(Error::InvalidCom.to_string(), "InvalidCom");
+        assert_eq!(Error::InvalidSig.to_string(), "InvalidSig");
+    }
+}
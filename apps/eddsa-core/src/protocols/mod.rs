@@ -67,6 +67,23 @@ impl ExpandedKeyPair {
     }
 }
 
+// Read access to the expanded secret, for tests that check it against the
+// RFC 8032 expansion. Never compiled into builds without the `testing` feature.
+#[cfg(feature = "testing")]
+impl ExpandedKeyPair {
+    pub fn expanded_private_key(&self) -> &ExpandedPrivateKey {
+        &self.expanded_private_key
+    }
+}
+
+#[cfg(feature = "testing")]
+impl ExpandedPrivateKey {
+    /// The clamped secret scalar a, reduced mod the group order
+    pub fn private_key(&self) -> &Scalar<Ed25519> {
+        &self.private_key
+    }
+}
+
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct Signature {
     pub R: Point<Ed25519>,
@@ -75,7 +92,17 @@ pub struct Signature {
 
 impl Signature {
     pub fn verify(&self, message: &[u8], public_key: &Point<Ed25519>) -> Result<(), ProofError> {
-        let k = Self::k(&self.R, public_key, message);
+        self.verify_with_hash::<Sha512>(message, public_key)
+    }
+
+    /// `verify` for Ed25519 variants whose challenge is H(R||A||M) under a hash
+    /// other than SHA-512. Only `Sha512` gives RFC 8032 signatures.
+    pub fn verify_with_hash<H: Digest>(
+        &self,
+        message: &[u8],
+        public_key: &Point<Ed25519>,
+    ) -> Result<(), ProofError> {
+        let k = Self::k_with_hash::<H>(&self.R, public_key, message);
         let A = public_key;
 
         let kA = A * k;
@@ -90,11 +117,45 @@ impl Signature {
     }
 
     pub(crate) fn k(R: &Point<Ed25519>, PK: &Point<Ed25519>, message: &[u8]) -> Scalar<Ed25519> {
-        let mut k = Sha512::new()
+        Self::k_from_hasher(Self::k_hasher(R, PK).chain(message))
+    }
+
+    /// The challenge k = H(R||A||M) under the hash `H`, read little-endian and
+    /// reduced mod the group order like the SHA-512 one.
+    pub fn k_with_hash<H: Digest>(
+        R: &Point<Ed25519>,
+        PK: &Point<Ed25519>,
+        message: &[u8],
+    ) -> Scalar<Ed25519> {
+        let hash = H::new()
             .chain(&*R.to_bytes(true))
             .chain(&*PK.to_bytes(true))
             .chain(message)
             .finalize();
+        Self::k_from_bytes(&hash)
+    }
+
+    /// Hasher for the challenge that has absorbed R and the public key, ready
+    /// to be fed the message (possibly in chunks).
+    pub fn k_hasher(R: &Point<Ed25519>, PK: &Point<Ed25519>) -> Sha512 {
+        Sha512::new()
+            .chain(&*R.to_bytes(true))
+            .chain(&*PK.to_bytes(true))
+    }
+
+    /// Finalize a hasher obtained from `k_hasher` into the challenge scalar.
+    pub fn k_from_hasher(hasher: Sha512) -> Scalar<Ed25519> {
+        Self::k_from_hasher_with_hash(hasher)
+    }
+
+    /// Finalize a hasher of any hash `H` that has absorbed R, the public key and
+    /// the message into the challenge scalar, as `k_with_hash` does.
+    pub fn k_from_hasher_with_hash<H: Digest>(hasher: H) -> Scalar<Ed25519> {
+        Self::k_from_bytes(&hasher.finalize())
+    }
+
+    fn k_from_bytes(hash: &[u8]) -> Scalar<Ed25519> {
+        let mut k = hash.to_vec();
         // reverse because BigInt uses BigEndian.
         k.reverse();
         // This will reduce it mod the group order.
@@ -115,10 +176,10 @@ pub(crate) mod tests {
 
     pub fn verify_dalek(pk: &Point<Ed25519>, sig: &Signature, msg: &[u8]) -> bool {
         let mut sig_bytes = [0u8; 64];
-        sig_bytes[..32].copy_from_slice(&*sig.R.to_bytes(true));
+        sig_bytes[..32].copy_from_slice(&sig.R.to_bytes(true));
         sig_bytes[32..].copy_from_slice(&sig.s.to_bytes());
 
-        let dalek_pub = ed25519_dalek::PublicKey::from_bytes(&*pk.to_bytes(true)).unwrap();
+        let dalek_pub = ed25519_dalek::PublicKey::from_bytes(&pk.to_bytes(true)).unwrap();
         let dalek_sig = ed25519_dalek::Signature::from_bytes(&sig_bytes).unwrap();
 
         dalek_pub.verify(msg, &dalek_sig).is_ok()
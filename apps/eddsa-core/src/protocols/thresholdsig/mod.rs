@@ -16,16 +16,20 @@ use curv::arithmetic::traits::*;
 use curv::cryptographic_primitives::commitments::hash_commitment::HashCommitment;
 use curv::cryptographic_primitives::commitments::traits::Commitment;
 use curv::cryptographic_primitives::hashing::DigestExt;
-use curv::cryptographic_primitives::secret_sharing::feldman_vss::{SecretShares, VerifiableSS};
+use curv::cryptographic_primitives::secret_sharing::feldman_vss::{
+    SecretShares, ShamirSecretSharing, VerifiableSS,
+};
+use curv::cryptographic_primitives::secret_sharing::Polynomial;
 use curv::elliptic::curves::{Ed25519, Point, Scalar};
 use curv::BigInt;
-use protocols::{ExpandedKeyPair, Signature};
+use protocols::{ExpandedKeyPair, ExpandedPrivateKey, Signature};
 use rand::{thread_rng, Rng};
 use sha2::{digest::Digest, Sha512};
 
 const SECURITY: usize = 256;
 
 // u_i is private key and {u__i, prefix} are extended private key.
+#[derive(Serialize, Deserialize)]
 pub struct Keys {
     pub keypair: ExpandedKeyPair,
     pub party_index: u16,
@@ -47,6 +51,7 @@ pub struct SharedKeys {
     pub prefix: Scalar<Ed25519>,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct EphemeralKey {
     pub r_i: Scalar<Ed25519>,
     pub R_i: Point<Ed25519>,
@@ -79,11 +84,27 @@ impl Keys {
         }
     }
 
+    // Rebuild a party's keys from its keygen output alone, e.g. when restoring a backed-up
+    // share. The share x_i stands in for the lost u_i, so the keys can no longer take part
+    // in a keygen, but the nonce prefix is kept and signing is unaffected.
+    pub fn phase1_create_from_shared_keys(party_index: u16, shared_keys: &SharedKeys) -> Keys {
+        Keys {
+            keypair: ExpandedKeyPair {
+                public_key: Point::generator() * &shared_keys.x_i,
+                expanded_private_key: ExpandedPrivateKey {
+                    prefix: shared_keys.prefix.clone(),
+                    private_key: shared_keys.x_i.clone(),
+                },
+            },
+            party_index,
+        }
+    }
+
     pub fn phase1_broadcast(&self) -> (KeyGenBroadcastMessage1, BigInt) {
         self.phase1_broadcast_rng(&mut thread_rng())
     }
 
-    fn phase1_broadcast_rng(&self, rng: &mut impl Rng) -> (KeyGenBroadcastMessage1, BigInt) {
+    pub fn phase1_broadcast_rng(&self, rng: &mut impl Rng) -> (KeyGenBroadcastMessage1, BigInt) {
         let blind_factor: [u8; SECURITY / 8] = rng.gen();
         let blind_factor = BigInt::from_bytes(&blind_factor);
         let com = HashCommitment::<Sha512>::create_commitment_with_user_defined_randomness(
@@ -102,24 +123,7 @@ impl Keys {
         bc1_vec: &[KeyGenBroadcastMessage1],
         parties: &[u16],
     ) -> Result<(VerifiableSS<Ed25519>, SecretShares<Ed25519>), Error> {
-        // test length:
-        assert_eq!(blind_vec.len(), usize::from(params.share_count));
-        assert_eq!(bc1_vec.len(), usize::from(params.share_count));
-        assert_eq!(y_vec.len(), usize::from(params.share_count));
-        // test decommitments
-        let correct_key_correct_decom_all = y_vec
-            .iter()
-            .zip(blind_vec.iter())
-            .zip(bc1_vec.iter())
-            .all(|((y, blind), comm)| {
-                HashCommitment::<Sha512>::create_commitment_with_user_defined_randomness(
-                    &y.y_coord().unwrap(),
-                    blind,
-                ) == comm.com
-            });
-        if !correct_key_correct_decom_all {
-            return Err(InvalidKey);
-        }
+        Self::verify_phase1_decommitments(params, blind_vec, y_vec, bc1_vec)?;
         Ok(VerifiableSS::share_at_indices(
             params.threshold,
             params.share_count,
@@ -128,6 +132,54 @@ impl Keys {
         ))
     }
 
+    // same as phase1_verify_com_phase2_distribute, but the sharing polynomial is sampled from `rng`
+    pub fn phase1_verify_com_phase2_distribute_rng(
+        &self,
+        params: &Parameters,
+        blind_vec: &[BigInt],
+        y_vec: &[Point<Ed25519>],
+        bc1_vec: &[KeyGenBroadcastMessage1],
+        parties: &[u16],
+        rng: &mut impl Rng,
+    ) -> Result<(VerifiableSS<Ed25519>, Vec<Scalar<Ed25519>>), Error> {
+        Self::verify_phase1_decommitments(params, blind_vec, y_vec, bc1_vec)?;
+        Ok(self.phase2_distribute_rng(params, parties, rng))
+    }
+
+    // phase 2 share distribution alone, for callers that verified the phase 1
+    // commitments under their own commitment scheme
+    pub fn phase2_distribute_rng(
+        &self,
+        params: &Parameters,
+        parties: &[u16],
+        rng: &mut impl Rng,
+    ) -> (VerifiableSS<Ed25519>, Vec<Scalar<Ed25519>>) {
+        share_at_indices_rng(
+            params.threshold,
+            params.share_count,
+            &self.keypair.expanded_private_key.private_key,
+            parties,
+            rng,
+        )
+    }
+
+    fn verify_phase1_decommitments(
+        params: &Parameters,
+        blind_vec: &[BigInt],
+        y_vec: &[Point<Ed25519>],
+        bc1_vec: &[KeyGenBroadcastMessage1],
+    ) -> Result<(), Error> {
+        // test length:
+        assert_eq!(blind_vec.len(), usize::from(params.share_count));
+        assert_eq!(bc1_vec.len(), usize::from(params.share_count));
+        assert_eq!(y_vec.len(), usize::from(params.share_count));
+        // test decommitments
+        match find_invalid_decommitment(blind_vec, y_vec, bc1_vec) {
+            Some(_) => Err(InvalidKey),
+            None => Ok(()),
+        }
+    }
+
     pub fn phase2_verify_vss_construct_keypair(
         &self,
         params: &Parameters,
@@ -140,15 +192,7 @@ impl Keys {
         assert_eq!(secret_shares_vec.len(), usize::from(params.share_count));
         assert_eq!(vss_scheme_vec.len(), usize::from(params.share_count));
 
-        let correct_ss_verify = vss_scheme_vec
-            .iter()
-            .zip(secret_shares_vec.iter())
-            .zip(y_vec.iter())
-            .all(|((vss_scheme, secret_share), y)| {
-                vss_scheme.validate_share(secret_share, index).is_ok()
-                    && &vss_scheme.commitments[0] == y
-            });
-        if !correct_ss_verify {
+        if find_invalid_share(vss_scheme_vec, secret_shares_vec, y_vec, index).is_some() {
             return Err(InvalidSS);
         }
         let first_y = y_vec[0].clone();
@@ -181,16 +225,70 @@ impl EphemeralKey {
         )
     }
 
-    fn ephermeral_key_create_from_deterministic_secret_rng(
+    pub fn ephermeral_key_create_from_deterministic_secret_rng(
+        keys: &Keys,
+        message: &[u8],
+        index: u16,
+        rng: &mut impl Rng,
+    ) -> EphemeralKey {
+        Self::ephermeral_key_create_from_deterministic_secret_with_context_rng(
+            keys,
+            message,
+            &[],
+            &[],
+            index,
+            rng,
+        )
+    }
+
+    // `domain` separates the nonces of different applications sharing a key, and `signers`
+    // binds the nonce to the signer set so a different subset signing the same message
+    // never reuses it. All signers of a session must pass the same domain and set; the
+    // order of `signers` does not matter. Empty values give the derivation above.
+    pub fn ephermeral_key_create_from_deterministic_secret_with_context_rng(
+        keys: &Keys,
+        message: &[u8],
+        domain: &[u8],
+        signers: &[u16],
+        index: u16,
+        rng: &mut impl Rng,
+    ) -> EphemeralKey {
+        Self::ephermeral_key_create_from_deterministic_secret_with_hash_rng::<Sha512>(
+            keys, message, domain, signers, index, rng,
+        )
+    }
+
+    // the derivation above with the nonce hashed by `H`, so a variant with a
+    // non-SHA-512 challenge uses the same hash throughout a signature
+    pub fn ephermeral_key_create_from_deterministic_secret_with_hash_rng<H: Digest + Clone>(
         keys: &Keys,
         message: &[u8],
+        domain: &[u8],
+        signers: &[u16],
         index: u16,
         rng: &mut impl Rng,
     ) -> EphemeralKey {
+        let mut hasher = H::new().chain_scalar(&keys.keypair.expanded_private_key.prefix);
+        // each block is tagged and length-prefixed so block and message boundaries are unambiguous
+        if !domain.is_empty() {
+            hasher = hasher
+                .chain(b"domain")
+                .chain((domain.len() as u64).to_be_bytes())
+                .chain(domain);
+        }
+        if !signers.is_empty() {
+            let mut signers = signers.to_vec();
+            signers.sort_unstable();
+            hasher = hasher
+                .chain(b"signers")
+                .chain((signers.len() as u64).to_be_bytes());
+            for signer in signers {
+                hasher = hasher.chain(signer.to_be_bytes());
+            }
+        }
         // here we deviate from the spec, by introducing  non-deterministic element (random number)
         // to the nonce
-        let r_i = Sha512::new()
-            .chain_scalar(&keys.keypair.expanded_private_key.prefix)
+        let r_i = hasher
             .chain(message)
             .chain(rng.gen::<[u8; 32]>())
             .result_scalar();
@@ -203,6 +301,19 @@ impl EphemeralKey {
         }
     }
 
+    // r sampled uniformly from `rng` alone, independent of the key prefix and the message,
+    // for parties that no longer trust their deterministic derivation
+    pub fn ephemeral_key_create_random_rng(index: u16, rng: &mut impl Rng) -> EphemeralKey {
+        let r_i = random_scalar_rng(rng);
+        let R_i = Point::generator() * &r_i;
+
+        EphemeralKey {
+            r_i,
+            R_i,
+            party_index: index,
+        }
+    }
+
     pub fn phase1_broadcast(&self) -> (KeyGenBroadcastMessage1, BigInt) {
         self.phase1_broadcast_rng(&mut thread_rng())
     }
@@ -226,6 +337,41 @@ impl EphemeralKey {
         bc1_vec: &[KeyGenBroadcastMessage1],
         parties: &[u16],
     ) -> Result<(VerifiableSS<Ed25519>, SecretShares<Ed25519>), Error> {
+        Self::verify_phase1_decommitments(params, blind_vec, R_vec, bc1_vec)?;
+        Ok(VerifiableSS::share_at_indices(
+            params.threshold,
+            params.share_count,
+            &self.r_i,
+            parties,
+        ))
+    }
+
+    // same as phase1_verify_com_phase2_distribute, but the sharing polynomial is sampled from `rng`
+    pub fn phase1_verify_com_phase2_distribute_rng(
+        &self,
+        params: &Parameters,
+        blind_vec: &[BigInt],
+        R_vec: &[Point<Ed25519>],
+        bc1_vec: &[KeyGenBroadcastMessage1],
+        parties: &[u16],
+        rng: &mut impl Rng,
+    ) -> Result<(VerifiableSS<Ed25519>, Vec<Scalar<Ed25519>>), Error> {
+        Self::verify_phase1_decommitments(params, blind_vec, R_vec, bc1_vec)?;
+        Ok(share_at_indices_rng(
+            params.threshold,
+            params.share_count,
+            &self.r_i,
+            parties,
+            rng,
+        ))
+    }
+
+    fn verify_phase1_decommitments(
+        params: &Parameters,
+        blind_vec: &[BigInt],
+        R_vec: &[Point<Ed25519>],
+        bc1_vec: &[KeyGenBroadcastMessage1],
+    ) -> Result<(), Error> {
         // test length:
         assert!(
             blind_vec.len() > usize::from(params.threshold)
@@ -240,27 +386,10 @@ impl EphemeralKey {
                 && R_vec.len() <= usize::from(params.share_count)
         );
         // test decommitments
-        let correct_key_correct_decom_all = R_vec
-            .iter()
-            .zip(blind_vec.iter())
-            .zip(bc1_vec.iter())
-            .all(|((R, blind), comm)| {
-                HashCommitment::<Sha512>::create_commitment_with_user_defined_randomness(
-                    &R.y_coord().unwrap(),
-                    blind,
-                ) == comm.com
-            });
-
-        if !correct_key_correct_decom_all {
-            return Err(InvalidKey);
+        match find_invalid_decommitment(blind_vec, R_vec, bc1_vec) {
+            Some(_) => Err(InvalidKey),
+            None => Ok(()),
         }
-
-        Ok(VerifiableSS::share_at_indices(
-            params.threshold,
-            params.share_count,
-            &self.r_i,
-            parties,
-        ))
     }
 
     pub fn phase2_verify_vss_construct_keypair(
@@ -284,15 +413,7 @@ impl EphemeralKey {
                 && vss_scheme_vec.len() <= usize::from(params.share_count)
         );
 
-        let correct_ss_verify = vss_scheme_vec
-            .iter()
-            .zip(secret_shares_vec.iter())
-            .zip(R_vec.iter())
-            .all(|((vss_scheme, secret_share), R)| {
-                vss_scheme.validate_share(secret_share, index).is_ok()
-                    && &vss_scheme.commitments[0] == R
-            });
-        if !correct_ss_verify {
+        if find_invalid_share(vss_scheme_vec, secret_shares_vec, R_vec, index).is_some() {
             return Err(InvalidSS);
         }
 
@@ -310,11 +431,30 @@ impl LocalSig {
         message: &[u8],
         local_ephemaral_key: &EphemeralSharedKeys,
         local_private_key: &SharedKeys,
+    ) -> LocalSig {
+        let k = Signature::k(&local_ephemaral_key.R, &local_private_key.y, message);
+        Self::compute_with_challenge(k, local_ephemaral_key, local_private_key)
+    }
+
+    // same as compute, with the challenge hashed by `H` (see Signature::k_with_hash)
+    pub fn compute_with_hash<H: Digest>(
+        message: &[u8],
+        local_ephemaral_key: &EphemeralSharedKeys,
+        local_private_key: &SharedKeys,
+    ) -> LocalSig {
+        let k = Signature::k_with_hash::<H>(&local_ephemaral_key.R, &local_private_key.y, message);
+        Self::compute_with_challenge(k, local_ephemaral_key, local_private_key)
+    }
+
+    // same as compute, for callers that derived the challenge k = H(R||A||M) themselves
+    pub fn compute_with_challenge(
+        k: Scalar<Ed25519>,
+        local_ephemaral_key: &EphemeralSharedKeys,
+        local_private_key: &SharedKeys,
     ) -> LocalSig {
         let r_i = local_ephemaral_key.r_i.clone();
         let s_i = local_private_key.x_i.clone();
 
-        let k = Signature::k(&local_ephemaral_key.R, &local_private_key.y, message);
         let gamma_i = r_i + &k * s_i;
 
         LocalSig { gamma_i, k }
@@ -364,9 +504,9 @@ impl LocalSig {
                 .zip(parties_index_vec.iter())
                 .all(|(gamma, &party_index)| {
                     let gamma_i_g = &gamma.gamma_i * g;
-                    vss_sum
-                        .validate_share_public(&gamma_i_g, party_index + 1)
-                        .is_ok()
+                    party_index.checked_add(1).is_some_and(|point| {
+                        vss_sum.validate_share_public(&gamma_i_g, point).is_ok()
+                    })
                 });
 
         match correct_ss_verify {
@@ -376,6 +516,165 @@ impl LocalSig {
     }
 }
 
+/// Position of the first party whose phase 1 decommitment (point, blind factor) does not
+/// open its broadcast commitment.
+pub fn find_invalid_decommitment(
+    blind_vec: &[BigInt],
+    point_vec: &[Point<Ed25519>],
+    bc1_vec: &[KeyGenBroadcastMessage1],
+) -> Option<usize> {
+    let n = point_vec.len().min(blind_vec.len()).min(bc1_vec.len());
+    first_failing(n, |i| {
+        HashCommitment::<Sha512>::create_commitment_with_user_defined_randomness(
+            &point_vec[i].y_coord().unwrap(),
+            &blind_vec[i],
+        ) == bc1_vec[i].com
+    })
+}
+
+/// Position of the first party whose secret share for `index` does not match its VSS
+/// commitments, or whose VSS does not commit to its broadcast point.
+pub fn find_invalid_share(
+    vss_scheme_vec: &[VerifiableSS<Ed25519>],
+    secret_shares_vec: &[Scalar<Ed25519>],
+    point_vec: &[Point<Ed25519>],
+    index: u16,
+) -> Option<usize> {
+    let n = vss_scheme_vec
+        .len()
+        .min(secret_shares_vec.len())
+        .min(point_vec.len());
+    first_failing(n, |i| {
+        vss_scheme_vec[i]
+            .validate_share(&secret_shares_vec[i], index)
+            .is_ok()
+            && vss_scheme_vec[i].commitments[0] == point_vec[i]
+    })
+}
+
+// The per-party checks are independent; with the `rayon` feature they run on the
+// current rayon pool. position_first keeps the reported party deterministic.
+#[cfg(feature = "rayon")]
+fn first_failing(n: usize, check: impl Fn(usize) -> bool + Sync + Send) -> Option<usize> {
+    use rayon::prelude::*;
+    (0..n).into_par_iter().position_first(|i| !check(i))
+}
+
+#[cfg(not(feature = "rayon"))]
+fn first_failing(n: usize, check: impl Fn(usize) -> bool) -> Option<usize> {
+    (0..n).position(|i| !check(i))
+}
+
+// Feldman VSS of `secret` at the given indices, like VerifiableSS::share_at_indices
+// but with the polynomial coefficients drawn from `rng`.
+fn share_at_indices_rng(
+    t: u16,
+    n: u16,
+    secret: &Scalar<Ed25519>,
+    index_vec: &[u16],
+    rng: &mut impl Rng,
+) -> (VerifiableSS<Ed25519>, Vec<Scalar<Ed25519>>) {
+    assert_eq!(usize::from(n), index_vec.len());
+
+    let mut coefficients = vec![secret.clone()];
+    coefficients.extend((0..t).map(|_| random_scalar_rng(rng)));
+    let polynomial = Polynomial::from_coefficients(coefficients);
+    let shares = polynomial
+        .evaluate_many_bigint(index_vec.iter().cloned())
+        .collect();
+    let g = Point::generator();
+    let commitments = polynomial
+        .coefficients()
+        .iter()
+        .map(|coef| g * coef)
+        .collect();
+    (
+        VerifiableSS {
+            parameters: ShamirSecretSharing {
+                threshold: t,
+                share_count: n,
+            },
+            commitments,
+        },
+        shares,
+    )
+}
+
+// Uniform scalar from 64 bytes of `rng`, reduced mod l
+fn random_scalar_rng(rng: &mut impl Rng) -> Scalar<Ed25519> {
+    let mut bytes = [0u8; 64];
+    rng.fill_bytes(&mut bytes);
+    Scalar::from_bigint(&BigInt::from_bytes(&bytes))
+}
+
+// Enrolling a party at a new index j into an existing sharing, leaving y unchanged.
+// Any t + 1 helpers S determine f(j) = sum_{i in S} lambda_i(j) * x_i. Each helper
+// splits its term into |S| random additive pieces, one per helper, and every helper
+// forwards only the sum of the pieces it received to the new party; those sums add
+// up to f(j) without the new party or any helper seeing another helper's term.
+impl SharedKeys {
+    /// This helper's pieces for enrolling `new_party_index`, the k-th one for `helpers[k]`.
+    /// All indices are keygen party indices, i.e. 1-based share points, and `helpers`
+    /// must include `party_index`.
+    pub fn enrollment_pieces_rng(
+        &self,
+        party_index: u16,
+        helpers: &[u16],
+        new_party_index: u16,
+        rng: &mut impl Rng,
+    ) -> Result<Vec<Scalar<Ed25519>>, Error> {
+        let term = lagrange_coefficient_at(party_index, helpers, new_party_index)? * &self.x_i;
+        let mut pieces: Vec<Scalar<Ed25519>> =
+            (1..helpers.len()).map(|_| random_scalar_rng(rng)).collect();
+        let last = pieces.iter().fold(term, |acc, piece| acc - piece);
+        pieces.push(last);
+        Ok(pieces)
+    }
+}
+
+// lambda_i(j) = prod_{k in S, k != i} (j - k) / (i - k), for distinct non-zero points
+// with i in S and j outside it
+fn lagrange_coefficient_at(i: u16, helpers: &[u16], j: u16) -> Result<Scalar<Ed25519>, Error> {
+    let distinct = helpers
+        .iter()
+        .enumerate()
+        .all(|(n, k)| *k != 0 && !helpers[..n].contains(k));
+    if !distinct || !helpers.contains(&i) || j == 0 || helpers.contains(&j) {
+        return Err(InvalidSS);
+    }
+    let (i, j) = (Scalar::<Ed25519>::from(i), Scalar::<Ed25519>::from(j));
+    let (numerator, denominator) = helpers
+        .iter()
+        .map(|k| Scalar::<Ed25519>::from(*k))
+        .filter(|k| *k != i)
+        .fold(
+            (Scalar::<Ed25519>::from(1), Scalar::<Ed25519>::from(1)),
+            |(num, den), k| (num * (&j - &k), den * (&i - &k)),
+        );
+    Ok(numerator * denominator.invert().ok_or(InvalidSS)?)
+}
+
+/// The share of a newly enrolled party: the sum of the helpers' forwarded sums, checked
+/// against the keygen VSS commitments evaluated at `new_party_index`, so a wrong
+/// contribution is caught here rather than at signing time.
+pub fn enroll_party_share(
+    new_party_index: u16,
+    forwarded_sums: &[Scalar<Ed25519>],
+    vss_scheme_vec: &[VerifiableSS<Ed25519>],
+) -> Result<Scalar<Ed25519>, Error> {
+    let x_j = forwarded_sums
+        .iter()
+        .fold(Scalar::zero(), |acc, sum| acc + sum);
+    let expected = vss_scheme_vec
+        .iter()
+        .map(|vss| vss.get_point_commitment(new_party_index))
+        .fold(Point::zero(), |acc, point| acc + point);
+    if Point::generator() * &x_j != expected {
+        return Err(InvalidSS);
+    }
+    Ok(x_j)
+}
+
 pub fn generate(
     vss_sum_local_sigs: &VerifiableSS<Ed25519>,
     local_sig_vec: &[LocalSig],
@@ -387,8 +686,65 @@ pub fn generate(
         .iter()
         .map(|sig| sig.gamma_i.clone())
         .collect();
-    let s = vss_sum_local_sigs.reconstruct(&parties_index_vec[0..reconstruct_limit], &gamma_vec);
+    let indices = &parties_index_vec[0..reconstruct_limit];
+    // when every signer is needed (n' = t + 1) all shares take part, so the full-set path applies
+    let s = if parties_index_vec.len() == reconstruct_limit {
+        let s = reconstruct_full_set(indices, &gamma_vec);
+        debug_assert!(s == vss_sum_local_sigs.reconstruct(indices, &gamma_vec));
+        s
+    } else {
+        vss_sum_local_sigs.reconstruct(indices, &gamma_vec)
+    };
     Signature { s, R }
 }
 
+// Lagrange interpolation at zero over the points indices + 1. The shares are
+// Shamir shares, so the coefficients are not 1 even when everyone signs; but with
+// the whole set known up front every coefficient
+//   lambda_i = prod_{j != i} x_j / prod_{j != i} (x_j - x_i)
+//            = (prod_j x_j) / (x_i * prod_{j != i} (x_j - x_i))
+// shares the same numerator, and the denominators are inverted in one batch
+// instead of once per party.
+fn reconstruct_full_set(indices: &[u16], shares: &[Scalar<Ed25519>]) -> Scalar<Ed25519> {
+    let points: Vec<Scalar<Ed25519>> = indices
+        .iter()
+        .map(|i| Scalar::from(u64::from(*i) + 1))
+        .collect();
+    let numerator = points.iter().fold(Scalar::from(1), |acc, x| acc * x);
+    let denominators: Vec<Scalar<Ed25519>> = points
+        .iter()
+        .enumerate()
+        .map(|(i, x_i)| {
+            points
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .fold(x_i.clone(), |acc, (_, x_j)| acc * (x_j - x_i))
+        })
+        .collect();
+
+    // batch inversion: prefix products, one inversion, then walk back
+    let mut prefix = Vec::with_capacity(denominators.len());
+    let mut acc = Scalar::<Ed25519>::from(1);
+    for d in &denominators {
+        prefix.push(acc.clone());
+        acc = acc * d;
+    }
+    let mut inv = acc
+        .invert()
+        .expect("interpolation points are distinct and non-zero");
+    let mut inverses = vec![Scalar::<Ed25519>::zero(); denominators.len()];
+    for i in (0..denominators.len()).rev() {
+        inverses[i] = &inv * &prefix[i];
+        inv = inv * &denominators[i];
+    }
+
+    shares
+        .iter()
+        .zip(inverses.iter())
+        .fold(Scalar::zero(), |acc, (share, inv_d)| {
+            acc + &numerator * inv_d * share
+        })
+}
+
 mod test;
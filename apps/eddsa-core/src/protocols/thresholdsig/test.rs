@@ -13,7 +13,7 @@
 #[cfg(test)]
 mod tests {
     use curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
-    use curv::elliptic::curves::{Ed25519, Point};
+    use curv::elliptic::curves::{Ed25519, Point, Scalar};
     use itertools::{izip, Itertools};
     use protocols::tests::{deterministic_fast_rand, verify_dalek};
     use protocols::thresholdsig::{
@@ -151,6 +151,54 @@ mod tests {
         assert!(verify_sig.is_ok());
     }
 
+    #[test]
+    fn test_n_of_n_full_set_matches_general_reconstruction() {
+        let mut rng = deterministic_fast_rand("test_n_of_n_full_set", None);
+        for _i in 0..16 {
+            // 3-of-3: every party signs, so generate takes the full-set path
+            let t = 2u16;
+            let n = 3u16;
+            let parties_index_vec: [u16; 3] = [0, 1, 2];
+            let parties_points_vec: Vec<_> = parties_index_vec.iter().map(|i| i + 1).collect();
+
+            let (priv_keys_vec, priv_shared_keys_vec, Y, key_gen_vss_vec) =
+                keygen_t_n_parties(t, n, &parties_points_vec, &mut rng);
+            let message: [u8; 4] = [79, 77, 69, 82];
+            let (eph_shared_keys_vec, R, eph_vss_vec) = eph_keygen_t_n_parties(
+                t,
+                n,
+                &parties_points_vec,
+                &priv_keys_vec,
+                &message,
+                &mut rng,
+            );
+            let local_sig_vec = (0..usize::from(n))
+                .map(|i| {
+                    LocalSig::compute(&message, &eph_shared_keys_vec[i], &priv_shared_keys_vec[i])
+                })
+                .collect::<Vec<LocalSig>>();
+            let vss_sum_local_sigs = LocalSig::verify_local_sigs(
+                &local_sig_vec,
+                &parties_index_vec,
+                &key_gen_vss_vec,
+                &eph_vss_vec,
+            )
+            .unwrap();
+
+            let signature =
+                thresholdsig::generate(&vss_sum_local_sigs, &local_sig_vec, &parties_index_vec, R);
+            let gamma_vec: Vec<_> = local_sig_vec
+                .iter()
+                .map(|sig| sig.gamma_i.clone())
+                .collect();
+            assert_eq!(
+                signature.s,
+                vss_sum_local_sigs.reconstruct(&parties_index_vec, &gamma_vec)
+            );
+            assert!(signature.verify(&message, &Y).is_ok());
+        }
+    }
+
     #[test]
     fn test_t2_n5_sign_with_4() {
         let mut rng = deterministic_fast_rand("test_t2_n5_sign_with_4", None);
@@ -213,6 +261,76 @@ mod tests {
         assert!(verify_sig.is_ok());
     }
 
+    #[test]
+    fn test_enroll_fourth_party_into_t1_n3() {
+        let mut rng = deterministic_fast_rand("test_enroll_fourth_party", None);
+        for _i in 0..16 {
+            let t = 1u16;
+            let key_gen_parties_points_vec = [1u16, 2, 3];
+            let (mut priv_keys_vec, mut priv_shared_keys_vec, Y, key_gen_vss_vec) =
+                keygen_t_n_parties(t, 3, &key_gen_parties_points_vec, &mut rng);
+
+            // helpers 1 and 3 enroll party 4; helper k forwards the sum of the pieces it received
+            let helpers = [1u16, 3];
+            let pieces: Vec<Vec<_>> = helpers
+                .iter()
+                .map(|&i| {
+                    priv_shared_keys_vec[usize::from(i - 1)]
+                        .enrollment_pieces_rng(i, &helpers, 4, &mut rng)
+                        .unwrap()
+                })
+                .collect();
+            let forwarded_sums: Vec<_> = (0..helpers.len())
+                .map(|k| pieces.iter().fold(Scalar::zero(), |acc, p| acc + &p[k]))
+                .collect();
+            let x_4 =
+                thresholdsig::enroll_party_share(4, &forwarded_sums, &key_gen_vss_vec).unwrap();
+            let mut tampered = forwarded_sums.clone();
+            tampered[0] = &tampered[0] + Scalar::from(1);
+            assert!(thresholdsig::enroll_party_share(4, &tampered, &key_gen_vss_vec).is_err());
+
+            let shared_keys = SharedKeys {
+                y: Y.clone(),
+                x_i: x_4,
+                prefix: Scalar::random(),
+            };
+            priv_keys_vec.push(Keys::phase1_create_from_shared_keys(4, &shared_keys));
+            priv_shared_keys_vec.push(shared_keys);
+
+            // the new party co-signs with party 2
+            let parties_index_vec: [u16; 2] = [1, 3];
+            let parties_points_vec: Vec<_> = parties_index_vec.iter().map(|i| i + 1).collect();
+            let message: [u8; 4] = [79, 77, 69, 82];
+            let (eph_shared_keys_vec, R, eph_vss_vec) = eph_keygen_t_n_parties(
+                t,
+                2,
+                &parties_points_vec,
+                &priv_keys_vec,
+                &message,
+                &mut rng,
+            );
+            let local_sig_vec: Vec<_> = (0..2)
+                .map(|i| {
+                    LocalSig::compute(
+                        &message,
+                        &eph_shared_keys_vec[i],
+                        &priv_shared_keys_vec[usize::from(parties_index_vec[i])],
+                    )
+                })
+                .collect();
+            let vss_sum_local_sigs = LocalSig::verify_local_sigs(
+                &local_sig_vec,
+                &parties_index_vec,
+                &key_gen_vss_vec,
+                &eph_vss_vec,
+            )
+            .unwrap();
+            let signature =
+                thresholdsig::generate(&vss_sum_local_sigs, &local_sig_vec, &parties_index_vec, R);
+            assert!(signature.verify(&message, &Y).is_ok());
+        }
+    }
+
     pub fn keygen_t_n_parties(
         t: u16,
         n: u16,